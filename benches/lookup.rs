@@ -0,0 +1,64 @@
+//! Simple timing of cell lookups and iteration on a large grid.
+//! Run with "cargo bench".
+
+use gridstore::{Grid, Scalar};
+use std::hint::black_box;
+use std::time::Instant;
+
+const SIZE: usize = 256;
+const PASSES: usize = 20;
+
+fn main() {
+    let grid = Grid::new_with_coords(256.0, 256.0, SIZE, SIZE, true, |col, row| col ^ row);
+
+    let start = Instant::now();
+    let mut sum = 0;
+    for _ in 0..PASSES {
+        for col in 0..SIZE {
+            for row in 0..SIZE {
+                sum += grid
+                    .get_cell_by_indices(black_box(col), black_box(row))
+                    .unwrap();
+            }
+        }
+    }
+    report("get_cell_by_indices", start, sum);
+
+    let start = Instant::now();
+    let mut sum = 0;
+    for _ in 0..PASSES {
+        for col in 0..SIZE {
+            for row in 0..SIZE {
+                let x = col as Scalar - 127.5;
+                let y = row as Scalar - 127.5;
+                sum += grid.get_cell(black_box(x), black_box(y)).unwrap();
+            }
+        }
+    }
+    report("get_cell", start, sum);
+
+    let start = Instant::now();
+    let mut sum = 0;
+    for _ in 0..PASSES {
+        sum += grid.iter_all_cells().sum::<usize>();
+    }
+    report("iter_all_cells", start, sum);
+
+    let start = Instant::now();
+    let mut sum = 0;
+    for _ in 0..PASSES {
+        sum += grid
+            .iter_cells_in_rect(-64.0, -64.0, 64.0, 64.0)
+            .sum::<usize>();
+    }
+    report("iter_cells_in_rect", start, sum);
+}
+
+fn report(name: &str, start: Instant, result: usize) {
+    let elapsed = start.elapsed();
+    let per_pass = elapsed / PASSES as u32;
+    println!(
+        "{:<24}{:>12.3?} per pass (checksum {})",
+        name, per_pass, result
+    );
+}
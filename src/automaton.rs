@@ -0,0 +1,134 @@
+use super::*;
+
+/// Read-only view of a cell's surroundings in the previous generation, passed to the
+/// "step" rule.
+#[derive(Debug)]
+pub struct NeighborView<'a, V> {
+    grid: &'a Grid<V>,
+    col: usize,
+    row: usize,
+}
+
+// Manual impls, since derives would require V: Clone / V: Copy.
+impl<'a, V> Clone for NeighborView<'a, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, V> Copy for NeighborView<'a, V> {}
+
+impl<'a, V> NeighborView<'a, V> {
+    /// Column of the cell being updated.
+    pub fn col(&self) -> usize {
+        self.col
+    }
+
+    /// Row of the cell being updated.
+    pub fn row(&self) -> usize {
+        self.row
+    }
+
+    /// Returns the cell at a (column, row) offset from the one being updated, or None if it
+    /// is outside the grid.
+    pub fn get(&self, d_col: isize, d_row: isize) -> Option<&'a V> {
+        let col = self.col.checked_add_signed(d_col)?;
+        let row = self.row.checked_add_signed(d_row)?;
+        self.grid.get_cell_by_indices(col, row)
+    }
+
+    /// Returns an iterator that yields ((column offset, row offset), value) for the 8
+    /// surrounding cells that are inside the grid, row by row starting at the bottom row.
+    pub fn iter(&self) -> impl Iterator<Item = ((isize, isize), &'a V)> + 'a {
+        let view = *self;
+        Connectivity::Eight
+            .offsets()
+            .iter()
+            .filter_map(move |&(d_col, d_row)| Some(((d_col, d_row), view.get(d_col, d_row)?)))
+    }
+
+    /// Returns the number of surrounding cells matching "pred". Cells outside the grid
+    /// don't count.
+    pub fn count<P>(&self, mut pred: P) -> usize
+    where
+        P: FnMut(&V) -> bool,
+    {
+        self.iter().filter(|(_, cell)| pred(cell)).count()
+    }
+}
+
+/// Owns a Grid and the buffer its next generation is built in, so stepping doesn't allocate
+/// after the first generation. Created with "Automaton::new" or "Grid::into_automaton".
+#[derive(Debug, Clone)]
+pub struct Automaton<V> {
+    grid: Grid<V>,
+    scratch: Vec<V>,
+}
+
+impl<V> Automaton<V> {
+    /// Returns an automaton starting from "grid" as the current generation.
+    pub fn new(grid: Grid<V>) -> Self {
+        Self {
+            grid,
+            scratch: Vec::new(),
+        }
+    }
+
+    /// Advances by one generation: every cell is replaced by the result of "rule", which
+    /// receives the cell and a view of its neighbors. All cells see the previous
+    /// generation, never partially updated values.
+    pub fn step<F>(&mut self, rule: F)
+    where
+        F: FnMut(&V, NeighborView<'_, V>) -> V,
+    {
+        self.grid.step_with(&mut self.scratch, rule);
+    }
+
+    /// The current generation.
+    pub fn grid(&self) -> &Grid<V> {
+        &self.grid
+    }
+
+    /// Mutable access to the current generation, i.e. to seed or edit cells between steps.
+    pub fn grid_mut(&mut self) -> &mut Grid<V> {
+        &mut self.grid
+    }
+
+    /// Returns the current generation, dropping the scratch buffer.
+    pub fn into_grid(self) -> Grid<V> {
+        self.grid
+    }
+}
+
+impl<V> Grid<V> {
+    /// Moves the grid into an Automaton, which reuses its buffer across steps.
+    pub fn into_automaton(self) -> Automaton<V> {
+        Automaton::new(self)
+    }
+
+    /// Advances the grid by one generation, building it in "scratch": every cell is
+    /// replaced by the result of "rule", which receives the cell and a view of its
+    /// neighbors. All cells see the previous generation, never partially updated values.
+    /// The previous generation is left in "scratch", so keeping the same buffer between
+    /// steps avoids allocating after the first one. See "Automaton" for a type that keeps
+    /// it for you.
+    pub fn step_with<F>(&mut self, scratch: &mut Vec<V>, mut rule: F)
+    where
+        F: FnMut(&V, NeighborView<'_, V>) -> V,
+    {
+        scratch.clear();
+        scratch.reserve(self.data.len());
+        for col in 0..self.columns {
+            for row in 0..self.rows {
+                let view = NeighborView {
+                    grid: self,
+                    col,
+                    row,
+                };
+                scratch.push(rule(&self.data[self.flat_index(col, row)], view));
+            }
+        }
+        mem::swap(&mut self.data, scratch);
+        self.mark_all_dirty();
+    }
+}
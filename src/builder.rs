@@ -0,0 +1,125 @@
+use super::*;
+
+/// Builds a Grid step by step, making the construction arguments explicit.
+/// ```
+/// use gridstore::GridBuilder;
+///
+/// let grid = GridBuilder::new()
+///     .size(200.0, 100.0)
+///     .cells(20, 10)
+///     .pivot(0.5, 0.5)
+///     .build_default::<u8>()
+///     .unwrap();
+///
+/// assert_eq!(grid.left(), -100.0);
+/// assert_eq!(grid.get_cell_coords(0.0, 0.0), Some((10, 5)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridBuilder {
+    width: Scalar,
+    height: Scalar,
+    columns: usize,
+    rows: usize,
+    pivot_x: Scalar,
+    pivot_y: Scalar,
+    x: Scalar,
+    y: Scalar,
+}
+
+impl Default for GridBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GridBuilder {
+    /// Returns a builder with no size and no cells, and the pivot at the bottom-left corner
+    /// placed at the origin.
+    pub fn new() -> Self {
+        Self {
+            width: 0.0,
+            height: 0.0,
+            columns: 0,
+            rows: 0,
+            pivot_x: 0.0,
+            pivot_y: 0.0,
+            x: 0.0,
+            y: 0.0,
+        }
+    }
+
+    /// Physical width and height.
+    pub fn size(self, width: Scalar, height: Scalar) -> Self {
+        Self {
+            width,
+            height,
+            ..self
+        }
+    }
+
+    /// Number of columns and rows.
+    pub fn cells(self, columns: usize, rows: usize) -> Self {
+        Self {
+            columns,
+            rows,
+            ..self
+        }
+    }
+
+    /// Normalized pivot, where (0.0, 0.0) is the bottom-left corner and (0.5, 0.5) the center.
+    pub fn pivot(self, x: Scalar, y: Scalar) -> Self {
+        Self {
+            pivot_x: x,
+            pivot_y: y,
+            ..self
+        }
+    }
+
+    /// Pivot from one of the named presets.
+    pub fn pivot_preset(self, pivot: Pivot) -> Self {
+        let (x, y) = pivot.normalized();
+        self.pivot(x, y)
+    }
+
+    /// Position of the pivot in the world. See "Grid::set_position".
+    pub fn position(self, x: Scalar, y: Scalar) -> Self {
+        Self { x, y, ..self }
+    }
+
+    /// Returns a Grid pre-filled with the result of function "func".
+    pub fn build_with<V, F>(self, mut func: F) -> Result<Grid<V>, GridError>
+    where
+        F: FnMut() -> V,
+    {
+        Grid::<V>::validate_args(self.width, self.height, self.columns, self.rows)?;
+        for (field, value) in [
+            ("pivot_x", self.pivot_x),
+            ("pivot_y", self.pivot_y),
+            ("x", self.x),
+            ("y", self.y),
+        ] {
+            if !value.is_finite() {
+                return Err(GridError::NonFiniteValue { field });
+            }
+        }
+        let mut grid = Grid::from_fn(
+            self.width,
+            self.height,
+            self.columns,
+            self.rows,
+            self.width * self.pivot_x,
+            self.height * self.pivot_y,
+            |_, _| func(),
+        );
+        grid.set_position(self.x, self.y);
+        Ok(grid)
+    }
+
+    /// Returns a Grid pre-filled with default values.
+    pub fn build_default<V>(self) -> Result<Grid<V>, GridError>
+    where
+        V: Default,
+    {
+        self.build_with(V::default)
+    }
+}
@@ -0,0 +1,160 @@
+use super::*;
+use bytemuck::Pod;
+
+// Identifies a Grid snapshot and its format version.
+const MAGIC: [u8; 4] = *b"GRDS";
+const VERSION: u16 = 1;
+// Magic, version, cell size, columns, rows, then width, height, offset_x, offset_y, x, y
+// as f64, so snapshots don't depend on the "f64" feature.
+const HEADER_LEN: usize = 4 + 2 + (4 * 3) + (8 * 6);
+
+/// Plain data cell types that can be converted to and from little-endian, so binary
+/// snapshots transfer between targets. Implemented for the primitive numbers and arrays
+/// of them.
+pub trait LittleEndian: Pod {
+    /// Returns the value with its bytes in little-endian order.
+    fn to_le(self) -> Self;
+
+    /// Returns the value read from little-endian bytes.
+    fn from_le(value: Self) -> Self;
+}
+
+macro_rules! impl_little_endian_int {
+    ($($ty:ty),*) => {
+        $(
+            impl LittleEndian for $ty {
+                fn to_le(self) -> Self {
+                    <$ty>::to_le(self)
+                }
+
+                fn from_le(value: Self) -> Self {
+                    <$ty>::from_le(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_little_endian_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+macro_rules! impl_little_endian_float {
+    ($($ty:ty),*) => {
+        $(
+            impl LittleEndian for $ty {
+                fn to_le(self) -> Self {
+                    <$ty>::from_bits(self.to_bits().to_le())
+                }
+
+                fn from_le(value: Self) -> Self {
+                    <$ty>::from_bits(LittleEndian::from_le(value.to_bits()))
+                }
+            }
+        )*
+    };
+}
+
+impl_little_endian_float!(f32, f64);
+
+impl<T, const N: usize> LittleEndian for [T; N]
+where
+    T: LittleEndian,
+    [T; N]: Pod,
+{
+    fn to_le(self) -> Self {
+        self.map(LittleEndian::to_le)
+    }
+
+    fn from_le(value: Self) -> Self {
+        value.map(LittleEndian::from_le)
+    }
+}
+
+// Binary snapshots for plain data cell types.
+impl<V> Grid<V>
+where
+    V: LittleEndian,
+{
+    /// Appends a binary snapshot of the grid to "out". Everything is little-endian: the
+    /// header, then each cell row by row starting at the bottom row.
+    #[allow(clippy::unnecessary_cast)]
+    pub fn to_bytes(&self, out: &mut Vec<u8>) {
+        out.reserve(HEADER_LEN + (self.columns * self.rows * mem::size_of::<V>()));
+        out.extend_from_slice(&MAGIC);
+        out.extend_from_slice(&VERSION.to_le_bytes());
+        out.extend_from_slice(&(mem::size_of::<V>() as u32).to_le_bytes());
+        out.extend_from_slice(&(self.columns as u32).to_le_bytes());
+        out.extend_from_slice(&(self.rows as u32).to_le_bytes());
+        for value in [
+            self.width,
+            self.height,
+            self.offset_x,
+            self.offset_y,
+            self.x,
+            self.y,
+        ] {
+            out.extend_from_slice(&(value as f64).to_le_bytes());
+        }
+        for cell in self.iter_all_cells() {
+            out.extend_from_slice(bytemuck::bytes_of(&cell.to_le()));
+        }
+    }
+
+    /// Creates a grid from a snapshot produced by "to_bytes".
+    #[allow(clippy::unnecessary_cast)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, GridError> {
+        let header = bytes.get(..HEADER_LEN).ok_or(GridError::TruncatedData)?;
+        if header[..4] != MAGIC || u16::from_le_bytes([header[4], header[5]]) != VERSION {
+            return Err(GridError::InvalidHeader);
+        }
+        let (words, floats) = header[6..].split_at(4 * 3);
+        let mut words = words
+            .chunks_exact(4)
+            .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]) as usize);
+        let mut floats = floats.chunks_exact(8).map(|chunk| {
+            let mut word = [0; 8];
+            word.copy_from_slice(chunk);
+            f64::from_le_bytes(word) as Scalar
+        });
+        let cell_size = words.next().unwrap_or_default();
+        let columns = words.next().unwrap_or_default();
+        let rows = words.next().unwrap_or_default();
+        let mut next_float = || floats.next().unwrap_or_default();
+        let width = next_float();
+        let height = next_float();
+        let offset_x = next_float();
+        let offset_y = next_float();
+        let x = next_float();
+        let y = next_float();
+        if cell_size != mem::size_of::<V>() {
+            return Err(GridError::InvalidHeader);
+        }
+        Self::validate_args(width, height, columns, rows)?;
+
+        let cells = &bytes[HEADER_LEN..];
+        let expected = columns
+            .checked_mul(rows)
+            .and_then(|count| count.checked_mul(cell_size))
+            .ok_or(GridError::InvalidHeader)?;
+        if cells.len() < expected {
+            return Err(GridError::TruncatedData);
+        }
+        let mut grid = Self::from_fn(
+            width,
+            height,
+            columns,
+            rows,
+            offset_x,
+            offset_y,
+            |col, row| {
+                let start = ((row * columns) + col) * cell_size;
+                V::from_le(bytemuck::pod_read_unaligned(
+                    &cells[start..start + cell_size],
+                ))
+            },
+        );
+        // Assigned directly so a corrupt position is an error instead of a panic
+        (grid.x, grid.y) = (x, y);
+        grid.validate()?;
+        Ok(grid)
+    }
+}
@@ -0,0 +1,139 @@
+use super::*;
+use core::iter::FusedIterator;
+
+/// Compact reference to a cell, created with "Grid::cell_id". Ids are only meaningful for
+/// the grid (or clones of it) they came from, and are invalidated when its number of
+/// columns or rows changes: lookups with a stale id return None.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CellId {
+    index: u32,
+    generation: u32,
+}
+
+// Counter bumped whenever the number of columns or rows changes, so stale CellIds can be
+// detected. Like dirty tracking it is bookkeeping, so it never affects equality.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Generation(u32);
+
+impl PartialEq for Generation {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Generation {
+    // The generation that follows this one.
+    pub(crate) fn next(self) -> Self {
+        Generation(self.0.wrapping_add(1))
+    }
+}
+
+/// Iterator that yields the CellId of each cell overlapping a rectangle, in the same order
+/// as "IterCoords".
+#[derive(Debug, Clone)]
+pub struct IterCellIds {
+    coords: IterCoords,
+    rows: usize,
+    generation: u32,
+}
+
+impl IterCellIds {
+    // Id of the cell at (col, row), which is known to be in range.
+    fn id(&self, (col, row): (usize, usize)) -> CellId {
+        CellId {
+            index: ((col * self.rows) + row) as u32,
+            generation: self.generation,
+        }
+    }
+}
+
+impl Iterator for IterCellIds {
+    type Item = CellId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.coords.next().map(|coords| self.id(coords))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.coords.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.coords.count()
+    }
+}
+
+impl DoubleEndedIterator for IterCellIds {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.coords.next_back().map(|coords| self.id(coords))
+    }
+}
+
+impl ExactSizeIterator for IterCellIds {}
+
+impl FusedIterator for IterCellIds {}
+
+impl<V> Grid<V> {
+    /// Returns the id of the cell at (col, row), or None if it is out of range. Grids with
+    /// more than u32::MAX cells can't create ids.
+    pub fn cell_id(&self, col: usize, row: usize) -> Option<CellId> {
+        let index = u32::try_from(self.raw_index(col, row)?).ok()?;
+        Some(CellId {
+            index,
+            generation: self.generation.0,
+        })
+    }
+
+    /// Returns the (column, row) coordinates of the cell an id refers to, or None if the id
+    /// is stale.
+    pub fn coords_of(&self, id: CellId) -> Option<(usize, usize)> {
+        let index = self.id_index(id)?;
+        Some((index / self.rows, index % self.rows))
+    }
+
+    /// Returns an optional reference to the cell an id refers to. None if the id is stale.
+    pub fn get_by_id(&self, id: CellId) -> Option<&V> {
+        let index = self.id_index(id)?;
+        Some(&self.data[index])
+    }
+
+    /// Returns an optional mutable reference to the cell an id refers to. None if the id
+    /// is stale.
+    pub fn get_by_id_mut(&mut self, id: CellId) -> Option<&mut V> {
+        let index = self.id_index(id)?;
+        self.mark_dirty(index / self.rows, index % self.rows);
+        Some(&mut self.data[index])
+    }
+
+    /// Returns an iterator that yields the CellId of each cell overlapping the provided
+    /// rectangle edges. Empty for grids with more than u32::MAX cells.
+    pub fn iter_ids_in_rect(
+        &self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+    ) -> IterCellIds {
+        let edges = if u32::try_from(self.data.len()).is_ok() {
+            self.get_edges(left, bottom, right, top)
+        } else {
+            None
+        };
+        IterCellIds {
+            coords: Self::coords_in_edges(edges),
+            rows: self.rows,
+            generation: self.generation.0,
+        }
+    }
+
+    // Flat index of the cell an id refers to, if the id is still valid.
+    fn id_index(&self, id: CellId) -> Option<usize> {
+        let index = id.index as usize;
+        (id.generation == self.generation.0 && index < self.data.len()).then_some(index)
+    }
+
+    // Invalidates every CellId created so far.
+    pub(crate) fn bump_generation(&mut self) {
+        self.generation = self.generation.next();
+    }
+}
@@ -0,0 +1,192 @@
+use super::*;
+use core::iter::FusedIterator;
+
+/// A cell's contents together with its physical information, yielded by
+/// "iter_refs_in_rect".
+#[derive(Debug, Clone, Copy)]
+pub struct CellRef<'a, V> {
+    info: CellInfo,
+    value: &'a V,
+}
+
+impl<'a, V> CellRef<'a, V> {
+    /// The (column, row) coordinates of the cell.
+    pub fn coords(&self) -> (usize, usize) {
+        (self.info.col, self.info.row)
+    }
+
+    /// The (left, bottom, right, top) edges of the cell, with the pivot applied.
+    pub fn rect(&self) -> (Scalar, Scalar, Scalar, Scalar) {
+        let info = &self.info;
+        (info.left, info.bottom, info.right, info.top)
+    }
+
+    /// The center of the cell, with the pivot applied.
+    pub fn center(&self) -> (Scalar, Scalar) {
+        (self.info.center_x, self.info.center_y)
+    }
+
+    /// All the physical information about the cell.
+    pub fn info(&self) -> &CellInfo {
+        &self.info
+    }
+
+    /// The contents of the cell.
+    pub fn value(&self) -> &'a V {
+        self.value
+    }
+}
+
+/// Mutable counterpart of CellRef, yielded by "iter_refs_in_rect_mut".
+#[derive(Debug)]
+pub struct CellMut<'a, V> {
+    info: CellInfo,
+    value: &'a mut V,
+}
+
+impl<'a, V> CellMut<'a, V> {
+    /// The (column, row) coordinates of the cell.
+    pub fn coords(&self) -> (usize, usize) {
+        (self.info.col, self.info.row)
+    }
+
+    /// The (left, bottom, right, top) edges of the cell, with the pivot applied.
+    pub fn rect(&self) -> (Scalar, Scalar, Scalar, Scalar) {
+        let info = &self.info;
+        (info.left, info.bottom, info.right, info.top)
+    }
+
+    /// The center of the cell, with the pivot applied.
+    pub fn center(&self) -> (Scalar, Scalar) {
+        (self.info.center_x, self.info.center_y)
+    }
+
+    /// All the physical information about the cell.
+    pub fn info(&self) -> &CellInfo {
+        &self.info
+    }
+
+    /// The contents of the cell.
+    pub fn value(&self) -> &V {
+        self.value
+    }
+
+    /// The contents of the cell, mutably.
+    pub fn value_mut(&mut self) -> &mut V {
+        self.value
+    }
+
+    /// Consumes the handle, returning the mutable reference with the full borrow lifetime.
+    pub fn into_value_mut(self) -> &'a mut V {
+        self.value
+    }
+}
+
+/// Iterator that yields a CellRef for each cell that overlaps the provided rectangle edges.
+#[derive(Debug, Clone)]
+pub struct IterCellRefs<'a, V> {
+    info: IterInfo,
+    grid: &'a Grid<V>,
+}
+
+impl<'a, V> Iterator for IterCellRefs<'a, V> {
+    type Item = CellRef<'a, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let info = self.info.next()?;
+        let value = &self.grid.data[self.grid.flat_index(info.col, info.row)];
+        Some(CellRef { info, value })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.info.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.info.count()
+    }
+}
+
+impl<'a, V> ExactSizeIterator for IterCellRefs<'a, V> {}
+
+impl<'a, V> FusedIterator for IterCellRefs<'a, V> {}
+
+impl<'a, V> IterCellRefs<'a, V> {
+    /// Inverts Y iteration direction
+    pub fn y_down(self) -> Self {
+        Self {
+            info: self.info.y_down(),
+            ..self
+        }
+    }
+}
+
+/// Iterator that yields a CellMut for each cell that overlaps the provided rectangle edges.
+#[derive(Debug)]
+pub struct IterCellMuts<'a, V> {
+    // Both walk the same coordinates in lockstep
+    info: IterInfo,
+    cells: IterGridRectMut<'a, V>,
+}
+
+impl<'a, V> Iterator for IterCellMuts<'a, V> {
+    type Item = CellMut<'a, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let info = self.info.next()?;
+        let value = self.cells.next()?;
+        Some(CellMut { info, value })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.info.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.info.count()
+    }
+}
+
+impl<'a, V> ExactSizeIterator for IterCellMuts<'a, V> {}
+
+impl<'a, V> FusedIterator for IterCellMuts<'a, V> {}
+
+impl<'a, V> IterCellMuts<'a, V> {
+    /// Inverts Y iteration direction
+    pub fn y_down(self) -> Self {
+        Self {
+            info: self.info.y_down(),
+            cells: self.cells.y_down(),
+        }
+    }
+}
+
+impl<V> Grid<V> {
+    /// Returns an iterator that yields a CellRef, with the contents and physical information,
+    /// for each cell that overlaps the provided rectangle edges.
+    pub fn iter_refs_in_rect(
+        &self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+    ) -> IterCellRefs<'_, V> {
+        IterCellRefs {
+            info: self.iter_info_in_rect(left, bottom, right, top),
+            grid: self,
+        }
+    }
+
+    /// Same as "iter_refs_in_rect", yielding a CellMut for each cell.
+    pub fn iter_refs_in_rect_mut(
+        &mut self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+    ) -> IterCellMuts<'_, V> {
+        let info = self.iter_info_in_rect(left, bottom, right, top);
+        let cells = self.iter_mut_with(info.coords.clone());
+        IterCellMuts { info, cells }
+    }
+}
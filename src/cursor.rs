@@ -0,0 +1,146 @@
+use super::*;
+
+// Cursor position, with the flat index cached so moving doesn't recompute it.
+#[derive(Debug, Clone, Copy)]
+struct Position {
+    col: usize,
+    row: usize,
+    index: usize,
+}
+
+impl Position {
+    // Moves by one cell, returning false (and staying put) at the grid's edges.
+    fn step(&mut self, d_col: isize, d_row: isize, columns: usize, rows: usize) -> bool {
+        let (Some(col), Some(row)) = (
+            self.col.checked_add_signed(d_col),
+            self.row.checked_add_signed(d_row),
+        ) else {
+            return false;
+        };
+        if col >= columns || row >= rows {
+            return false;
+        }
+        self.index = (self.index as isize + d_col * rows as isize + d_row) as usize;
+        self.col = col;
+        self.row = row;
+        true
+    }
+}
+
+/// Read-only handle to a single cell that can move to the adjacent cells, created with
+/// "Grid::cursor".
+#[derive(Debug, Clone, Copy)]
+pub struct Cursor<'a, V> {
+    grid: &'a Grid<V>,
+    pos: Position,
+}
+
+impl<'a, V> Cursor<'a, V> {
+    /// The contents of the current cell.
+    pub fn value(&self) -> &'a V {
+        &self.grid.data[self.pos.index]
+    }
+
+    /// The (column, row) coordinates of the current cell.
+    pub fn coords(&self) -> (usize, usize) {
+        (self.pos.col, self.pos.row)
+    }
+
+    /// Moves one row up. Returns false and stays put if already on the top row.
+    pub fn up(&mut self) -> bool {
+        self.step(0, 1)
+    }
+
+    /// Moves one row down. Returns false and stays put if already on the bottom row.
+    pub fn down(&mut self) -> bool {
+        self.step(0, -1)
+    }
+
+    /// Moves one column left. Returns false and stays put if already on the left column.
+    pub fn left(&mut self) -> bool {
+        self.step(-1, 0)
+    }
+
+    /// Moves one column right. Returns false and stays put if already on the right column.
+    pub fn right(&mut self) -> bool {
+        self.step(1, 0)
+    }
+
+    fn step(&mut self, d_col: isize, d_row: isize) -> bool {
+        self.pos
+            .step(d_col, d_row, self.grid.columns, self.grid.rows)
+    }
+}
+
+/// Same as Cursor, with mutable access to the current cell. Created with
+/// "Grid::cursor_mut".
+#[derive(Debug)]
+pub struct CursorMut<'a, V> {
+    grid: &'a mut Grid<V>,
+    pos: Position,
+}
+
+impl<'a, V> CursorMut<'a, V> {
+    /// The contents of the current cell.
+    pub fn value(&self) -> &V {
+        &self.grid.data[self.pos.index]
+    }
+
+    /// The contents of the current cell, mutably.
+    pub fn value_mut(&mut self) -> &mut V {
+        self.grid.mark_dirty(self.pos.col, self.pos.row);
+        &mut self.grid.data[self.pos.index]
+    }
+
+    /// The (column, row) coordinates of the current cell.
+    pub fn coords(&self) -> (usize, usize) {
+        (self.pos.col, self.pos.row)
+    }
+
+    /// Moves one row up. Returns false and stays put if already on the top row.
+    pub fn up(&mut self) -> bool {
+        self.step(0, 1)
+    }
+
+    /// Moves one row down. Returns false and stays put if already on the bottom row.
+    pub fn down(&mut self) -> bool {
+        self.step(0, -1)
+    }
+
+    /// Moves one column left. Returns false and stays put if already on the left column.
+    pub fn left(&mut self) -> bool {
+        self.step(-1, 0)
+    }
+
+    /// Moves one column right. Returns false and stays put if already on the right column.
+    pub fn right(&mut self) -> bool {
+        self.step(1, 0)
+    }
+
+    fn step(&mut self, d_col: isize, d_row: isize) -> bool {
+        self.pos
+            .step(d_col, d_row, self.grid.columns, self.grid.rows)
+    }
+}
+
+impl<V> Grid<V> {
+    /// Returns a Cursor on the cell at the provided indices, or None if they are out of
+    /// range.
+    pub fn cursor(&self, col: usize, row: usize) -> Option<Cursor<'_, V>> {
+        let index = self.raw_index(col, row)?;
+        Some(Cursor {
+            grid: self,
+            pos: Position { col, row, index },
+        })
+    }
+
+    /// Returns a CursorMut on the cell at the provided indices, or None if they are out of
+    /// range.
+    pub fn cursor_mut(&mut self, col: usize, row: usize) -> Option<CursorMut<'_, V>> {
+        let index = self.raw_index(col, row)?;
+        Some(CursorMut {
+            grid: self,
+            pos: Position { col, row, index },
+        })
+    }
+}
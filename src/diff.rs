@@ -0,0 +1,53 @@
+use super::*;
+
+impl<V> Grid<V>
+where
+    V: PartialEq,
+{
+    /// Returns an iterator that yields (column, row, value in this grid, value in "other")
+    /// for every cell where the two grids differ, column by column from the bottom row up.
+    /// Fails if the grids have different column or row counts.
+    pub fn diff<'a>(
+        &'a self,
+        other: &'a Grid<V>,
+    ) -> Result<impl Iterator<Item = (usize, usize, &'a V, &'a V)> + 'a, GridError> {
+        self.check_shape(other)?;
+        let rows = self.rows;
+        Ok(self
+            .data
+            .iter()
+            .zip(&other.data)
+            .enumerate()
+            .filter(|(_, (a, b))| a != b)
+            .map(move |(i, (a, b))| (i / rows, i % rows, a, b)))
+    }
+
+    /// Returns the number of cells where the two grids differ. Fails if the grids have
+    /// different column or row counts.
+    pub fn diff_count(&self, other: &Grid<V>) -> Result<usize, GridError> {
+        self.check_shape(other)?;
+        Ok(self
+            .data
+            .iter()
+            .zip(&other.data)
+            .filter(|(a, b)| a != b)
+            .count())
+    }
+}
+
+impl<V> Grid<V> {
+    /// Stores each (column, row, value) change in its cell, i.e. the output of "diff" with
+    /// the new values. Fails on the first change with indices out of range, keeping the
+    /// changes applied before it.
+    pub fn apply_diff<I>(&mut self, changes: I) -> Result<(), GridError>
+    where
+        I: IntoIterator<Item = (usize, usize, V)>,
+    {
+        for (col, row, value) in changes {
+            if self.set_cell_by_indices(col, row, value).is_err() {
+                return Err(GridError::IndicesOutOfRange { col, row });
+            }
+        }
+        Ok(())
+    }
+}
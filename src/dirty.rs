@@ -0,0 +1,100 @@
+use super::*;
+
+// Bounding rect of the cells changed since the last "take_dirty_rect", when tracking is on.
+// It is bookkeeping rather than content, so it never affects equality.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct DirtyTracker {
+    enabled: bool,
+    rect: Option<(usize, usize, usize, usize)>,
+}
+
+impl PartialEq for DirtyTracker {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl DirtyTracker {
+    // Grows the rect to include the (left, bottom, right, top) cell indices.
+    #[inline]
+    fn mark(&mut self, left: usize, bottom: usize, right: usize, top: usize) {
+        if !self.enabled {
+            return;
+        }
+        self.rect = Some(match self.rect {
+            Some((l, b, r, t)) => (l.min(left), b.min(bottom), r.max(right), t.max(top)),
+            None => (left, bottom, right, top),
+        });
+    }
+}
+
+impl<V> Grid<V> {
+    /// Starts recording which cells are changed. Every method giving mutable access to cells
+    /// marks them, even if the caller doesn't write to them; mutable iterators mark all the
+    /// cells they cover when created. Changing the number of columns or rows marks the
+    /// whole grid.
+    pub fn enable_dirty_tracking(&mut self) {
+        self.dirty.enabled = true;
+    }
+
+    /// Stops recording changes and forgets the current dirty cells.
+    pub fn disable_dirty_tracking(&mut self) {
+        self.dirty = DirtyTracker::default();
+    }
+
+    /// Returns true if changes are being recorded.
+    pub fn is_dirty_tracking_enabled(&self) -> bool {
+        self.dirty.enabled
+    }
+
+    /// Returns the (left, bottom, right, top) cell indices of the smallest rectangle
+    /// containing every cell changed since the last call, and resets it. None if nothing
+    /// changed or tracking is disabled.
+    pub fn take_dirty_rect(&mut self) -> Option<(usize, usize, usize, usize)> {
+        self.dirty.rect.take()
+    }
+
+    /// Same as "take_dirty_rect", returning the (column, row) coordinates of every cell in
+    /// that rectangle, row by row starting at the bottom row.
+    pub fn take_dirty(&mut self) -> IterCoords {
+        Self::coords_in_edges(self.take_dirty_rect())
+    }
+
+    // Records a change to a single cell.
+    #[inline]
+    pub(crate) fn mark_dirty(&mut self, col: usize, row: usize) {
+        self.dirty.mark(col, row, col, row);
+    }
+
+    // Records changes to the cells within the (left, bottom, right, top) indices.
+    #[inline]
+    pub(crate) fn mark_dirty_edges(&mut self, edges: Option<(usize, usize, usize, usize)>) {
+        if let Some((left, bottom, right, top)) = edges {
+            self.dirty.mark(left, bottom, right, top);
+        }
+    }
+
+    // Records changes to every cell.
+    #[inline]
+    pub(crate) fn mark_all_dirty(&mut self) {
+        self.dirty.mark(0, 0, self.columns - 1, self.rows - 1);
+    }
+
+    // Records a change to the number of columns or rows, which invalidates every cell and
+    // every CellId.
+    pub(crate) fn mark_resized(&mut self) {
+        self.bump_generation();
+        self.dirty = self.derived_dirty(self.columns, self.rows);
+    }
+
+    // Tracker for a Grid built from this one, with the same tracking state and every cell
+    // marked.
+    pub(crate) fn derived_dirty(&self, columns: usize, rows: usize) -> DirtyTracker {
+        let mut dirty = DirtyTracker {
+            enabled: self.dirty.enabled,
+            rect: None,
+        };
+        dirty.mark(0, 0, columns - 1, rows - 1);
+        dirty
+    }
+}
@@ -0,0 +1,120 @@
+use crate::Scalar;
+use core::fmt;
+
+/// Errors returned by the fallible Grid operations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GridError {
+    /// A Grid needs at least one column.
+    ZeroColumns,
+    /// A Grid needs at least one row.
+    ZeroRows,
+    /// The physical width and height must be larger than zero.
+    NonPositiveSize { width: Scalar, height: Scalar },
+    /// The physical width or height is NaN or infinite.
+    NonFiniteSize,
+    /// The physical point is outside the grid.
+    PointOutOfBounds { x: Scalar, y: Scalar },
+    /// The physical rectangle doesn't overlap the grid.
+    RectOutOfBounds {
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+    },
+    /// The (column, row) indices are outside the grid.
+    IndicesOutOfRange { col: usize, row: usize },
+    /// The column index is outside the grid.
+    ColumnOutOfRange { col: usize },
+    /// The row index is outside the grid.
+    RowOutOfRange { row: usize },
+    /// The number of cells doesn't match the number of columns and rows.
+    CellCountMismatch { expected: usize, found: usize },
+    /// The binary data doesn't start with a valid Grid header for this cell type.
+    InvalidHeader,
+    /// The binary data ended before all cells were read.
+    TruncatedData,
+    /// Two grids were expected to have the same number of columns and rows.
+    ShapeMismatch {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    /// A stored value that must be finite is NaN or infinite.
+    NonFiniteValue { field: &'static str },
+    /// The stored cell size doesn't match the size divided by the number of cells.
+    CellSizeMismatch {
+        field: &'static str,
+        expected: Scalar,
+        found: Scalar,
+    },
+    /// A value the integer lookups need as a whole number isn't one (or is out of range).
+    NonIntegerFrame { field: &'static str, value: Scalar },
+    /// A column in nested data doesn't have the same number of cells as the first one.
+    RaggedColumn {
+        col: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::ZeroColumns => write!(f, "a grid needs at least one column"),
+            GridError::ZeroRows => write!(f, "a grid needs at least one row"),
+            GridError::NonPositiveSize { width, height } => write!(
+                f,
+                "grid size must be larger than zero, found {}x{}",
+                width, height
+            ),
+            GridError::NonFiniteSize => write!(f, "grid size must be finite"),
+            GridError::PointOutOfBounds { x, y } => {
+                write!(f, "point ({}, {}) is outside the grid", x, y)
+            }
+            GridError::RectOutOfBounds {
+                left,
+                bottom,
+                right,
+                top,
+            } => write!(
+                f,
+                "rect ({}, {}) -> ({}, {}) doesn't overlap the grid",
+                left, bottom, right, top
+            ),
+            GridError::IndicesOutOfRange { col, row } => {
+                write!(f, "cell indices ({}, {}) are out of range", col, row)
+            }
+            GridError::ColumnOutOfRange { col } => write!(f, "column {} is out of range", col),
+            GridError::RowOutOfRange { row } => write!(f, "row {} is out of range", row),
+            GridError::CellCountMismatch { expected, found } => {
+                write!(f, "expected {} cells, found {}", expected, found)
+            }
+            GridError::InvalidHeader => write!(f, "invalid grid header"),
+            GridError::TruncatedData => write!(f, "grid data is truncated"),
+            GridError::ShapeMismatch { expected, found } => write!(
+                f,
+                "expected a {}x{} grid, found {}x{}",
+                expected.0, expected.1, found.0, found.1
+            ),
+            GridError::NonFiniteValue { field } => write!(f, "{} must be finite", field),
+            GridError::CellSizeMismatch {
+                field,
+                expected,
+                found,
+            } => write!(f, "{} is {}, expected {}", field, found, expected),
+            GridError::NonIntegerFrame { field, value } => {
+                write!(f, "{} must be a whole number, found {}", field, value)
+            }
+            GridError::RaggedColumn {
+                col,
+                expected,
+                found,
+            } => write!(
+                f,
+                "column {} has {} cells, expected {}",
+                col, found, expected
+            ),
+        }
+    }
+}
+
+impl core::error::Error for GridError {}
@@ -0,0 +1,61 @@
+use super::*;
+use alloc::collections::VecDeque;
+use alloc::vec;
+
+impl<V> Grid<V> {
+    /// Calls "apply" on every cell of the 4-connected region of cells matching "matches",
+    /// starting at (col, row). Does nothing if the start cell doesn't match or is out of range.
+    /// Uses a work queue instead of recursion, so large regions are safe.
+    pub fn flood_fill<M, F>(&mut self, col: usize, row: usize, matches: M, mut apply: F)
+    where
+        M: Fn(&V) -> bool,
+        F: FnMut(&mut V),
+    {
+        // The region is found first, so "apply" can't change which cells are part of it
+        for index in self.flood_region(col, row, matches) {
+            self.mark_dirty(index / self.rows, index % self.rows);
+            apply(&mut self.data[index]);
+        }
+    }
+
+    /// Returns the (column, row) coordinates of the region "flood_fill" would visit, ordered
+    /// by their distance in steps from the start cell.
+    pub fn flood_collect<M>(&self, col: usize, row: usize, matches: M) -> Vec<(usize, usize)>
+    where
+        M: Fn(&V) -> bool,
+    {
+        self.flood_region(col, row, matches)
+            .into_iter()
+            .map(|index| (index / self.rows, index % self.rows))
+            .collect()
+    }
+
+    // Returns the flat indices of the matching region, in breadth-first order.
+    fn flood_region<M>(&self, col: usize, row: usize, matches: M) -> Vec<usize>
+    where
+        M: Fn(&V) -> bool,
+    {
+        let mut region = Vec::new();
+        let Some(start) = self.raw_index(col, row) else {
+            return region;
+        };
+        if !matches(&self.data[start]) {
+            return region;
+        }
+        let mut visited = vec![false; self.data.len()];
+        let mut queue = VecDeque::new();
+        visited[start] = true;
+        queue.push_back((col, row));
+        while let Some((col, row)) = queue.pop_front() {
+            region.push(self.flat_index(col, row));
+            for (next_col, next_row) in self.neighbor_coords(col, row, Connectivity::Four) {
+                let next = self.flat_index(next_col, next_row);
+                if !visited[next] && matches(&self.data[next]) {
+                    visited[next] = true;
+                    queue.push_back((next_col, next_row));
+                }
+            }
+        }
+        region
+    }
+}
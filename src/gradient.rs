@@ -0,0 +1,52 @@
+use super::*;
+use scalar::sqrt;
+
+impl<V> Grid<V> {
+    /// Returns the gradient (change per physical unit along x and y) of the values returned
+    /// by "func", at the cell containing the provided coordinates. Uses central differences
+    /// between the neighboring cells, or one-sided differences on the grid borders.
+    /// None if the point is outside the grid.
+    pub fn gradient_at<F>(&self, x: Scalar, y: Scalar, func: F) -> Option<(Scalar, Scalar)>
+    where
+        F: Fn(&V) -> Scalar,
+    {
+        let (col, row) = self.get_cell_coords(x, y)?;
+        let value = |col: usize, row: usize| func(&self.data[self.flat_index(col, row)]);
+        let (left, right) = (col.saturating_sub(1), (col + 1).min(self.columns - 1));
+        let (bottom, top) = (row.saturating_sub(1), (row + 1).min(self.rows - 1));
+        let difference = |from: usize, to: usize, a: Scalar, b: Scalar, size: Scalar| {
+            // A single column or row has no slope along it
+            if to == from {
+                0.0
+            } else {
+                (b - a) / ((to - from) as Scalar * size)
+            }
+        };
+        Some((
+            difference(
+                left,
+                right,
+                value(left, row),
+                value(right, row),
+                self.cell_width,
+            ),
+            difference(
+                bottom,
+                top,
+                value(col, bottom),
+                value(col, top),
+                self.cell_height,
+            ),
+        ))
+    }
+
+    /// Returns the magnitude of "gradient_at", i.e. how steep the values are at the cell
+    /// containing the provided coordinates.
+    pub fn slope_at<F>(&self, x: Scalar, y: Scalar, func: F) -> Option<Scalar>
+    where
+        F: Fn(&V) -> Scalar,
+    {
+        let (dx, dy) = self.gradient_at(x, y, func)?;
+        Some(sqrt((dx * dx) + (dy * dy)))
+    }
+}
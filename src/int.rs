@@ -0,0 +1,216 @@
+// Integer lookups, for targets where float math is expensive (i.e. no FPU).
+// The grid's position and cell size are converted once into an IntFrame, after which cells
+// are resolved with integer math only.
+use super::*;
+
+impl<V> Grid<V> {
+    /// Returns a Grid with whole-number cell sizes, pre-filled with the result of function
+    /// "func". Intended for the integer lookups ("get_cell_int" and friends). If "centered"
+    /// is true the pivot is at the center of the grid, rounded down to a whole number.
+    pub fn new_int<F>(
+        cell_width: u32,
+        cell_height: u32,
+        columns: usize,
+        rows: usize,
+        centered: bool,
+        func: F,
+    ) -> Self
+    where
+        F: FnMut() -> V,
+    {
+        let (width, height) = int_size(cell_width, cell_height, columns, rows);
+        let mut grid = Self::new_with(width, height, columns, rows, false, func);
+        grid.center_int(centered);
+        grid
+    }
+
+    /// Same as "new_int", but returns an error instead of panicking if a cell size is zero,
+    /// or if there are no columns or rows.
+    pub fn try_new_int<F>(
+        cell_width: u32,
+        cell_height: u32,
+        columns: usize,
+        rows: usize,
+        centered: bool,
+        func: F,
+    ) -> Result<Self, GridError>
+    where
+        F: FnMut() -> V,
+    {
+        let (width, height) = int_size(cell_width, cell_height, columns, rows);
+        let mut grid = Self::try_new_with(width, height, columns, rows, false, func)?;
+        grid.center_int(centered);
+        Ok(grid)
+    }
+
+    // Moves the pivot to the center, keeping it on whole numbers.
+    fn center_int(&mut self, centered: bool) {
+        if centered {
+            self.offset_x = ((self.width as i64) / 2) as Scalar;
+            self.offset_y = ((self.height as i64) / 2) as Scalar;
+        }
+    }
+
+    /// Returns the grid's position and cell size as integers, to be passed to the integer
+    /// lookups. Fails if the left or bottom edge, or the cell size, isn't a whole number,
+    /// since the lookups would then resolve cells differently from the float ones.
+    pub fn int_frame(&self) -> Result<IntFrame, GridError> {
+        let whole = |field: &'static str, value: Scalar, min: Scalar| {
+            let valid = value.is_finite() && floor(value) == value && value >= min;
+            // Beyond this range not every whole number is exactly representable
+            let valid = valid && value.abs() <= (1u64 << 52) as Scalar;
+            if valid {
+                Ok(value as i64)
+            } else {
+                Err(GridError::NonIntegerFrame { field, value })
+            }
+        };
+        Ok(IntFrame {
+            left: whole("left", self.left(), Scalar::MIN)?,
+            bottom: whole("bottom", self.bottom(), Scalar::MIN)?,
+            cell_width: whole("cell_width", self.cell_width, 1.0)?,
+            cell_height: whole("cell_height", self.cell_height, 1.0)?,
+            columns: self.columns,
+            rows: self.rows,
+        })
+    }
+
+    /// Integer version of "get_cell_coords", using a frame from "int_frame". Points on the
+    /// right or top edges are considered outside the grid. None if the frame has a different
+    /// number of columns or rows than the grid.
+    pub fn get_cell_coords_int(&self, frame: &IntFrame, x: i32, y: i32) -> Option<(usize, usize)> {
+        if !self.fits_frame(frame) {
+            return None;
+        }
+        frame.cell_coords(x, y)
+    }
+
+    /// Integer version of "get_cell".
+    pub fn get_cell_int(&self, frame: &IntFrame, x: i32, y: i32) -> Option<&V> {
+        let coords = self.get_cell_coords_int(frame, x, y)?;
+        self.get_cell_by_indices(coords.0, coords.1)
+    }
+
+    /// Integer version of "get_cell_mut".
+    pub fn get_cell_int_mut(&mut self, frame: &IntFrame, x: i32, y: i32) -> Option<&mut V> {
+        let coords = self.get_cell_coords_int(frame, x, y)?;
+        self.get_cell_by_indices_mut(coords.0, coords.1)
+    }
+
+    /// Integer version of "iter_coords". Empty if the frame has a different number of
+    /// columns or rows than the grid.
+    pub fn iter_coords_int(
+        &self,
+        frame: &IntFrame,
+        left: i32,
+        bottom: i32,
+        right: i32,
+        top: i32,
+    ) -> IterCoords {
+        let edges = if self.fits_frame(frame) {
+            frame.edges(left, bottom, right, top)
+        } else {
+            None
+        };
+        Self::coords_in_edges(edges)
+    }
+
+    /// Integer version of "iter_cells_in_rect".
+    pub fn iter_cells_in_rect_int(
+        &self,
+        frame: &IntFrame,
+        left: i32,
+        bottom: i32,
+        right: i32,
+        top: i32,
+    ) -> IterGridRect<'_, V> {
+        IterGridRect {
+            grid: self,
+            coords: self.iter_coords_int(frame, left, bottom, right, top),
+        }
+    }
+
+    /// Integer version of "iter_cells_in_rect_mut".
+    pub fn iter_cells_in_rect_int_mut(
+        &mut self,
+        frame: &IntFrame,
+        left: i32,
+        bottom: i32,
+        right: i32,
+        top: i32,
+    ) -> IterGridRectMut<'_, V> {
+        let coords = self.iter_coords_int(frame, left, bottom, right, top);
+        self.iter_mut_with(coords)
+    }
+
+    // Frames from grids with other dimensions could resolve cells out of range.
+    fn fits_frame(&self, frame: &IntFrame) -> bool {
+        frame.columns == self.columns && frame.rows == self.rows
+    }
+}
+
+/// Position and cell size of a Grid as integers, created with "Grid::int_frame". The
+/// integer lookups use it so they never touch floats. It is a copy: get a new one after
+/// moving the grid, or changing its pivot or dimensions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IntFrame {
+    left: i64,
+    bottom: i64,
+    cell_width: i64,
+    cell_height: i64,
+    columns: usize,
+    rows: usize,
+}
+
+impl IntFrame {
+    // Cell containing the point, if any.
+    fn cell_coords(&self, x: i32, y: i32) -> Option<(usize, usize)> {
+        let x = x as i64 - self.left;
+        let y = y as i64 - self.bottom;
+        if x < 0 || y < 0 {
+            return None;
+        }
+        let col = (x / self.cell_width) as usize;
+        let row = (y / self.cell_height) as usize;
+        if col >= self.columns || row >= self.rows {
+            return None;
+        }
+        Some((col, row))
+    }
+
+    // Integer version of "get_edges".
+    fn edges(
+        &self,
+        left: i32,
+        bottom: i32,
+        right: i32,
+        top: i32,
+    ) -> Option<(usize, usize, usize, usize)> {
+        if left > right || bottom > top {
+            return None;
+        }
+        let col_left = (left as i64 - self.left).div_euclid(self.cell_width);
+        let row_bottom = (bottom as i64 - self.bottom).div_euclid(self.cell_height);
+        let col_right = (right as i64 - self.left).div_euclid(self.cell_width);
+        let row_top = (top as i64 - self.bottom).div_euclid(self.cell_height);
+        let max_right = self.columns as i64 - 1;
+        let max_top = self.rows as i64 - 1;
+        if col_right < 0 || row_top < 0 || col_left > max_right || row_bottom > max_top {
+            return None;
+        }
+        Some((
+            col_left.max(0) as usize,
+            row_bottom.max(0) as usize,
+            col_right.min(max_right) as usize,
+            row_top.min(max_top) as usize,
+        ))
+    }
+}
+
+// Physical size of a grid with whole-number cells.
+fn int_size(cell_width: u32, cell_height: u32, columns: usize, rows: usize) -> (Scalar, Scalar) {
+    (
+        cell_width as Scalar * columns as Scalar,
+        cell_height as Scalar * rows as Scalar,
+    )
+}
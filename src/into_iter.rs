@@ -0,0 +1,68 @@
+use super::*;
+use core::iter::FusedIterator;
+
+/// Consuming iterator that yields all cells of a Grid, row by row, starting at the bottom row.
+#[derive(Debug)]
+pub struct IntoIter<V> {
+    pub(super) columns: Vec<alloc::vec::IntoIter<V>>,
+    pub(super) current_col: usize,
+}
+
+impl<V> Iterator for IntoIter<V> {
+    type Item = V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Take one cell from each column in turn, which walks the rows upwards.
+        let value = self.columns.get_mut(self.current_col)?.next()?;
+        self.current_col = (self.current_col + 1) % self.columns.len();
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.columns.iter().map(|col| col.len()).sum();
+        (remaining, Some(remaining))
+    }
+}
+
+impl<V> ExactSizeIterator for IntoIter<V> {}
+
+impl<V> FusedIterator for IntoIter<V> {}
+
+impl<V> IntoIterator for Grid<V> {
+    type Item = V;
+    type IntoIter = IntoIter<V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        // Split the storage into columns, starting from the end so that each split only
+        // moves the cells of one column.
+        let mut data = self.data;
+        let mut columns = Vec::with_capacity(self.columns);
+        for _ in 0..self.columns {
+            let col = data.split_off(data.len() - self.rows);
+            columns.push(col.into_iter());
+        }
+        columns.reverse();
+        IntoIter {
+            columns,
+            current_col: 0,
+        }
+    }
+}
+
+impl<'a, V> IntoIterator for &'a Grid<V> {
+    type Item = &'a V;
+    type IntoIter = IterGridRect<'a, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_all_cells()
+    }
+}
+
+impl<'a, V> IntoIterator for &'a mut Grid<V> {
+    type Item = &'a mut V;
+    type IntoIter = IterGridRectMut<'a, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_all_cells_mut()
+    }
+}
@@ -1,87 +1,127 @@
 use super::*;
+use core::iter::FusedIterator;
 
 /// Iterator that yields references to cells in the grid overlapping with a specified rectangle.
+/// Cloning it is cheap, and the clone continues from the same cell.
 #[derive(Debug)]
 pub struct IterGridRect<'a, V> {
-    pub(super) y_up: bool,
     pub(super) grid: &'a Grid<V>,
-    pub(super) top: usize,
-    pub(super) bottom: usize,
-    pub(super) left: usize,
-    pub(super) right: usize,
-    pub(super) current_row: usize,
-    pub(super) current_col: usize,
-    pub(super) done: bool,
+    pub(super) coords: IterCoords,
+}
+
+// Derived Clone would require V: Clone
+impl<'a, V> Clone for IterGridRect<'a, V> {
+    fn clone(&self) -> Self {
+        Self {
+            grid: self.grid,
+            coords: self.coords.clone(),
+        }
+    }
 }
 
 impl<'a, V> Iterator for IterGridRect<'a, V> {
     type Item = &'a V;
 
     fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            if self.done == true {
-                break;
-            }
-            if let Some(col) = self.grid.data.get(self.current_col) {
-                if let Some(cell) = col.get(self.current_row) {
-                    self.advance();
-                    return Some(cell);
-                } else {
-                    break;
-                }
-            } else {
-                self.advance();
-            }
-        }
-        None
+        self.next_with_coords().map(|(cell, _, _)| cell)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining();
+        (remaining, Some(remaining))
+    }
+
+    fn count(self) -> usize {
+        self.remaining()
     }
 }
 
+impl<'a, V> DoubleEndedIterator for IterGridRect<'a, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.next_back_with_coords().map(|(cell, _, _)| cell)
+    }
+}
+
+impl<'a, V> ExactSizeIterator for IterGridRect<'a, V> {}
+
+impl<'a, V> FusedIterator for IterGridRect<'a, V> {}
+
 impl<'a, V> IterGridRect<'a, V> {
-    /// Inverts Y iteration direction
+    /// Iterates from the top row down instead, starting over from the first cell. Can be
+    /// used at any time.
     pub fn y_down(self) -> Self {
-        assert_eq!(
-            self.current_row, self.bottom,
-            "IterGridRect: Error, 'y_down()' can only be used on freshly created Iterator."
-        );
-        let top = self.top;
         Self {
-            y_up: false,
-            current_row: top,
+            coords: self.coords.y_down(),
+            ..self
+        }
+    }
+
+    /// Iterates from the right column to the left one instead, starting over from the first
+    /// cell. Can be used at any time.
+    pub fn x_right_to_left(self) -> Self {
+        Self {
+            coords: self.coords.x_right_to_left(),
+            ..self
+        }
+    }
+
+    /// Iterates column by column instead of row by row, starting over from the first cell.
+    /// This follows the storage order, so it is faster. Can be used at any time.
+    pub fn column_major(self) -> Self {
+        Self {
+            coords: self.coords.column_major(),
             ..self
         }
     }
 
+    /// Rewinds to the first cell of the rect, keeping the iteration order.
+    pub fn reset(&mut self) {
+        self.coords.reset();
+    }
 
     /// Returns an iterator that enumerates each cell with its coordinates (value, column, row).
     pub fn enumerate_coords(self) -> IterWithCoords<'a, V> {
-        let current_col = self.current_col;
-        let current_row = self.current_row;
-        IterWithCoords {
-            iter: self,
-            current_col,
-            current_row,
-        }
+        IterWithCoords { iter: self }
+    }
+
+    // Returns the next cell along with the coordinates it was read from.
+    pub(super) fn next_with_coords(&mut self) -> Option<(&'a V, usize, usize)> {
+        let (col, row) = self.coords.next()?;
+        // SAFETY: The coordinates never leave the iteration rect, which is clamped to the grid.
+        let cell = unsafe { self.grid.get_cell_by_indices_unchecked(col, row) };
+        Some((cell, col, row))
     }
 
+    // Returns the last cell along with the coordinates it was read from.
+    pub(super) fn next_back_with_coords(&mut self) -> Option<(&'a V, usize, usize)> {
+        let (col, row) = self.coords.next_back()?;
+        // SAFETY: The coordinates never leave the iteration rect, which is clamped to the grid.
+        let cell = unsafe { self.grid.get_cell_by_indices_unchecked(col, row) };
+        Some((cell, col, row))
+    }
+
+    // Number of cells left to visit.
+    pub(super) fn remaining(&self) -> usize {
+        self.coords.remaining()
+    }
+
+    /// Moves the front cursor one cell forward without checking the back cursor.
+    #[deprecated(note = "use \"skip_to\" or \"skip_row\", or \"nth\" to skip cells")]
     pub fn advance(&mut self) {
-        // Advance column
-        self.current_col += 1;
-        // Wrap around to the next row if necessary
-        if self.current_col > self.right {
-            self.current_col = self.left;
-            if self.y_up {
-                self.current_row += 1;
-                if self.current_row > self.top {
-                    self.done = true;
-                }
-            } else {
-                if self.current_row == self.bottom {
-                    self.done = true;
-                } else {
-                    self.current_row -= 1;
-                }
-            }
-        }
+        self.coords.advance_front();
+    }
+
+    /// Skips ahead so the next cell yielded is (col, row), clamped to the iteration rect.
+    /// Cells already yielded can't be revisited, so targets behind the front of the
+    /// iterator do nothing. Targets past the last remaining cell finish the iterator.
+    pub fn skip_to(&mut self, col: usize, row: usize) {
+        self.coords.skip_to(col, row);
+    }
+
+    /// Skips the remaining cells of the current row, so the next cell yielded is the first
+    /// one of the following row (in the iteration direction). With "column_major" it skips
+    /// the rest of the current column instead.
+    pub fn skip_row(&mut self) {
+        self.coords.skip_line();
     }
 }
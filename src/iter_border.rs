@@ -0,0 +1,116 @@
+use super::*;
+use core::iter::FusedIterator;
+
+/// Iterator that yields the (column,row) pairs of the perimeter cells of a rectangle, row by
+/// row starting at the bottom row. Each cell is yielded once, even in rectangles only one
+/// cell wide or tall.
+#[derive(Debug, Clone)]
+pub struct IterBorderCoords {
+    pub(super) left: usize,
+    pub(super) bottom: usize,
+    pub(super) right: usize,
+    pub(super) top: usize,
+    pub(super) current_col: usize,
+    pub(super) current_row: usize,
+    pub(super) done: bool,
+}
+
+impl IterBorderCoords {
+    // Number of cells left to yield.
+    fn remaining(&self) -> usize {
+        if self.done {
+            return 0;
+        }
+        let width = self.right - self.left + 1;
+        let sides = if width > 1 { 2 } else { 1 };
+        if self.current_row == self.bottom || self.current_row == self.top {
+            // Rest of the current full row, plus every row above it
+            let rest_of_row = self.right - self.current_col + 1;
+            if self.current_row == self.top {
+                return rest_of_row;
+            }
+            let middle = self.top - self.current_row - 1;
+            return rest_of_row + (middle * sides) + width;
+        }
+        // Current side cell, plus the rest of the middle rows and the top row
+        let rest_of_row = if self.current_col == self.left {
+            sides
+        } else {
+            1
+        };
+        let middle = self.top - self.current_row - 1;
+        rest_of_row + (middle * sides) + width
+    }
+}
+
+impl Iterator for IterBorderCoords {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let result = (self.current_col, self.current_row);
+        let full_row = self.current_row == self.bottom || self.current_row == self.top;
+        if self.current_col < self.right {
+            // Middle rows jump straight to the right side
+            self.current_col = if full_row {
+                self.current_col + 1
+            } else {
+                self.right
+            };
+        } else if self.current_row < self.top {
+            self.current_col = self.left;
+            self.current_row += 1;
+        } else {
+            self.done = true;
+        }
+        Some(result)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining();
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for IterBorderCoords {}
+
+impl FusedIterator for IterBorderCoords {}
+
+impl<V> Grid<V> {
+    /// Returns an iterator that yields (column,row) pairs for the perimeter cells of the
+    /// region overlapping the provided rectangle edges, clamped to the grid.
+    pub fn iter_border_coords_in_rect(
+        &self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+    ) -> IterBorderCoords {
+        let edges = self.get_edges(left, bottom, right, top);
+        let (col_left, row_bottom, col_right, row_top) = edges.unwrap_or_default();
+        IterBorderCoords {
+            left: col_left,
+            bottom: row_bottom,
+            right: col_right,
+            top: row_top,
+            current_col: col_left,
+            current_row: row_bottom,
+            done: edges.is_none(),
+        }
+    }
+
+    /// Returns an iterator with the perimeter cells of the region overlapping the provided
+    /// rectangle edges, in the same order as "iter_border_coords_in_rect".
+    pub fn iter_border_in_rect(
+        &self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+    ) -> impl Iterator<Item = &V> + '_ {
+        self.iter_border_coords_in_rect(left, bottom, right, top)
+            .map(|(col, row)| &self.data[self.flat_index(col, row)])
+    }
+}
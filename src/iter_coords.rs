@@ -1,65 +1,236 @@
+use core::iter::FusedIterator;
+
 /// Iterator that yields (column,row) pairs for each cell that overlaps the provided
-/// rectangle edges.
+/// rectangle edges. By default it walks row by row, left to right, starting at the bottom
+/// row; "y_down", "x_right_to_left" and "column_major" change the order and can be combined.
 #[derive(Debug, Clone)]
 pub struct IterCoords {
+    // Iteration order
     pub(super) y_up: bool,
+    pub(super) x_right: bool,
+    pub(super) column_major: bool,
+    // Rect edges, as cell indices
     pub(super) top: usize,
     pub(super) bottom: usize,
     pub(super) left: usize,
     pub(super) right: usize,
-    pub(super) current_row: usize,
-    pub(super) current_col: usize,
+    // Positions of the next cell from each end, along the iteration order
+    pub(super) front: usize,
+    pub(super) back: usize,
     pub(super) done: bool,
+    // Set when the rect doesn't overlap the grid, so "reset" leaves it finished
+    pub(super) empty: bool,
 }
 
 impl Iterator for IterCoords {
     type Item = (usize, usize);
 
     fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            if self.done == true {
-                break;
-            }
-            let result = (self.current_col, self.current_row);
-            self.advance();
-            return Some(result);
+        if self.done {
+            return None;
+        }
+        let result = self.coords_at(self.front);
+        if self.front == self.back {
+            self.done = true;
+        } else {
+            self.front += 1;
+        }
+        Some(result)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining();
+        (remaining, Some(remaining))
+    }
+
+    fn count(self) -> usize {
+        self.remaining()
+    }
+}
+
+impl DoubleEndedIterator for IterCoords {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let result = self.coords_at(self.back);
+        if self.front == self.back {
+            self.done = true;
+        } else {
+            self.back -= 1;
         }
-        None
+        Some(result)
     }
 }
 
+impl ExactSizeIterator for IterCoords {}
+
+impl FusedIterator for IterCoords {}
+
 impl IterCoords {
-    pub fn y_down(self) -> Self {
-        assert_eq!(
-            self.current_row, self.bottom,
-            "IterCoords: Error, 'y_down()' can only be used on freshly created Iterator."
-        );
-        let top = self.top;
-        Self {
-            y_up: false,
-            current_row: top,
-            ..self
+    // Iterator over the (left, bottom, right, top) cell indices, in the default order.
+    // None creates an iterator that is already finished.
+    pub(super) fn new(edges: Option<(usize, usize, usize, usize)>) -> Self {
+        let (left, bottom, right, top) = edges.unwrap_or_default();
+        let mut coords = Self {
+            y_up: true,
+            x_right: true,
+            column_major: false,
+            top,
+            bottom,
+            left,
+            right,
+            front: 0,
+            back: 0,
+            done: true,
+            empty: edges.is_none(),
+        };
+        coords.reset();
+        coords
+    }
+
+    /// Iterates from the top row down instead, starting over from the first cell. Can be
+    /// used at any time.
+    pub fn y_down(mut self) -> Self {
+        self.y_up = false;
+        self.reset();
+        self
+    }
+
+    /// Iterates from the right column to the left one instead, starting over from the first
+    /// cell. Can be used at any time.
+    pub fn x_right_to_left(mut self) -> Self {
+        self.x_right = false;
+        self.reset();
+        self
+    }
+
+    /// Iterates column by column instead of row by row, starting over from the first cell.
+    /// This follows the storage order, so it is faster when visiting cells. Can be used at
+    /// any time.
+    pub fn column_major(mut self) -> Self {
+        self.column_major = true;
+        self.reset();
+        self
+    }
+
+    /// Rewinds to the first cell of the rect, keeping the iteration order.
+    pub fn reset(&mut self) {
+        self.front = 0;
+        self.back = (self.width() * self.height()) - 1;
+        self.done = self.empty;
+    }
+
+    // True if no coordinates were taken from either end yet.
+    pub(super) fn is_fresh(&self) -> bool {
+        let mut fresh = self.clone();
+        fresh.reset();
+        (self.front, self.back, self.done) == (fresh.front, fresh.back, fresh.done)
+    }
+
+    // Number of cells left to visit.
+    pub(super) fn remaining(&self) -> usize {
+        if self.done {
+            return 0;
+        }
+        self.back - self.front + 1
+    }
+
+    // Moves the front forward without checking the back.
+    pub(super) fn advance_front(&mut self) {
+        self.front += 1;
+        if self.front > self.back {
+            self.done = true;
+        }
+    }
+
+    // Skips ahead so the next coordinates yielded are (col, row), clamped to the rect.
+    pub(super) fn skip_to(&mut self, col: usize, row: usize) {
+        if self.done {
+            return;
+        }
+        let col = col.clamp(self.left, self.right);
+        let row = row.clamp(self.bottom, self.top);
+        let target = self.order_index(col, row);
+        if target <= self.front {
+            return;
+        }
+        if target > self.back {
+            self.done = true;
+        } else {
+            self.front = target;
         }
     }
 
-    fn advance(&mut self) {
-        // Advance column
-        self.current_col += 1;
-        // Wrap around to the next row if necessary
-        if self.current_col > self.right {
-            self.current_col = self.left;
-            if self.y_up {
-                self.current_row += 1;
-                if self.current_row > self.top {
-                    self.done = true;
-                }
-            } else {
-                if self.current_row == self.bottom {
-                    self.done = true;
-                } else {
-                    self.current_row -= 1;
-                }
-            }
+    // Skips the rest of the current row (or column, if column major).
+    pub(super) fn skip_line(&mut self) {
+        if self.done {
+            return;
+        }
+        let line = self.line_len();
+        let next = ((self.front / line) + 1) * line;
+        if next > self.back {
+            self.done = true;
+        } else {
+            self.front = next;
         }
     }
+
+    fn width(&self) -> usize {
+        self.right - self.left + 1
+    }
+
+    fn height(&self) -> usize {
+        self.top - self.bottom + 1
+    }
+
+    // Number of cells visited before moving on to the next row (or column).
+    fn line_len(&self) -> usize {
+        if self.column_major {
+            self.height()
+        } else {
+            self.width()
+        }
+    }
+
+    // Linear position of a cell along the iteration order.
+    fn order_index(&self, col: usize, row: usize) -> usize {
+        let x = if self.x_right {
+            col - self.left
+        } else {
+            self.right - col
+        };
+        let y = if self.y_up {
+            row - self.bottom
+        } else {
+            self.top - row
+        };
+        if self.column_major {
+            (x * self.height()) + y
+        } else {
+            (y * self.width()) + x
+        }
+    }
+
+    // Cell at a linear position along the iteration order.
+    fn coords_at(&self, index: usize) -> (usize, usize) {
+        let line = self.line_len();
+        let (major, minor) = (index / line, index % line);
+        let (x, y) = if self.column_major {
+            (major, minor)
+        } else {
+            (minor, major)
+        };
+        let col = if self.x_right {
+            self.left + x
+        } else {
+            self.right - x
+        };
+        let row = if self.y_up {
+            self.bottom + y
+        } else {
+            self.top - y
+        };
+        (col, row)
+    }
 }
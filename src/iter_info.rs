@@ -0,0 +1,86 @@
+use super::*;
+use core::iter::FusedIterator;
+
+/// Physical information about a single cell, in world coordinates with the pivot applied.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CellInfo {
+    pub col: usize,
+    pub row: usize,
+    pub left: Scalar,
+    pub bottom: Scalar,
+    pub right: Scalar,
+    pub top: Scalar,
+    pub center_x: Scalar,
+    pub center_y: Scalar,
+}
+
+/// Iterator that yields a CellInfo for each cell that overlaps the provided rectangle edges.
+#[derive(Debug, Clone)]
+pub struct IterInfo {
+    pub(super) coords: IterCoords,
+    pub(super) cell_width: Scalar,
+    pub(super) cell_height: Scalar,
+    // Physical position of the grid's bottom/left corner
+    pub(super) left: Scalar,
+    pub(super) bottom: Scalar,
+}
+
+impl Iterator for IterInfo {
+    type Item = CellInfo;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (col, row) = self.coords.next()?;
+        let left = (col as Scalar * self.cell_width) + self.left;
+        let bottom = (row as Scalar * self.cell_height) + self.bottom;
+        Some(CellInfo {
+            col,
+            row,
+            left,
+            bottom,
+            right: left + self.cell_width,
+            top: bottom + self.cell_height,
+            center_x: left + (self.cell_width / 2.0),
+            center_y: bottom + (self.cell_height / 2.0),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.coords.remaining();
+        (remaining, Some(remaining))
+    }
+
+    fn count(self) -> usize {
+        self.coords.remaining()
+    }
+}
+
+impl ExactSizeIterator for IterInfo {}
+
+impl FusedIterator for IterInfo {}
+
+impl IterInfo {
+    /// Inverts Y iteration direction
+    pub fn y_down(self) -> Self {
+        Self {
+            coords: self.coords.y_down(),
+            ..self
+        }
+    }
+
+    /// Iterates from the right column to the left one instead, starting over from the first
+    /// cell.
+    pub fn x_right_to_left(self) -> Self {
+        Self {
+            coords: self.coords.x_right_to_left(),
+            ..self
+        }
+    }
+
+    /// Iterates column by column instead of row by row, starting over from the first cell.
+    pub fn column_major(self) -> Self {
+        Self {
+            coords: self.coords.column_major(),
+            ..self
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use super::*;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+
+/// Iterator that yields mutable references to cells in the grid overlapping with a specified rectangle.
+#[derive(Debug)]
+pub struct IterGridRectMut<'a, V> {
+    pub(super) coords: IterCoords,
+    pub(super) data: *mut V,
+    pub(super) columns: usize,
+    pub(super) rows: usize,
+    pub(super) _marker: PhantomData<&'a mut V>,
+}
+
+impl<'a, V> Iterator for IterGridRectMut<'a, V> {
+    type Item = &'a mut V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_coords().map(|(cell, _, _)| cell)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.coords.remaining();
+        (remaining, Some(remaining))
+    }
+
+    fn count(self) -> usize {
+        self.coords.remaining()
+    }
+}
+
+impl<'a, V> ExactSizeIterator for IterGridRectMut<'a, V> {}
+
+impl<'a, V> FusedIterator for IterGridRectMut<'a, V> {}
+
+impl<'a, V> IterGridRectMut<'a, V> {
+    /// Inverts Y iteration direction. Unlike the shared iterators it can't start over, since
+    /// that would hand out the same cells twice, so it panics after iteration has begun.
+    pub fn y_down(self) -> Self {
+        self.assert_fresh("y_down");
+        Self {
+            coords: self.coords.y_down(),
+            ..self
+        }
+    }
+
+    /// Iterates from the right column to the left one instead. Panics after iteration has
+    /// begun, like "y_down".
+    pub fn x_right_to_left(self) -> Self {
+        self.assert_fresh("x_right_to_left");
+        Self {
+            coords: self.coords.x_right_to_left(),
+            ..self
+        }
+    }
+
+    /// Iterates column by column instead of row by row, which follows the storage order.
+    /// Panics after iteration has begun, like "y_down".
+    pub fn column_major(self) -> Self {
+        self.assert_fresh("column_major");
+        Self {
+            coords: self.coords.column_major(),
+            ..self
+        }
+    }
+
+    fn assert_fresh(&self, method: &str) {
+        assert!(
+            self.coords.is_fresh(),
+            "IterGridRectMut: Error, '{}()' can only be used on freshly created Iterator.",
+            method
+        );
+    }
+
+    /// Returns an iterator that enumerates each cell with its coordinates (value, column, row).
+    pub fn enumerate_coords(self) -> IterWithCoordsMut<'a, V> {
+        IterWithCoordsMut { iter: self }
+    }
+
+    fn next_with_coords(&mut self) -> Option<(&'a mut V, usize, usize)> {
+        let (col, row) = self.coords.next()?;
+        debug_assert!(
+            col < self.columns && row < self.rows,
+            "IterGridRectMut: Error, cell ({}, {}) is out of range",
+            col,
+            row
+        );
+        // SAFETY: The iterator holds the grid's exclusive borrow for 'a, the rect was checked
+        // against the grid when it was created, and each (col, row) pair is yielded only
+        // once, so the returned references never alias.
+        let cell = unsafe { &mut *self.data.add((col * self.rows) + row) };
+        Some((cell, col, row))
+    }
+}
+
+/// Iterator that yields (mutable value, column, row) tuples from `IterGridRectMut`.
+#[derive(Debug)]
+pub struct IterWithCoordsMut<'a, V> {
+    pub(super) iter: IterGridRectMut<'a, V>,
+}
+
+impl<'a, V> Iterator for IterWithCoordsMut<'a, V> {
+    type Item = (&'a mut V, usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next_with_coords()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.iter.coords.remaining();
+        (remaining, Some(remaining))
+    }
+
+    fn count(self) -> usize {
+        self.iter.coords.remaining()
+    }
+}
+
+impl<'a, V> ExactSizeIterator for IterWithCoordsMut<'a, V> {}
+
+impl<'a, V> FusedIterator for IterWithCoordsMut<'a, V> {}
@@ -0,0 +1,294 @@
+use super::*;
+use core::iter::FusedIterator;
+use scalar::ceil;
+
+/// Iterator that yields the (column,row) pairs of every cell crossed by a line segment, in
+/// order from its start point. The segment is clipped to the grid bounds.
+#[derive(Debug, Clone)]
+pub struct IterSegmentCoords {
+    pub(super) col: isize,
+    pub(super) row: isize,
+    pub(super) columns: usize,
+    pub(super) rows: usize,
+    pub(super) step_col: isize,
+    pub(super) step_row: isize,
+    // Parameter at which the traversal enters the current cell
+    pub(super) t: Scalar,
+    // Parameter at which the segment ends
+    pub(super) t_end: Scalar,
+    // Parameter at which the next vertical / horizontal cell boundary is crossed
+    pub(super) t_max_x: Scalar,
+    pub(super) t_max_y: Scalar,
+    // Parameter increment needed to cross a whole cell horizontally / vertically
+    pub(super) t_delta_x: Scalar,
+    pub(super) t_delta_y: Scalar,
+    // Cells only touched at a corner, yielded before the current cell
+    pub(super) supercover: bool,
+    pub(super) corners: [(isize, isize); 2],
+    pub(super) corner_count: usize,
+    pub(super) done: bool,
+}
+
+impl IterSegmentCoords {
+    // Creates a traversal of the points (x + dx * t, y + dy * t) with t in t_start..=t_end.
+    // Coordinates are relative to the grid's bottom/left corner, without offsets.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn new<V>(
+        grid: &Grid<V>,
+        x: Scalar,
+        y: Scalar,
+        dx: Scalar,
+        dy: Scalar,
+        t_start: Scalar,
+        t_end: Scalar,
+        supercover: bool,
+    ) -> Self {
+        let mut iter = Self {
+            col: 0,
+            row: 0,
+            columns: grid.columns,
+            rows: grid.rows,
+            step_col: 0,
+            step_row: 0,
+            t: 0.0,
+            t_end: 0.0,
+            t_max_x: Scalar::INFINITY,
+            t_max_y: Scalar::INFINITY,
+            t_delta_x: Scalar::INFINITY,
+            t_delta_y: Scalar::INFINITY,
+            supercover,
+            corners: [(0, 0); 2],
+            corner_count: 0,
+            done: true,
+        };
+        // NaN would pass through "clip" and land on cell 0. An infinite end is allowed, i.e.
+        // for rays without a maximum distance.
+        let finite = [x, y, dx, dy, t_start]
+            .iter()
+            .all(|value| value.is_finite());
+        if !finite || t_end.is_nan() {
+            return iter;
+        }
+        // A single point uses the same rules as "get_cell_coords"
+        if dx == 0.0 && dy == 0.0 {
+            if x >= 0.0 && x < grid.width && y >= 0.0 && y < grid.height {
+                iter.col = (floor(x / grid.cell_width) as usize).min(grid.columns - 1) as isize;
+                iter.row = (floor(y / grid.cell_height) as usize).min(grid.rows - 1) as isize;
+                iter.t = t_start;
+                iter.t_end = t_start;
+                iter.done = false;
+            }
+            return iter;
+        }
+        // Lines running exactly along the right or top edges don't overlap any cell
+        if (dx == 0.0 && x >= grid.width) || (dy == 0.0 && y >= grid.height) {
+            return iter;
+        }
+        let Some((t_start, t_end)) = clip(x, y, dx, dy, t_start, t_end, grid.width, grid.height)
+        else {
+            return iter;
+        };
+        let x = x + (dx * t_start);
+        let y = y + (dy * t_start);
+
+        let (col, step_col, t_max_x, t_delta_x) =
+            axis_start(x, dx, grid.cell_width, grid.columns, t_start);
+        let (row, step_row, t_max_y, t_delta_y) =
+            axis_start(y, dy, grid.cell_height, grid.rows, t_start);
+        Self {
+            col,
+            row,
+            step_col,
+            step_row,
+            t: t_start,
+            t_end,
+            t_max_x,
+            t_max_y,
+            t_delta_x,
+            t_delta_y,
+            done: false,
+            ..iter
+        }
+    }
+
+    // Returns the next cell as (column, row, t_enter, t_exit).
+    pub(super) fn next_hit(&mut self) -> Option<(usize, usize, Scalar, Scalar)> {
+        while self.corner_count > 0 {
+            self.corner_count -= 1;
+            let (col, row) = self.corners[1 - self.corner_count];
+            if let Some(coords) = self.checked(col, row) {
+                return Some((coords.0, coords.1, self.t, self.t));
+            }
+        }
+        if self.done {
+            return None;
+        }
+        let (col, row) = self.checked(self.col, self.row)?;
+        let t_enter = self.t;
+        let t_next = self.t_max_x.min(self.t_max_y);
+        if t_next >= self.t_end {
+            self.done = true;
+            return Some((col, row, t_enter, self.t_end));
+        }
+        if self.t_max_x < self.t_max_y {
+            self.col += self.step_col;
+            self.t_max_x += self.t_delta_x;
+        } else if self.t_max_y < self.t_max_x {
+            self.row += self.step_row;
+            self.t_max_y += self.t_delta_y;
+        } else {
+            // Passes exactly through a corner
+            if self.supercover {
+                self.corners = [
+                    (self.col + self.step_col, self.row),
+                    (self.col, self.row + self.step_row),
+                ];
+                self.corner_count = 2;
+            }
+            self.col += self.step_col;
+            self.row += self.step_row;
+            self.t_max_x += self.t_delta_x;
+            self.t_max_y += self.t_delta_y;
+        }
+        self.t = t_next;
+        Some((col, row, t_enter, t_next))
+    }
+
+    // Converts the coordinates to unsigned ones, finishing the iterator if they are out of
+    // range. Guards against float rounding at the far end of the segment.
+    fn checked(&mut self, col: isize, row: isize) -> Option<(usize, usize)> {
+        if col < 0 || row < 0 || col as usize >= self.columns || row as usize >= self.rows {
+            self.done = true;
+            self.corner_count = 0;
+            return None;
+        }
+        Some((col as usize, row as usize))
+    }
+}
+
+impl Iterator for IterSegmentCoords {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_hit().map(|(col, row, _, _)| (col, row))
+    }
+}
+
+impl FusedIterator for IterSegmentCoords {}
+
+// Clips the parameter range of a line to the rectangle (0, 0, width, height)
+// (Liang-Barsky). Returns None if the line misses the rectangle.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn clip(
+    x: Scalar,
+    y: Scalar,
+    dx: Scalar,
+    dy: Scalar,
+    t_start: Scalar,
+    t_end: Scalar,
+    width: Scalar,
+    height: Scalar,
+) -> Option<(Scalar, Scalar)> {
+    let mut t0 = t_start;
+    let mut t1 = t_end;
+    for (p, q) in [(-dx, x), (dx, width - x), (-dy, y), (dy, height - y)] {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
+        } else {
+            let r = q / p;
+            if p < 0.0 {
+                t0 = t0.max(r);
+            } else {
+                t1 = t1.min(r);
+            }
+        }
+    }
+    if t0 > t1 {
+        return None;
+    }
+    Some((t0, t1))
+}
+
+// Returns the starting cell, step, first boundary parameter and parameter delta along one
+// axis. Points on a cell boundary start on the side the line is moving towards.
+fn axis_start(
+    pos: Scalar,
+    delta: Scalar,
+    cell_size: Scalar,
+    count: usize,
+    t: Scalar,
+) -> (isize, isize, Scalar, Scalar) {
+    let max = (count - 1) as Scalar;
+    if delta > 0.0 {
+        let index = floor(pos / cell_size).clamp(0.0, max);
+        let t_max = t + ((((index + 1.0) * cell_size) - pos) / delta);
+        (index as isize, 1, t_max, cell_size / delta)
+    } else if delta < 0.0 {
+        let index = (ceil(pos / cell_size) - 1.0).clamp(0.0, max);
+        let t_max = t + (((index * cell_size) - pos) / delta);
+        (index as isize, -1, t_max, -cell_size / delta)
+    } else {
+        let index = floor(pos / cell_size).clamp(0.0, max);
+        (index as isize, 0, Scalar::INFINITY, Scalar::INFINITY)
+    }
+}
+
+impl<V> Grid<V> {
+    /// Returns an iterator that yields the (column,row) pairs of every cell a line segment
+    /// passes through, in order from the start point. Segments are clipped to the grid.
+    /// When "supercover" is true, cells the line only touches at a corner are included.
+    pub fn iter_coords_on_segment(
+        &self,
+        x0: Scalar,
+        y0: Scalar,
+        x1: Scalar,
+        y1: Scalar,
+        supercover: bool,
+    ) -> IterSegmentCoords {
+        let x = self.local_x(x0);
+        let y = self.local_y(y0);
+        IterSegmentCoords::new(self, x, y, x1 - x0, y1 - y0, 0.0, 1.0, supercover)
+    }
+
+    /// Returns an iterator that yields ((column,row), value) for every cell a line segment
+    /// passes through, in the same order as "iter_coords_on_segment".
+    pub fn iter_cells_on_segment(
+        &self,
+        x0: Scalar,
+        y0: Scalar,
+        x1: Scalar,
+        y1: Scalar,
+        supercover: bool,
+    ) -> impl Iterator<Item = ((usize, usize), &V)> + '_ {
+        self.iter_coords_on_segment(x0, y0, x1, y1, supercover)
+            .map(|(col, row)| ((col, row), &self.data[self.flat_index(col, row)]))
+    }
+
+    /// Same as "iter_coords_on_segment", but takes the end points as points. See
+    /// "get_cell_coords_at".
+    pub fn iter_coords_on_segment_at(
+        &self,
+        start: impl Into<[Scalar; 2]>,
+        end: impl Into<[Scalar; 2]>,
+        supercover: bool,
+    ) -> IterSegmentCoords {
+        let [x0, y0] = start.into();
+        let [x1, y1] = end.into();
+        self.iter_coords_on_segment(x0, y0, x1, y1, supercover)
+    }
+
+    /// Same as "iter_cells_on_segment", but takes the end points as points. See
+    /// "get_cell_coords_at".
+    pub fn iter_cells_on_segment_at(
+        &self,
+        start: impl Into<[Scalar; 2]>,
+        end: impl Into<[Scalar; 2]>,
+        supercover: bool,
+    ) -> impl Iterator<Item = ((usize, usize), &V)> + '_ {
+        let [x0, y0] = start.into();
+        let [x1, y1] = end.into();
+        self.iter_cells_on_segment(x0, y0, x1, y1, supercover)
+    }
+}
@@ -0,0 +1,124 @@
+use super::*;
+use core::iter::FusedIterator;
+
+/// Iterator that yields (column,row) pairs in square rings of increasing distance from a
+/// starting cell. All cells at a given distance are yielded before any cell further away,
+/// each ring row by row starting at its bottom row. Out of range cells are skipped.
+#[derive(Debug, Clone)]
+pub struct IterSpiralCoords {
+    pub(super) center_col: isize,
+    pub(super) center_row: isize,
+    pub(super) columns: isize,
+    pub(super) rows: isize,
+    pub(super) max_radius: isize,
+    pub(super) radius: isize,
+    pub(super) current_col: isize,
+    pub(super) current_row: isize,
+    pub(super) done: bool,
+}
+
+impl IterSpiralCoords {
+    // Returns the first column of the current ring in a row that is after "after", if any.
+    fn col_in_row(&self, row: isize, after: isize) -> Option<isize> {
+        let left = self.center_col - self.radius;
+        let right = self.center_col + self.radius;
+        let full_row = row == self.center_row - self.radius || row == self.center_row + self.radius;
+        let col = if full_row {
+            (after + 1).max(left).max(0)
+        } else if after < left && left >= 0 {
+            left
+        } else {
+            right.max(after + 1)
+        };
+        (col <= right && col < self.columns).then_some(col)
+    }
+
+    // Moves to the first cell of the current ring at or above "row", if any.
+    fn find_from_row(&mut self, row: isize) -> bool {
+        let mut row = row.max(self.center_row - self.radius).max(0);
+        let top = (self.center_row + self.radius).min(self.rows - 1);
+        while row <= top {
+            if let Some(col) = self.col_in_row(row, isize::MIN) {
+                self.current_col = col;
+                self.current_row = row;
+                return true;
+            }
+            // Side rows all have the same columns, skip to the top row
+            let top_row = self.center_row + self.radius;
+            row = if row < top_row { top_row } else { row + 1 };
+        }
+        false
+    }
+}
+
+impl Iterator for IterSpiralCoords {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let result = (self.current_col as usize, self.current_row as usize);
+        if let Some(col) = self.col_in_row(self.current_row, self.current_col) {
+            self.current_col = col;
+        } else if !self.find_from_row(self.current_row + 1) {
+            loop {
+                self.radius += 1;
+                if self.radius > self.max_radius {
+                    self.done = true;
+                    break;
+                }
+                if self.find_from_row(isize::MIN) {
+                    break;
+                }
+            }
+        }
+        Some(result)
+    }
+}
+
+impl FusedIterator for IterSpiralCoords {}
+
+impl<V> Grid<V> {
+    /// Returns an iterator that yields (column,row) pairs in rings of increasing Chebyshev
+    /// distance from a cell, until the whole grid is visited or the distance goes beyond
+    /// "max_radius". Empty if the starting cell is out of range.
+    pub fn iter_spiral_coords(
+        &self,
+        col: usize,
+        row: usize,
+        max_radius: Option<usize>,
+    ) -> IterSpiralCoords {
+        let in_range = col < self.columns && row < self.rows;
+        // Distance to the furthest grid edge
+        let furthest = col
+            .max(self.columns - 1 - col.min(self.columns - 1))
+            .max(row)
+            .max(self.rows - 1 - row.min(self.rows - 1));
+        let max_radius = max_radius.map_or(furthest, |radius| radius.min(furthest));
+        IterSpiralCoords {
+            center_col: col as isize,
+            center_row: row as isize,
+            columns: self.columns as isize,
+            rows: self.rows as isize,
+            max_radius: max_radius as isize,
+            radius: 0,
+            current_col: col as isize,
+            current_row: row as isize,
+            done: !in_range,
+        }
+    }
+
+    /// Returns an iterator that yields ((column,row), value) in rings of increasing distance
+    /// from a cell, in the same order as "iter_spiral_coords". Combined with "find", this
+    /// finds the nearest matching cell without allocating.
+    pub fn iter_spiral(
+        &self,
+        col: usize,
+        row: usize,
+        max_radius: Option<usize>,
+    ) -> impl Iterator<Item = ((usize, usize), &V)> + '_ {
+        self.iter_spiral_coords(col, row, max_radius)
+            .map(|(col, row)| ((col, row), &self.data[self.flat_index(col, row)]))
+    }
+}
@@ -0,0 +1,134 @@
+use super::*;
+use core::iter::FusedIterator;
+
+/// Iterator that yields the (column,row) pairs of every cell overlapped by a rectangle moving
+/// along a straight line, ordered by the time the rectangle first overlaps them.
+#[derive(Debug, Clone)]
+pub struct IterSweptCoords {
+    pub(super) coords: alloc::vec::IntoIter<(usize, usize)>,
+}
+
+impl Iterator for IterSweptCoords {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.coords.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.coords.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for IterSweptCoords {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.coords.next_back()
+    }
+}
+
+impl ExactSizeIterator for IterSweptCoords {}
+
+impl FusedIterator for IterSweptCoords {}
+
+// Range of times in 0.0..=1.0 at which "pos + (delta * t)" is within lo..hi, the upper bound
+// being exclusive like the right and top edges of a cell. None if it never is.
+fn overlap_times(pos: Scalar, delta: Scalar, lo: Scalar, hi: Scalar) -> Option<(Scalar, Scalar)> {
+    if delta == 0.0 {
+        return (pos >= lo && pos < hi).then_some((0.0, 1.0));
+    }
+    // Times at which the exclusive bound is reached (open) and the inclusive one (closed)
+    let (t_closed, t_open) = ((lo - pos) / delta, (hi - pos) / delta);
+    let (start, end, overlaps) = if delta > 0.0 {
+        // Enters through "lo", leaves through "hi"
+        let start = t_closed.max(0.0);
+        if t_open > 1.0 {
+            (start, 1.0, start <= 1.0)
+        } else {
+            (start, t_open, start < t_open)
+        }
+    } else {
+        // Enters through "hi", leaves through "lo"
+        let end = t_closed.min(1.0);
+        if t_open < 0.0 {
+            (0.0, end, end >= 0.0)
+        } else {
+            (t_open, end, t_open < end)
+        }
+    };
+    overlaps.then_some((start, end))
+}
+
+impl<V> Grid<V> {
+    /// Returns an iterator that yields the (column,row) pairs of every cell overlapped by a
+    /// rectangle with the given half extents while its center moves from "from" to "to",
+    /// ordered by the time the rectangle first overlaps them. Cells entered at the same time
+    /// keep the order of "iter_coords", so without motion this is the same as a rect query.
+    pub fn iter_coords_swept_rect(
+        &self,
+        half_extents: impl Into<[Scalar; 2]>,
+        from: impl Into<[Scalar; 2]>,
+        to: impl Into<[Scalar; 2]>,
+    ) -> IterSweptCoords {
+        let [half_w, half_h] = half_extents.into();
+        let [x0, y0] = from.into();
+        let [x1, y1] = to.into();
+        let mut hits: Vec<(Scalar, usize, usize)> = Vec::new();
+        let edges = self.get_edges(
+            x0.min(x1) - half_w,
+            y0.min(y1) - half_h,
+            x0.max(x1) + half_w,
+            y0.max(y1) + half_h,
+        );
+        if let Some((_, row_bottom, _, row_top)) = edges {
+            let (x, y) = (self.local_x(x0), self.local_y(y0));
+            let (dx, dy) = (x1 - x0, y1 - y0);
+            for row in row_bottom..=row_top {
+                let bottom = row as Scalar * self.cell_height;
+                let Some((t_enter, t_exit)) =
+                    overlap_times(y, dy, bottom - half_h, bottom + self.cell_height + half_h)
+                else {
+                    continue;
+                };
+                // Columns covered while the rectangle overlaps this row
+                let a = x + (dx * t_enter);
+                let b = x + (dx * t_exit);
+                let (left, right) = (a.min(b) - half_w, a.max(b) + half_w);
+                if right < 0.0 || left >= self.width {
+                    continue;
+                }
+                let max_col = self.columns - 1;
+                let col_left = (floor(left / self.cell_width).max(0.0) as usize).min(max_col);
+                let col_right = (floor(right / self.cell_width) as usize).min(max_col);
+                for col in col_left..=col_right {
+                    let left = col as Scalar * self.cell_width;
+                    let hit = overlap_times(x, dx, left - half_w, left + self.cell_width + half_w);
+                    if let Some((col_enter, _)) = hit {
+                        hits.push((t_enter.max(col_enter), col, row));
+                    }
+                }
+            }
+        }
+        // Stable, so simultaneous hits stay row by row
+        hits.sort_by(|a, b| a.0.total_cmp(&b.0));
+        IterSweptCoords {
+            coords: hits
+                .into_iter()
+                .map(|(_, col, row)| (col, row))
+                .collect::<Vec<_>>()
+                .into_iter(),
+        }
+    }
+
+    /// Returns an iterator that yields ((column,row), value) for every cell overlapped by a
+    /// moving rectangle, in the same order as "iter_coords_swept_rect". Useful for swept
+    /// collisions, stopping at the first solid cell.
+    pub fn iter_cells_swept_rect(
+        &self,
+        half_extents: impl Into<[Scalar; 2]>,
+        from: impl Into<[Scalar; 2]>,
+        to: impl Into<[Scalar; 2]>,
+    ) -> impl Iterator<Item = ((usize, usize), &V)> + '_ {
+        self.iter_coords_swept_rect(half_extents, from, to)
+            .map(|(col, row)| ((col, row), &self.data[self.flat_index(col, row)]))
+    }
+}
@@ -1,33 +1,66 @@
 use super::*;
+use core::iter::FusedIterator;
 
 /// Iterator that yields (value, column, row) tuples from `IterGridRect`.
 #[derive(Debug)]
 pub struct IterWithCoords<'a, V> {
     pub(super) iter: IterGridRect<'a, V>,
-    pub(super) current_col: usize,
-    pub(super) current_row: usize,
 }
 
+impl<'a, V> Clone for IterWithCoords<'a, V> {
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
 
 impl<'a, V> Iterator for IterWithCoords<'a, V> {
     type Item = (&'a V, usize, usize);
 
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(value) = self.iter.next() {
-            // Capture the current coordinates
-            let col = self.current_col;
-            let row = self.current_row;
-
-            // Advance the column, wrapping to the next row if needed
-            self.current_col += 1;
-            if self.current_col > self.iter.right {
-                self.current_col = self.iter.left;
-                self.current_row += 1;
-            }
-
-            Some((value, col, row))
-        } else {
-            None
+        self.iter.next_with_coords()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.iter.remaining();
+        (remaining, Some(remaining))
+    }
+
+    fn count(self) -> usize {
+        self.iter.remaining()
+    }
+}
+
+impl<'a, V> ExactSizeIterator for IterWithCoords<'a, V> {}
+
+impl<'a, V> FusedIterator for IterWithCoords<'a, V> {}
+
+impl<'a, V> IterWithCoords<'a, V> {
+    /// Inverts Y iteration direction
+    pub fn y_down(self) -> Self {
+        Self {
+            iter: self.iter.y_down(),
+        }
+    }
+
+    /// Iterates from the right column to the left one instead, starting over from the first
+    /// cell.
+    pub fn x_right_to_left(self) -> Self {
+        Self {
+            iter: self.iter.x_right_to_left(),
         }
     }
+
+    /// Iterates column by column instead of row by row, starting over from the first cell.
+    pub fn column_major(self) -> Self {
+        Self {
+            iter: self.iter.column_major(),
+        }
+    }
+
+    /// Rewinds to the first cell of the rect, keeping the Y direction.
+    pub fn reset(&mut self) {
+        self.iter.reset();
+    }
 }
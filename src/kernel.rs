@@ -0,0 +1,67 @@
+use super::*;
+
+/// How "apply_kernel" treats the parts of a window that fall outside the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeMode {
+    /// Uses the nearest cell on the grid edge.
+    Clamp,
+    /// Uses the cell on the opposite side of the grid.
+    Wrap,
+    /// Leaves those cells out of the window.
+    Skip,
+}
+
+impl EdgeMode {
+    // Resolves a possibly out of range index along an axis with "count" cells.
+    fn resolve(self, index: isize, count: usize) -> Option<usize> {
+        let count = count as isize;
+        match self {
+            EdgeMode::Clamp => Some(index.clamp(0, count - 1) as usize),
+            EdgeMode::Wrap => Some(index.rem_euclid(count) as usize),
+            EdgeMode::Skip => (index >= 0 && index < count).then_some(index as usize),
+        }
+    }
+}
+
+impl<V> Grid<V> {
+    /// Returns a new Grid where each cell is the result of "func", which receives the cell and
+    /// every cell in the square window of "radius" cells around it, including itself, as
+    /// ((column offset, row offset), value) pairs ordered row by row starting at the bottom
+    /// row. "edge" controls windows crossing the grid edges. Results go to the new Grid, so
+    /// every window sees the original values.
+    pub fn apply_kernel<U, F>(&self, radius: usize, edge: EdgeMode, mut func: F) -> Grid<U>
+    where
+        F: FnMut(&V, &[((isize, isize), &V)]) -> U,
+    {
+        let radius = radius as isize;
+        let mut window = Vec::new();
+        let mut grid = Grid::from_fn(
+            self.width,
+            self.height,
+            self.columns,
+            self.rows,
+            self.offset_x,
+            self.offset_y,
+            |col, row| {
+                window.clear();
+                for d_row in -radius..=radius {
+                    let Some(source_row) = edge.resolve(row as isize + d_row, self.rows) else {
+                        continue;
+                    };
+                    for d_col in -radius..=radius {
+                        let Some(source_col) = edge.resolve(col as isize + d_col, self.columns)
+                        else {
+                            continue;
+                        };
+                        let cell = &self.data[self.flat_index(source_col, source_row)];
+                        window.push(((d_col, d_row), cell));
+                    }
+                }
+                func(&self.data[self.flat_index(col, row)], &window)
+            },
+        );
+        grid.x = self.x;
+        grid.y = self.y;
+        grid
+    }
+}
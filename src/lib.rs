@@ -1,41 +1,163 @@
 //! A Rectangular grid of non-overlapping rects containing a single generic item each.
 //! Its dimensions can be centered around (0.0, 0.0) or start at the lower-left corner.
-//! Once created, allows retrieving its contents via physical, f32 coordinates
-//! or directly from colums/row indices.
+//! Once created, allows retrieving its contents via physical coordinates (f32, or f64 with
+//! the "f64" feature) or directly from colums/row indices.
+//! The crate is no_std and uses libm for float math; the "std" feature uses the standard
+//! library's float functions instead.
 
 #![no_std]
 
-use libm::floorf;
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::fmt::{self, Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::{Index, IndexMut};
+use scalar::{ceil, floor};
+
+mod scalar;
+pub use scalar::Scalar;
 
 mod iter;
 pub use iter::*;
 
+mod iter_mut;
+pub use iter_mut::*;
+
 mod iter_coords;
 pub use iter_coords::*;
 
 mod iter_with_coords;
 pub use iter_with_coords::*;
 
+mod iter_info;
+pub use iter_info::*;
+
+mod cell_ref;
+pub use cell_ref::*;
+
+mod cursor;
+pub use cursor::*;
+
+mod cell_id;
+pub use cell_id::*;
+
+mod view;
+pub use view::*;
+
+mod into_iter;
+pub use into_iter::*;
+
+mod iter_border;
+pub use iter_border::*;
+
+mod iter_spiral;
+pub use iter_spiral::*;
+
+mod iter_segment;
+pub use iter_segment::*;
+
+mod iter_swept;
+pub use iter_swept::*;
+
+mod raycast;
+pub use raycast::*;
+
+mod neighbors;
+pub use neighbors::*;
+
+mod path;
+pub use path::*;
+
+mod prefix_sums;
+pub use prefix_sums::*;
+
+mod kernel;
+pub use kernel::*;
+
+mod automaton;
+pub use automaton::*;
+
+mod sparse;
+pub use sparse::*;
+
+mod occupancy;
+pub use occupancy::*;
+
+mod dirty;
+use dirty::DirtyTracker;
+
+mod builder;
+pub use builder::*;
+
+mod pivot;
+pub use pivot::*;
+
+mod error;
+pub use error::*;
+
+mod render;
+pub use render::*;
+
+mod reshape;
+
+mod shapes;
+
+mod flood;
+
+mod diff;
+
+mod option;
+
+mod query;
+
+mod gradient;
+
+mod pyramid;
+
+mod int;
+pub use int::*;
+
+#[cfg(feature = "serde")]
+mod serialize;
+
+#[cfg(feature = "bytemuck")]
+mod bytes;
+#[cfg(feature = "bytemuck")]
+pub use bytes::LittleEndian;
+
+#[cfg(feature = "glam")]
+mod vec2;
+
 #[cfg(test)]
 mod test;
 
 extern crate alloc;
 use alloc::vec::Vec;
 
-#[derive(Debug)]
+#[derive(Clone, PartialEq)]
 pub struct Grid<V> {
     // Dimensions
-    width: f32,
-    height: f32,
-    cell_width: f32,
-    cell_height: f32,
+    width: Scalar,
+    height: Scalar,
+    cell_width: Scalar,
+    cell_height: Scalar,
     columns: usize,
     rows: usize,
     //Pivot
-    offset_x: f32,
-    offset_y: f32,
-    // Storage
-    data: Vec<Vec<V>>,
+    offset_x: Scalar,
+    offset_y: Scalar,
+    // Position of the pivot in the world
+    x: Scalar,
+    y: Scalar,
+    // Storage, column by column.
+    data: Vec<V>,
+    // Changed cells, if tracking is enabled
+    dirty: DirtyTracker,
+    // Bumped when the number of columns or rows changes, to detect stale CellIds
+    generation: Generation,
 }
 
 // Standard Error message helper
@@ -45,24 +167,130 @@ macro_rules! err {
     };
 }
 
+// Clamps a physical coordinate to "min..min + size", excluding the upper edge. Values already
+// inside are returned unchanged.
+fn clamp_axis(value: Scalar, min: Scalar, size: Scalar) -> Scalar {
+    let local = value - min;
+    if local < 0.0 {
+        min
+    } else if local >= size {
+        let clamped = min + size.next_down();
+        // The sum may round back onto the edge, one step down is enough to leave it
+        if clamped - min >= size {
+            clamped.next_down()
+        } else {
+            clamped
+        }
+    } else {
+        value
+    }
+}
+
 // Default implementation always needs "width" and "height" provided.
 impl<V> Grid<V>
 where
     V: Default,
 {
-    pub fn new(width: f32, height: f32, columns: usize, rows: usize, centered: bool) -> Self {
+    /// Returns a Grid pre-filled with default values. If "centered" is true the pivot is
+    /// at the center of the grid, otherwise it is at the bottom-left corner.
+    pub fn new(width: Scalar, height: Scalar, columns: usize, rows: usize, centered: bool) -> Self {
         Self::new_with(width, height, columns, rows, centered, || {
             Default::default()
         })
     }
+
+    /// Same as "new", but returns an error instead of panicking if the size is not finite
+    /// and larger than zero, or if there are no columns or rows.
+    pub fn try_new(
+        width: Scalar,
+        height: Scalar,
+        columns: usize,
+        rows: usize,
+        centered: bool,
+    ) -> Result<Self, GridError> {
+        Self::try_new_with(width, height, columns, rows, centered, Default::default)
+    }
+
+    /// Replaces the content of the cell containing the provided coordinates with its default
+    /// value and returns the previous content, if any.
+    pub fn take_cell(&mut self, x: Scalar, y: Scalar) -> Option<V> {
+        let cell = self.get_cell_mut(x, y)?;
+        Some(mem::take(cell))
+    }
+
+    /// Replaces the content of the cell in the provided coordinates with its default value
+    /// and returns the previous content, if any.
+    pub fn take_cell_by_indices(&mut self, col: usize, row: usize) -> Option<V> {
+        let cell = self.get_cell_by_indices_mut(col, row)?;
+        Some(mem::take(cell))
+    }
+
+    /// Resets all cells to their default value, reusing the current allocation.
+    pub fn clear(&mut self) {
+        self.modify_all(|cell| *cell = V::default());
+    }
+}
+
+// Methods that need to clone cell contents.
+impl<V> Grid<V>
+where
+    V: Clone,
+{
+    /// Returns a Grid pre-filled with clones of "value".
+    pub fn new_cloned(
+        width: Scalar,
+        height: Scalar,
+        columns: usize,
+        rows: usize,
+        centered: bool,
+        value: V,
+    ) -> Self {
+        Self::new_with(width, height, columns, rows, centered, || value.clone())
+    }
+
+    /// Returns a copy of the cells in a flat Vec, row by row, starting at the bottom row.
+    pub fn to_flat_vec(&self) -> Vec<V> {
+        self.iter_all_cells().cloned().collect()
+    }
+
+    /// Overwrites every cell with a clone of "value", reusing the current allocation.
+    pub fn fill(&mut self, value: V) {
+        self.mark_all_dirty();
+        self.data.fill(value);
+    }
+
+    /// Overwrites every cell that overlaps a rectangle with a clone of "value". The last
+    /// cell receives "value" itself, so a single-cell rect never clones.
+    pub fn fill_rect(
+        &mut self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+        value: V,
+    ) {
+        let edges = self.get_edges(left, bottom, right, top);
+        self.mark_dirty_edges(edges);
+        let mut coords = Self::coords_in_edges(edges);
+        let Some((last_col, last_row)) = coords.next_back() else {
+            return;
+        };
+        for (col, row) in coords {
+            let index = self.flat_index(col, row);
+            self.data[index] = value.clone();
+        }
+        let index = self.flat_index(last_col, last_row);
+        self.data[index] = value;
+    }
 }
 
 // Unconstrained implementation.
 impl<V> Grid<V> {
-    /// Returns a Grid pre-filled with the result of function "func"
+    /// Returns a Grid pre-filled with the result of function "func". If "centered" is true
+    /// the pivot is at the center of the grid, otherwise it is at the bottom-left corner.
     pub fn new_with<F>(
-        width: f32,
-        height: f32,
+        width: Scalar,
+        height: Scalar,
         columns: usize,
         rows: usize,
         centered: bool,
@@ -71,43 +299,305 @@ impl<V> Grid<V> {
     where
         F: FnMut() -> V,
     {
-        assert!(width >= 0.0, err!("Width must be > 0.0"));
-        assert!(height >= 0.0, err!("Height must > 0.0"));
-        let cell_width = width / columns as f32;
-        let cell_height = height / rows as f32;
+        Self::new_with_coords(width, height, columns, rows, centered, |_, _| func())
+    }
+
+    /// Returns a Grid pre-filled with the result of function "func", which receives the
+    /// (column, row) coordinates of each cell.
+    pub fn new_with_coords<F>(
+        width: Scalar,
+        height: Scalar,
+        columns: usize,
+        rows: usize,
+        centered: bool,
+        func: F,
+    ) -> Self
+    where
+        F: FnMut(usize, usize) -> V,
+    {
+        match Self::try_new_with_coords(width, height, columns, rows, centered, func) {
+            Ok(grid) => grid,
+            Err(error) => panic!(err!("{}"), error),
+        }
+    }
+
+    /// Same as "new_with", but returns an error instead of panicking if the size is not
+    /// finite and larger than zero, or if there are no columns or rows.
+    pub fn try_new_with<F>(
+        width: Scalar,
+        height: Scalar,
+        columns: usize,
+        rows: usize,
+        centered: bool,
+        mut func: F,
+    ) -> Result<Self, GridError>
+    where
+        F: FnMut() -> V,
+    {
+        Self::try_new_with_coords(width, height, columns, rows, centered, |_, _| func())
+    }
+
+    /// Same as "new_with_coords", but returns an error instead of panicking if the size is
+    /// not finite and larger than zero, or if there are no columns or rows.
+    pub fn try_new_with_coords<F>(
+        width: Scalar,
+        height: Scalar,
+        columns: usize,
+        rows: usize,
+        centered: bool,
+        func: F,
+    ) -> Result<Self, GridError>
+    where
+        F: FnMut(usize, usize) -> V,
+    {
+        Self::validate_args(width, height, columns, rows)?;
+        let offset_x = if centered { width / 2.0 } else { 0.0 };
+        let offset_y = if centered { height / 2.0 } else { 0.0 };
+        Ok(Self::from_fn(
+            width, height, columns, rows, offset_x, offset_y, func,
+        ))
+    }
+
+    // Checks the arguments used to create a Grid.
+    pub(crate) fn validate_args(
+        width: Scalar,
+        height: Scalar,
+        columns: usize,
+        rows: usize,
+    ) -> Result<(), GridError> {
+        if !width.is_finite() || !height.is_finite() {
+            return Err(GridError::NonFiniteSize);
+        }
+        if width <= 0.0 || height <= 0.0 {
+            return Err(GridError::NonPositiveSize { width, height });
+        }
+        if columns == 0 {
+            return Err(GridError::ZeroColumns);
+        }
+        if rows == 0 {
+            return Err(GridError::ZeroRows);
+        }
+        Ok(())
+    }
 
-        Self {
+    /// Checks the internal invariants: the size is finite and positive, there is at least
+    /// one column and row, the number of cells is columns * rows, the cell size matches the
+    /// size (within a small relative tolerance), and the pivot and position are finite.
+    /// Useful as a safety net after deserializing or heavy mutation. The pivot may lie
+    /// outside the grid, so its range isn't checked.
+    pub fn validate(&self) -> Result<(), GridError> {
+        Self::validate_args(self.width, self.height, self.columns, self.rows)?;
+        let expected = self.columns * self.rows;
+        if self.data.len() != expected {
+            return Err(GridError::CellCountMismatch {
+                expected,
+                found: self.data.len(),
+            });
+        }
+        for (field, value) in [
+            ("cell_width", self.cell_width),
+            ("cell_height", self.cell_height),
+            ("offset_x", self.offset_x),
+            ("offset_y", self.offset_y),
+            ("x", self.x),
+            ("y", self.y),
+        ] {
+            if !value.is_finite() {
+                return Err(GridError::NonFiniteValue { field });
+            }
+        }
+        for (field, found, size, count) in [
+            ("cell_width", self.cell_width, self.width, self.columns),
+            ("cell_height", self.cell_height, self.height, self.rows),
+        ] {
+            let expected = size / count as Scalar;
+            if (found - expected).abs() > expected * 1e-4 {
+                return Err(GridError::CellSizeMismatch {
+                    field,
+                    expected,
+                    found,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    // Creates a Grid without validating its arguments.
+    pub(crate) fn from_fn<F>(
+        width: Scalar,
+        height: Scalar,
+        columns: usize,
+        rows: usize,
+        offset_x: Scalar,
+        offset_y: Scalar,
+        mut func: F,
+    ) -> Self
+    where
+        F: FnMut(usize, usize) -> V,
+    {
+        let mut data = Vec::with_capacity(columns * rows);
+        for col in 0..columns {
+            for row in 0..rows {
+                data.push(func(col, row));
+            }
+        }
+        let mut grid = Self {
             width,
             height,
-            cell_width,
-            cell_height,
+            cell_width: 0.0,
+            cell_height: 0.0,
             columns,
             rows,
+            offset_x,
+            offset_y,
+            x: 0.0,
+            y: 0.0,
+            data,
+            dirty: DirtyTracker::default(),
+            generation: Generation::default(),
+        };
+        grid.recompute_cell_size();
+        grid
+    }
+
+    /// Creates a Grid from a flat list of cells ordered row by row, starting at the bottom
+    /// row, as returned by "into_flat_vec". Fails if the number of cells isn't
+    /// columns * rows, or if the size is invalid.
+    pub fn from_flat_vec(
+        width: Scalar,
+        height: Scalar,
+        columns: usize,
+        rows: usize,
+        centered: bool,
+        cells: Vec<V>,
+    ) -> Result<Self, GridError> {
+        let offset_x = if centered { width / 2.0 } else { 0.0 };
+        let offset_y = if centered { height / 2.0 } else { 0.0 };
+        Self::from_rows(width, height, columns, rows, offset_x, offset_y, cells)
+    }
+
+    /// Creates a Grid from a list of columns, from left to right, each one listing its cells
+    /// from bottom to top. The number of columns and rows is taken from the data. Fails if
+    /// there are no columns or rows, if a column's length differs from the first one, or if
+    /// the size is invalid.
+    pub fn from_nested(
+        width: Scalar,
+        height: Scalar,
+        centered: bool,
+        columns: Vec<Vec<V>>,
+    ) -> Result<Self, GridError> {
+        // Checked before the size, which "try_from" derives from the counts
+        let rows = columns.first().map_or(0, Vec::len);
+        if columns.is_empty() {
+            return Err(GridError::ZeroColumns);
+        }
+        if rows == 0 {
+            return Err(GridError::ZeroRows);
+        }
+        Self::validate_args(width, height, columns.len(), rows)?;
+        if let Some((col, column)) = columns
+            .iter()
+            .enumerate()
+            .find(|(_, column)| column.len() != rows)
+        {
+            return Err(GridError::RaggedColumn {
+                col,
+                expected: rows,
+                found: column.len(),
+            });
+        }
+        let mut grid = Self {
+            width,
+            height,
+            cell_width: 0.0,
+            cell_height: 0.0,
+            columns: columns.len(),
+            rows,
             offset_x: if centered { width / 2.0 } else { 0.0 },
             offset_y: if centered { height / 2.0 } else { 0.0 },
-            data: (0..columns)
-                .map(|_| (0..rows).map(|_| func()).collect())
-                .collect(),
+            x: 0.0,
+            y: 0.0,
+            data: columns.into_iter().flatten().collect(),
+            dirty: DirtyTracker::default(),
+            generation: Generation::default(),
+        };
+        grid.recompute_cell_size();
+        Ok(grid)
+    }
+
+    // Validates the arguments and creates a Grid from cells ordered row by row.
+    pub(crate) fn from_rows(
+        width: Scalar,
+        height: Scalar,
+        columns: usize,
+        rows: usize,
+        offset_x: Scalar,
+        offset_y: Scalar,
+        cells: Vec<V>,
+    ) -> Result<Self, GridError> {
+        Self::validate_args(width, height, columns, rows)?;
+        let expected = columns * rows;
+        if cells.len() != expected {
+            return Err(GridError::CellCountMismatch {
+                expected,
+                found: cells.len(),
+            });
+        }
+        // Transpose the rows into columns.
+        let mut columns_data: Vec<Vec<V>> =
+            (0..columns).map(|_| Vec::with_capacity(rows)).collect();
+        for (i, cell) in cells.into_iter().enumerate() {
+            columns_data[i % columns].push(cell);
         }
+        let data = columns_data.into_iter().flatten().collect();
+        let mut grid = Self {
+            width,
+            height,
+            cell_width: 0.0,
+            cell_height: 0.0,
+            columns,
+            rows,
+            offset_x,
+            offset_y,
+            x: 0.0,
+            y: 0.0,
+            data,
+            dirty: DirtyTracker::default(),
+            generation: Generation::default(),
+        };
+        grid.recompute_cell_size();
+        Ok(grid)
+    }
+
+    /// Consumes the grid and returns its cells in a flat Vec, row by row, starting at the
+    /// bottom row.
+    pub fn into_flat_vec(self) -> Vec<V> {
+        self.into_iter().collect()
+    }
+
+    // Updates the cell dimensions from the physical size and the column/row counts.
+    fn recompute_cell_size(&mut self) {
+        self.cell_width = self.width / self.columns as Scalar;
+        self.cell_height = self.height / self.rows as Scalar;
     }
 
     /// Physical width.
-    pub fn width(&self) -> f32 {
+    pub fn width(&self) -> Scalar {
         self.width
     }
 
     /// Physical height.
-    pub fn height(&self) -> f32 {
+    pub fn height(&self) -> Scalar {
         self.height
     }
 
     /// Physical width of each cell.
-    pub fn cell_width(&self) -> f32 {
+    pub fn cell_width(&self) -> Scalar {
         self.cell_width
     }
 
     /// Physical height of each cell.
-    pub fn cell_height(&self) -> f32 {
+    pub fn cell_height(&self) -> Scalar {
         self.cell_height
     }
 
@@ -122,80 +612,437 @@ impl<V> Grid<V> {
     }
 
     /// The left-most edge occupied by the Grid. This is the Y origin if grid is not centered.
-    pub fn left(&self) -> f32 {
-        -self.offset_x
+    pub fn left(&self) -> Scalar {
+        self.x - self.offset_x
     }
 
     /// The right-most edge occupied by the Grid.
-    pub fn right(&self) -> f32 {
-        self.width - self.offset_x
+    pub fn right(&self) -> Scalar {
+        self.left() + self.width
     }
 
     /// The bottom-most edge occupied by the Grid. WARNING, coordinates are Y up
     /// (positive values go up), so this is the Y origin if the grid is not centered.
-    pub fn bottom(&self) -> f32 {
-        -self.offset_y
+    pub fn bottom(&self) -> Scalar {
+        self.y - self.offset_y
     }
 
     /// The top-most edge occupied by the Grid. WARNING, coordinates are Y up (positive values go up).
-    pub fn top(&self) -> f32 {
-        self.height - self.offset_x
+    pub fn top(&self) -> Scalar {
+        self.bottom() + self.height
     }
 
     /// The horizontal offset if the center is not at (0.0, 0.0)
-    pub fn offset_x(&self) -> f32 {
+    pub fn offset_x(&self) -> Scalar {
         self.offset_x
     }
 
     /// The vertical offset if the center is not at (0.0, 0.0)
-    pub fn offset_y(&self) -> f32 {
+    pub fn offset_y(&self) -> Scalar {
         self.offset_y
     }
 
+    /// Returns the normalized (x, y) pivot, where (0.0, 0.0) is the bottom-left corner and
+    /// (1.0, 1.0) the top-right one.
+    pub fn pivot(&self) -> (Scalar, Scalar) {
+        (self.offset_x / self.width, self.offset_y / self.height)
+    }
+
+    /// Sets the normalized pivot. The Grid's position and cell contents stay the same, but the
+    /// Grid now extends from its position according to the new pivot, so physical
+    /// coordinates may map to different cells.
+    ///
+    /// # Panics
+    ///
+    /// Panics if "x" or "y" is NaN or infinite.
+    pub fn set_pivot(&mut self, x: Scalar, y: Scalar) {
+        assert!(
+            x.is_finite() && y.is_finite(),
+            err!("Pivot ({}, {}) is not finite"),
+            x,
+            y
+        );
+        self.offset_x = self.width * x;
+        self.offset_y = self.height * y;
+    }
+
+    /// Sets the pivot to one of the named presets. See "set_pivot".
+    pub fn set_pivot_preset(&mut self, pivot: Pivot) {
+        let (x, y) = pivot.normalized();
+        self.set_pivot(x, y);
+    }
+
+    /// Horizontal position of the pivot in the world.
+    pub fn x(&self) -> Scalar {
+        self.x
+    }
+
+    /// Vertical position of the pivot in the world.
+    pub fn y(&self) -> Scalar {
+        self.y
+    }
+
+    /// Moves the Grid so that its pivot is at (x, y). All physical coordinates used in
+    /// lookups and queries are relative to the world, so they follow the new position.
+    /// Cell contents are not affected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if "x" or "y" is NaN or infinite.
+    pub fn set_position(&mut self, x: Scalar, y: Scalar) {
+        assert!(
+            x.is_finite() && y.is_finite(),
+            err!("Position ({}, {}) is not finite"),
+            x,
+            y
+        );
+        self.x = x;
+        self.y = y;
+    }
+
+    /// Moves the Grid by (dx, dy). See "set_position".
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new position is NaN or infinite.
+    pub fn translate(&mut self, dx: Scalar, dy: Scalar) {
+        self.set_position(self.x + dx, self.y + dy);
+    }
+
+    // Converts a physical x coordinate to the grid's local space, where 0.0 is its left edge.
+    #[inline]
+    fn local_x(&self, x: Scalar) -> Scalar {
+        x - self.left()
+    }
+
+    // Converts a physical y coordinate to the grid's local space, where 0.0 is its bottom edge.
+    #[inline]
+    fn local_y(&self, y: Scalar) -> Scalar {
+        y - self.bottom()
+    }
+
+    /// Returns true if the point is inside the grid. Points on the right or top edges are
+    /// considered outside, same as in "get_cell_coords".
+    pub fn contains_point(&self, x: Scalar, y: Scalar) -> bool {
+        let x = self.local_x(x);
+        let y = self.local_y(y);
+        x >= 0.0 && x < self.width && y >= 0.0 && y < self.height
+    }
+
+    /// Returns true if the rectangle overlaps at least one cell, using the same rules as the
+    /// rect iterators.
+    pub fn intersects_rect(
+        &self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+    ) -> bool {
+        self.get_edges(left, bottom, right, top).is_some()
+    }
+
+    /// Returns the point inside the grid that is nearest to the provided one. Since the right
+    /// and top edges are outside the grid, points beyond them are moved right below them.
+    pub fn clamp_point(&self, x: Scalar, y: Scalar) -> (Scalar, Scalar) {
+        (
+            clamp_axis(x, self.left(), self.width),
+            clamp_axis(y, self.bottom(), self.height),
+        )
+    }
+
     /// Returns an optional tuple with the current coordinates in the (column, row) format, given
     /// x and y "physical" coordinates.
-    pub fn get_cell_coords(&self, x: f32, y: f32) -> Option<(usize, usize)> {
-        let x = x + self.offset_x;
-        if x < 0.0 {
+    /// A point on the boundary between two cells belongs to the cell on its right (or above
+    /// it), the one whose left (or bottom) edge it is on. Points on the right or top edges
+    /// are considered outside the grid. See "cells_touching_point" to get every cell.
+    pub fn get_cell_coords(&self, x: Scalar, y: Scalar) -> Option<(usize, usize)> {
+        if !self.contains_point(x, y) {
             return None;
         }
-        let y = y + self.offset_y;
-        if y < 0.0 {
+        let x = self.local_x(x);
+        let y = self.local_y(y);
+        let col = floor(x / self.cell_width) as usize;
+        let row = floor(y / self.cell_height) as usize;
+        // Guards against float rounding right below the edges.
+        Some((col.min(self.columns - 1), row.min(self.rows - 1)))
+    }
+
+    /// Returns the (column, row) coordinates given x and y "physical" coordinates, without
+    /// checking the grid bounds. Negative results are returned as signed values.
+    pub fn get_cell_coords_unclamped(&self, x: Scalar, y: Scalar) -> (isize, isize) {
+        let col = floor(self.local_x(x) / self.cell_width) as isize;
+        let row = floor(self.local_y(y) / self.cell_height) as isize;
+        (col, row)
+    }
+
+    /// Returns an iterator with the (column, row) coordinates of every cell whose edges are
+    /// within "epsilon" of the point, measured separately along each axis. Unlike
+    /// "get_cell_coords" boundaries are shared, so a point on the edge between two cells
+    /// yields both, and one on a corner yields the four cells around it, even with an epsilon
+    /// of 0.0. That includes the grid's right and top edges.
+    pub fn cells_touching_point(&self, x: Scalar, y: Scalar, epsilon: Scalar) -> IterCoords {
+        let epsilon = epsilon.max(0.0);
+        let (x, y) = (self.local_x(x), self.local_y(y));
+        let edges = if x + epsilon >= 0.0
+            && x - epsilon <= self.width
+            && y + epsilon >= 0.0
+            && y - epsilon <= self.height
+        {
+            // Cells are closed on both ends here, so the right and top edges count too.
+            let (max_col, max_row) = (self.columns - 1, self.rows - 1);
+            let first = |pos: Scalar, size: Scalar, max: usize| {
+                ((ceil((pos - epsilon) / size) - 1.0).max(0.0) as usize).min(max)
+            };
+            let last = |pos: Scalar, size: Scalar, max: usize| {
+                (floor((pos + epsilon) / size).max(0.0) as usize).min(max)
+            };
+            Some((
+                first(x, self.cell_width, max_col),
+                first(y, self.cell_height, max_row),
+                last(x, self.cell_width, max_col),
+                last(y, self.cell_height, max_row),
+            ))
+        } else {
+            None
+        };
+        Self::coords_in_edges(edges)
+    }
+
+    /// Returns the (left, bottom, right, top) edges of a cell in physical coordinates, with the
+    /// pivot applied. None if the indices are out of range.
+    pub fn cell_rect(&self, col: usize, row: usize) -> Option<(Scalar, Scalar, Scalar, Scalar)> {
+        if col >= self.columns || row >= self.rows {
             return None;
         }
-        let col = libm::floorf(x / self.cell_width) as usize;
-        let row = libm::floorf(y / self.cell_height) as usize;
-        Some((col, row))
+        let left = (col as Scalar * self.cell_width) + self.left();
+        let bottom = (row as Scalar * self.cell_height) + self.bottom();
+        Some((
+            left,
+            bottom,
+            left + self.cell_width,
+            bottom + self.cell_height,
+        ))
+    }
+
+    /// Returns the center of a cell in physical coordinates, with the pivot applied.
+    /// None if the indices are out of range.
+    pub fn cell_center(&self, col: usize, row: usize) -> Option<(Scalar, Scalar)> {
+        let (left, bottom, right, top) = self.cell_rect(col, row)?;
+        Some(((left + right) / 2.0, (bottom + top) / 2.0))
+    }
+
+    /// Returns the (column, row) coordinates of the cell nearest to the provided point. Points
+    /// outside the grid are clamped to its edges, so this always succeeds.
+    pub fn get_cell_coords_clamped(&self, x: Scalar, y: Scalar) -> (usize, usize) {
+        let x = self.local_x(x).clamp(0.0, self.width);
+        let y = self.local_y(y).clamp(0.0, self.height);
+        let col = floor(x / self.cell_width) as usize;
+        let row = floor(y / self.cell_height) as usize;
+        (col.min(self.columns - 1), row.min(self.rows - 1))
+    }
+
+    /// Returns a reference to the content of the cell nearest to the provided point.
+    pub fn get_cell_clamped(&self, x: Scalar, y: Scalar) -> &V {
+        let (col, row) = self.get_cell_coords_clamped(x, y);
+        &self.data[self.flat_index(col, row)]
+    }
+
+    /// Returns a mutable reference to the content of the cell nearest to the provided point.
+    pub fn get_cell_clamped_mut(&mut self, x: Scalar, y: Scalar) -> &mut V {
+        let (col, row) = self.get_cell_coords_clamped(x, y);
+        self.mark_dirty(col, row);
+        let index = self.flat_index(col, row);
+        &mut self.data[index]
+    }
+
+    /// Returns the (column, row) coordinates of the cell containing the provided point, with
+    /// coordinates wrapping around the grid's physical size in both directions.
+    pub fn get_cell_coords_wrapped(&self, x: Scalar, y: Scalar) -> (usize, usize) {
+        let col = floor(wrap(self.local_x(x), self.width) / self.cell_width) as usize;
+        let row = floor(wrap(self.local_y(y), self.height) / self.cell_height) as usize;
+        // Guards against float rounding right below the edges.
+        (col.min(self.columns - 1), row.min(self.rows - 1))
+    }
+
+    /// Returns a reference to the content of the cell containing the provided point, with
+    /// coordinates wrapping around the grid.
+    pub fn get_cell_wrapped(&self, x: Scalar, y: Scalar) -> &V {
+        let (col, row) = self.get_cell_coords_wrapped(x, y);
+        &self.data[self.flat_index(col, row)]
+    }
+
+    /// Returns a mutable reference to the content of the cell containing the provided point,
+    /// with coordinates wrapping around the grid.
+    pub fn get_cell_wrapped_mut(&mut self, x: Scalar, y: Scalar) -> &mut V {
+        let (col, row) = self.get_cell_coords_wrapped(x, y);
+        self.mark_dirty(col, row);
+        let index = self.flat_index(col, row);
+        &mut self.data[index]
     }
 
     /// Returns an optional reference to the content of a cell containing the
     /// provided coordinates, if any.
-    pub fn get_cell(&self, x: f32, y: f32) -> Option<&V> {
+    pub fn get_cell(&self, x: Scalar, y: Scalar) -> Option<&V> {
         let coords = self.get_cell_coords(x, y)?;
         self.get_cell_by_indices(coords.0, coords.1)
     }
 
     /// Returns an optional mutable reference to the content of a cell containing the
     /// provided coordinates, if any.
-    pub fn get_cell_mut(&mut self, x: f32, y: f32) -> Option<&mut V> {
+    pub fn get_cell_mut(&mut self, x: Scalar, y: Scalar) -> Option<&mut V> {
         let coords = self.get_cell_coords(x, y)?;
         self.get_cell_by_indices_mut(coords.0, coords.1)
     }
 
+    /// Same as "get_cell_coords", but takes a point: a tuple, an array, or any type that
+    /// converts into "[x, y]", like mint or glam vectors.
+    pub fn get_cell_coords_at(&self, point: impl Into<[Scalar; 2]>) -> Option<(usize, usize)> {
+        let [x, y] = point.into();
+        self.get_cell_coords(x, y)
+    }
+
+    /// Same as "get_cell", but takes a point. See "get_cell_coords_at".
+    pub fn get_cell_at(&self, point: impl Into<[Scalar; 2]>) -> Option<&V> {
+        let [x, y] = point.into();
+        self.get_cell(x, y)
+    }
+
+    /// Same as "get_cell_mut", but takes a point. See "get_cell_coords_at".
+    pub fn get_cell_at_mut(&mut self, point: impl Into<[Scalar; 2]>) -> Option<&mut V> {
+        let [x, y] = point.into();
+        self.get_cell_mut(x, y)
+    }
+
     /// Returns an optional reference to the content of a cell in the
     /// provided coordinates, if any.
     pub fn get_cell_by_indices(&self, col: usize, row: usize) -> Option<&V> {
-        let col = self.data.get(col)?;
-        let cell = col.get(row)?;
-        Some(cell)
+        let index = self.raw_index(col, row)?;
+        Some(&self.data[index])
     }
 
     /// Returns an optional mutable reference to the content of a cell in the
     /// provided coordinates, if any.
     pub fn get_cell_by_indices_mut(&mut self, col: usize, row: usize) -> Option<&mut V> {
-        let col = self.data.get_mut(col)?;
-        let cell = col.get_mut(row)?;
-        Some(cell)
+        let index = self.raw_index(col, row)?;
+        self.mark_dirty(col, row);
+        Some(&mut self.data[index])
+    }
+
+    /// Same as "get_cell", but returns an error describing why the lookup failed.
+    pub fn try_get_cell(&self, x: Scalar, y: Scalar) -> Result<&V, GridError> {
+        let (col, row) = self
+            .get_cell_coords(x, y)
+            .ok_or(GridError::PointOutOfBounds { x, y })?;
+        self.try_get_cell_by_indices(col, row)
+    }
+
+    /// Same as "get_cell_by_indices", but returns an error describing why the lookup failed.
+    pub fn try_get_cell_by_indices(&self, col: usize, row: usize) -> Result<&V, GridError> {
+        self.get_cell_by_indices(col, row)
+            .ok_or(GridError::IndicesOutOfRange { col, row })
+    }
+
+    /// Stores a value in the cell containing the provided coordinates and returns the
+    /// previous contents. If the coordinates are outside the grid the value is given back
+    /// as an error.
+    pub fn set_cell(&mut self, x: Scalar, y: Scalar, value: V) -> Result<V, V> {
+        match self.get_cell_coords(x, y) {
+            Some((col, row)) => self.set_cell_by_indices(col, row, value),
+            None => Err(value),
+        }
+    }
+
+    /// Stores a value in the cell at the provided indices and returns the previous
+    /// contents. If the indices are out of range the value is given back as an error.
+    pub fn set_cell_by_indices(&mut self, col: usize, row: usize, value: V) -> Result<V, V> {
+        match self.get_cell_by_indices_mut(col, row) {
+            Some(cell) => Ok(mem::replace(cell, value)),
+            None => Err(value),
+        }
+    }
+
+    /// Stores a value in the cell at the provided indices and returns the previous contents,
+    /// or None (dropping the value) if the indices are out of range.
+    pub fn replace_cell_by_indices(&mut self, col: usize, row: usize, value: V) -> Option<V> {
+        self.set_cell_by_indices(col, row, value).ok()
+    }
+
+    /// Returns mutable references to several cells at once, given their (column, row)
+    /// coordinates. Returns None if any coordinate is out of range or appears twice.
+    pub fn get_disjoint_cells_mut<const N: usize>(
+        &mut self,
+        coords: [(usize, usize); N],
+    ) -> Option<[&mut V; N]> {
+        let mut indices = [0; N];
+        for (index, (col, row)) in indices.iter_mut().zip(coords) {
+            *index = self.raw_index(col, row)?;
+        }
+        for (col, row) in coords {
+            self.mark_dirty(col, row);
+        }
+        self.data.get_disjoint_mut(indices).ok()
+    }
+
+    /// Returns mutable references to two different cells, given their (column, row)
+    /// coordinates. Returns None if either is out of range or both are the same cell.
+    pub fn get_pair_mut(
+        &mut self,
+        a: (usize, usize),
+        b: (usize, usize),
+    ) -> Option<(&mut V, &mut V)> {
+        let [a, b] = self.get_disjoint_cells_mut([a, b])?;
+        Some((a, b))
+    }
+
+    /// Exchanges the contents of two cells. Swapping a cell with itself does nothing.
+    pub fn swap_cells(
+        &mut self,
+        col_a: usize,
+        row_a: usize,
+        col_b: usize,
+        row_b: usize,
+    ) -> Result<(), GridError> {
+        for (col, row) in [(col_a, row_a), (col_b, row_b)] {
+            if col >= self.columns || row >= self.rows {
+                return Err(GridError::IndicesOutOfRange { col, row });
+            }
+        }
+        let index_a = self.flat_index(col_a, row_a);
+        let index_b = self.flat_index(col_b, row_b);
+        self.data.swap(index_a, index_b);
+        self.mark_dirty(col_a, row_a);
+        self.mark_dirty(col_b, row_b);
+        Ok(())
+    }
+
+    /// Returns a reference to the content of a cell without checking the indices.
+    /// Use indexing (grid[(col, row)]) for a checked version that panics instead.
+    ///
+    /// # Safety
+    /// "col" must be lower than "columns()" and "row" lower than "rows()".
+    #[inline]
+    pub unsafe fn get_cell_by_indices_unchecked(&self, col: usize, row: usize) -> &V {
+        debug_assert!(
+            col < self.columns && row < self.rows,
+            err!("Unchecked cell indices out of range")
+        );
+        self.data.get_unchecked(self.flat_index(col, row))
+    }
+
+    /// Returns a mutable reference to the content of a cell without checking the indices.
+    /// Use indexing (grid[(col, row)]) for a checked version that panics instead.
+    ///
+    /// # Safety
+    /// "col" must be lower than "columns()" and "row" lower than "rows()".
+    #[inline]
+    pub unsafe fn get_cell_by_indices_unchecked_mut(&mut self, col: usize, row: usize) -> &mut V {
+        debug_assert!(
+            col < self.columns && row < self.rows,
+            err!("Unchecked cell indices out of range")
+        );
+        self.mark_dirty(col, row);
+        let index = self.flat_index(col, row);
+        self.data.get_unchecked_mut(index)
     }
 
     /// Allows a single function to modify the contents of all cells.
@@ -204,36 +1051,49 @@ impl<V> Grid<V> {
     where
         F: FnMut(&mut V),
     {
-        for col in &mut self.data {
-            for cell in col {
-                func(cell)
-            }
+        self.mark_all_dirty();
+        for cell in &mut self.data {
+            func(cell)
         }
     }
 
+    // Returns the (left, bottom, right, top) cell indices overlapping a rectangle, clamped to
+    // the grid. Returns None if the rectangle is entirely outside the grid.
     fn get_edges(
         &self,
-        left: f32,
-        bottom: f32,
-        right: f32,
-        top: f32,
-    ) -> (usize, usize, usize, usize) {
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+    ) -> Option<(usize, usize, usize, usize)> {
         // Apply offsets
-        let left = left + self.offset_x;
-        let bottom = bottom + self.offset_y;
-        let right = right + self.offset_x;
-        let top = top + self.offset_y;
+        let left = self.local_x(left);
+        let bottom = self.local_y(bottom);
+        let right = self.local_x(right);
+        let top = self.local_y(top);
+        if right < 0.0 || top < 0.0 || left >= self.width || bottom >= self.height {
+            return None;
+        }
+        // Written this way so NaN edges, which fail every comparison, are rejected too
+        let ordered = left <= right && bottom <= top;
+        if !ordered {
+            return None;
+        }
         // Get columns and rows
         //
-        let col_left = floorf(left / self.cell_width).max(0.0) as usize;
-        let row_bottom = floorf(bottom / self.cell_height).max(0.0) as usize;
+        let col_left = floor(left / self.cell_width).max(0.0) as usize;
+        let row_bottom = floor(bottom / self.cell_height).max(0.0) as usize;
 
-        let max_right = self.data.len() - 1;
-        let col_right = (floorf(right / self.cell_width) as usize).min(max_right);
+        let max_right = self.columns - 1;
+        let col_right = (floor(right / self.cell_width) as usize).min(max_right);
 
-        let max_top = self.data[0].len() - 1;
-        let row_top = (floorf(top / self.cell_height) as usize).min(max_top);
-        (col_left, row_bottom, col_right, row_top)
+        let max_top = self.rows - 1;
+        let row_top = (floor(top / self.cell_height) as usize).min(max_top);
+        let col_left = col_left.min(max_right);
+        let row_bottom = row_bottom.min(max_top);
+        // The iterators read cells unchecked, so this must hold even in release builds
+        assert!(col_left <= col_right && row_bottom <= row_top);
+        Some((col_left, row_bottom, col_right, row_top))
     }
 
     /// Returns an iterator with the cells overlapping a rectangle, starting at the
@@ -241,92 +1101,557 @@ impl<V> Grid<V> {
     /// and from top to bottom if y_up is "false".
     pub fn iter_cells_in_rect(
         &self,
-        left: f32,
-        bottom: f32,
-        right: f32,
-        top: f32,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
     ) -> IterGridRect<'_, V> {
-        let (col_left, row_bottom, col_right, row_top) = self.get_edges(left, bottom, right, top);
-        // Create and return the iterator with calculated bounds
-        // println!("{}, {} -> {}, {}", col_left, row_bottom, col_right, row_top);
+        self.iter_in_edges(self.get_edges(left, bottom, right, top))
+    }
+
+    // Returns an iterator over the cells within the (left, bottom, right, top) cell indices.
+    // None creates an iterator that is already finished.
+    fn iter_in_edges(&self, edges: Option<(usize, usize, usize, usize)>) -> IterGridRect<'_, V> {
         IterGridRect {
-            y_up: true,
             grid: self,
-            left: col_left,
-            right: col_right,
-            top: row_top,
-            bottom: row_bottom,
-            current_row: row_bottom,
-            current_col: col_left,
-            done: false,
+            coords: Self::coords_in_edges(edges),
         }
     }
 
-    /// Returns an iterator with all cells.
+    /// Returns an iterator with mutable references to the cells overlapping a rectangle,
+    /// in the same order as "iter_cells_in_rect".
+    pub fn iter_cells_in_rect_mut(
+        &mut self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+    ) -> IterGridRectMut<'_, V> {
+        let coords = self.iter_coords(left, bottom, right, top);
+        self.iter_mut_with(coords)
+    }
+
+    /// Returns an iterator with all cells, row by row, starting at the bottom row.
     pub fn iter_all_cells(&self) -> IterGridRect<'_, V> {
-        // Create and return the iterator with calculated bounds
-        // println!("{}, {} -> {}, {}", col_left, row_bottom, col_right, row_top);
-        IterGridRect {
-            y_up: true,
-            grid: self,
-            left: 0,
-            right: self.columns()-1,
-            top: self.rows()-1,
-            bottom: 0,
-            current_row: 0,
-            current_col: 0,
-            done: false,
+        self.iter_in_edges(Some((0, 0, self.columns - 1, self.rows - 1)))
+    }
+
+    /// Returns an iterator with mutable references to all cells, in the same order as
+    /// "iter_all_cells": row by row, starting at the bottom row.
+    pub fn iter_all_cells_mut(&mut self) -> IterGridRectMut<'_, V> {
+        let coords = Self::coords_in_edges(Some((0, 0, self.columns - 1, self.rows - 1)));
+        self.iter_mut_with(coords)
+    }
+
+    /// Returns an iterator that yields (value, column, row) tuples for all cells, row by row,
+    /// starting at the bottom row.
+    pub fn iter_all_with_coords(&self) -> IterWithCoords<'_, V> {
+        self.iter_all_cells().enumerate_coords()
+    }
+
+    /// Returns an iterator that yields (mutable value, column, row) tuples for all cells,
+    /// row by row, starting at the bottom row.
+    pub fn iter_all_with_coords_mut(&mut self) -> IterWithCoordsMut<'_, V> {
+        self.iter_all_cells_mut().enumerate_coords()
+    }
+
+    /// Returns an iterator over the cells of a row, from left to right. Empty if the row is
+    /// out of range.
+    pub fn iter_row(&self, row: usize) -> impl Iterator<Item = &V> + '_ {
+        let count = if row < self.rows { self.columns } else { 0 };
+        self.data.iter().skip(row).step_by(self.rows).take(count)
+    }
+
+    /// Returns an iterator over mutable references to the cells of a row, from left to right.
+    /// Empty if the row is out of range.
+    pub fn iter_row_mut(&mut self, row: usize) -> impl Iterator<Item = &mut V> + '_ {
+        let count = if row < self.rows { self.columns } else { 0 };
+        if count > 0 {
+            self.mark_dirty_edges(Some((0, row, self.columns - 1, row)));
         }
+        let rows = self.rows;
+        self.data.iter_mut().skip(row).step_by(rows).take(count)
+    }
+
+    /// Returns an iterator over the cells of a column, from bottom to top. Empty if the column
+    /// is out of range.
+    pub fn iter_column(&self, col: usize) -> core::slice::Iter<'_, V> {
+        self.data[self.column_range(col)].iter()
+    }
+
+    /// Returns an iterator over mutable references to the cells of a column, from bottom to
+    /// top. Empty if the column is out of range.
+    pub fn iter_column_mut(&mut self, col: usize) -> core::slice::IterMut<'_, V> {
+        let range = self.column_range(col);
+        if !range.is_empty() {
+            self.mark_dirty_edges(Some((col, 0, col, self.rows - 1)));
+        }
+        self.data[range].iter_mut()
     }
 
     /// Returns an iterator that yields (column,row) pairs for each cell that overlaps the provided
     /// rectangle edges.
-    pub fn iter_coords(&self, left: f32, bottom: f32, right: f32, top: f32) -> IterCoords {
-        let (col_left, row_bottom, col_right, row_top) = self.get_edges(left, bottom, right, top);
-        IterCoords {
-            y_up: true,
-            top: row_top,
-            bottom: row_bottom,
-            left: col_left,
-            right: col_right,
-            current_row: row_bottom,
-            current_col: col_left,
-            done: false,
+    pub fn iter_coords(
+        &self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+    ) -> IterCoords {
+        Self::coords_in_edges(self.get_edges(left, bottom, right, top))
+    }
+
+    /// Returns an iterator that yields (column,row) pairs for each cell that overlaps the provided
+    /// rectangle edges, with coordinates wrapping around the grid. Rectangles crossing an edge
+    /// continue on the opposite side, and each cell is yielded at most once.
+    pub fn iter_coords_in_rect_wrapped(
+        &self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let columns = self.columns as isize;
+        let rows = self.rows as isize;
+        let col_left = floor(self.local_x(left) / self.cell_width) as isize;
+        let row_bottom = floor(self.local_y(bottom) / self.cell_height) as isize;
+        let mut col_right = floor(self.local_x(right) / self.cell_width) as isize;
+        let mut row_top = floor(self.local_y(top) / self.cell_height) as isize;
+        if left > right || bottom > top {
+            // Empty ranges
+            col_right = col_left - 1;
+            row_top = row_bottom - 1;
+        }
+        // Larger rectangles would visit cells more than once
+        let col_right = col_right.min(col_left.saturating_add(columns - 1));
+        let row_top = row_top.min(row_bottom.saturating_add(rows - 1));
+        (row_bottom..=row_top).flat_map(move |row| {
+            (col_left..=col_right).map(move |col| {
+                (
+                    col.rem_euclid(columns) as usize,
+                    row.rem_euclid(rows) as usize,
+                )
+            })
+        })
+    }
+
+    // Returns an iterator over the (left, bottom, right, top) cell indices. None
+    // creates an iterator that is already finished.
+    fn coords_in_edges(edges: Option<(usize, usize, usize, usize)>) -> IterCoords {
+        IterCoords::new(edges)
+    }
+
+    // Returns a mutable iterator visiting the provided coordinates.
+    fn iter_mut_with(&mut self, coords: IterCoords) -> IterGridRectMut<'_, V> {
+        if !coords.done {
+            // Checked once here, since the iterator reads cells unchecked
+            assert!(
+                coords.right < self.columns && coords.top < self.rows,
+                "IterGridRectMut: Error, rect ({}, {}, {}, {}) is out of range",
+                coords.left,
+                coords.bottom,
+                coords.right,
+                coords.top
+            );
+            self.mark_dirty_edges(Some((coords.left, coords.bottom, coords.right, coords.top)));
+        }
+        IterGridRectMut {
+            coords,
+            data: self.data.as_mut_ptr(),
+            columns: self.columns,
+            rows: self.rows,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns an iterator that yields the physical information (coordinates, edges and center)
+    /// of each cell that overlaps the provided rectangle edges.
+    pub fn iter_info_in_rect(
+        &self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+    ) -> IterInfo {
+        IterInfo {
+            coords: self.iter_coords(left, bottom, right, top),
+            cell_width: self.cell_width,
+            cell_height: self.cell_height,
+            left: self.left(),
+            bottom: self.bottom(),
         }
     }
 
     /// Allows a function to modify the contents of any cell that overlaps a rectangle.
     /// TODO: Update to use iter_coords so that all overlapping cells are considered
-    pub fn modify_in_rect<F>(&mut self, left: f32, bottom: f32, right: f32, top: f32, mut func: F)
-    where
+    pub fn modify_in_rect<F>(
+        &mut self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+        mut func: F,
+    ) where
         F: FnMut(&mut V),
     {
-        let (col_left, row_bottom, col_right, row_top) = self.get_edges(left, bottom, right, top);
+        for (col, row) in self.modified_cells(left, bottom, right, top) {
+            self.mark_dirty(col, row);
+            let index = self.flat_index(col, row);
+            func(&mut self.data[index]);
+        }
+    }
+
+    // Cells visited by "modify_in_rect", in order.
+    fn modified_cells(
+        &self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let Some((col_left, row_bottom, col_right, row_top)) =
+            self.get_edges(left, bottom, right, top)
+        else {
+            return [None; 4].into_iter().flatten();
+        };
         // Modify (if needed)!
-        if row_bottom != row_top {
-            let value = &mut self.data[col_left][row_top];
-            func(value);
-        }
-        if col_left != col_right {
-            let value = &mut self.data[col_right][row_bottom];
-            func(value);
-            if row_bottom != row_top {
-                let value = &mut self.data[col_right][row_top];
-                func(value);
+        let rows = row_bottom != row_top;
+        let cols = col_left != col_right;
+        [
+            rows.then_some((col_left, row_top)),
+            cols.then_some((col_right, row_bottom)),
+            (cols && rows).then_some((col_right, row_top)),
+            Some((col_left, row_bottom)),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// Same as "modify_in_rect", but returns an error if the rectangle doesn't overlap the grid.
+    pub fn try_modify_in_rect<F>(
+        &mut self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+        func: F,
+    ) -> Result<(), GridError>
+    where
+        F: FnMut(&mut V),
+    {
+        if self.get_edges(left, bottom, right, top).is_none() {
+            return Err(GridError::RectOutOfBounds {
+                left,
+                bottom,
+                right,
+                top,
+            });
+        }
+        self.modify_in_rect(left, bottom, right, top, func);
+        Ok(())
+    }
+
+    /// Returns a new Grid with the same dimensions and pivot, where each cell contains
+    /// the result of "func" applied to the corresponding cell in this grid.
+    pub fn map<U, F>(&self, mut func: F) -> Grid<U>
+    where
+        F: FnMut(&V) -> U,
+    {
+        self.map_with_coords(|_, _, value| func(value))
+    }
+
+    /// Same as "map", but "func" also receives the (column, row) coordinates of each cell.
+    /// Cells are visited in the same order as "iter_all_cells", row by row starting at the
+    /// bottom row.
+    pub fn map_with_coords<U, F>(&self, mut func: F) -> Grid<U>
+    where
+        F: FnMut(usize, usize, &V) -> U,
+    {
+        // Built row by row, then stored column by column.
+        let mut columns_data: Vec<Vec<U>> = (0..self.columns)
+            .map(|_| Vec::with_capacity(self.rows))
+            .collect();
+        for row in 0..self.rows {
+            for (col, column) in columns_data.iter_mut().enumerate() {
+                column.push(func(col, row, &self.data[self.flat_index(col, row)]));
             }
         }
+        self.with_data(columns_data.into_iter().flatten().collect())
+    }
 
-        let value = &mut self.data[col_left][row_bottom];
-        func(value);
+    /// Consumes the grid and returns a new one with the same dimensions and pivot, where
+    /// each cell contains the result of "func" applied to the moved cell contents.
+    pub fn into_map<U, F>(mut self, func: F) -> Grid<U>
+    where
+        F: FnMut(V) -> U,
+    {
+        let data = mem::take(&mut self.data).into_iter().map(func).collect();
+        self.with_data(data)
+    }
+
+    /// Returns a new Grid with the same dimensions and pivot as this one, where each cell
+    /// contains the result of "func" applied to the cells at the same (column, row) in both
+    /// grids. Fails if the grids have different column or row counts.
+    pub fn zip_with<W, U, F>(&self, other: &Grid<W>, mut func: F) -> Result<Grid<U>, GridError>
+    where
+        F: FnMut(&V, &W) -> U,
+    {
+        self.check_shape(other)?;
+        let data = self
+            .data
+            .iter()
+            .zip(&other.data)
+            .map(|(a, b)| func(a, b))
+            .collect();
+        Ok(self.with_data(data))
+    }
+
+    /// Modifies each cell using the cell at the same (column, row) in "other".
+    /// Fails if the grids have different column or row counts.
+    pub fn zip_modify<W, F>(&mut self, other: &Grid<W>, mut func: F) -> Result<(), GridError>
+    where
+        F: FnMut(&mut V, &W),
+    {
+        self.check_shape(other)?;
+        self.mark_all_dirty();
+        for (a, b) in self.data.iter_mut().zip(&other.data) {
+            func(a, b)
+        }
+        Ok(())
     }
 
-    /// Returns a reference to the underlying data.
-    pub fn raw_data(&self) -> &Vec<Vec<V>> {
+    /// Returns true if "other" has the same number of columns and rows, regardless of its
+    /// contents and physical size.
+    pub fn same_shape<W>(&self, other: &Grid<W>) -> bool {
+        self.columns == other.columns && self.rows == other.rows
+    }
+
+    // Returns an error if "other" doesn't have the same column and row counts.
+    fn check_shape<W>(&self, other: &Grid<W>) -> Result<(), GridError> {
+        if !self.same_shape(other) {
+            return Err(GridError::ShapeMismatch {
+                expected: (self.columns, self.rows),
+                found: (other.columns, other.rows),
+            });
+        }
+        Ok(())
+    }
+
+    // Returns a Grid with the same geometry as this one, containing "data".
+    fn with_data<U>(&self, data: Vec<U>) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cell_width: self.cell_width,
+            cell_height: self.cell_height,
+            columns: self.columns,
+            rows: self.rows,
+            offset_x: self.offset_x,
+            offset_y: self.offset_y,
+            x: self.x,
+            y: self.y,
+            data,
+            dirty: DirtyTracker::default(),
+            generation: self.generation,
+        }
+    }
+
+    /// Returns the underlying cells, stored column by column. Use "raw_index" to find
+    /// a cell in it.
+    pub fn raw_data(&self) -> &[V] {
         &self.data
     }
 
-    /// Returns a reference to the underlying data. Be careful and don't resize it!
-    pub fn raw_data_mut(&mut self) -> &mut Vec<Vec<V>> {
+    /// Returns the underlying cells mutably, stored column by column. Use "raw_index" to
+    /// find a cell in it. Being a slice, it can't be resized, so the grid's shape is always
+    /// kept.
+    pub fn raw_data_mut(&mut self) -> &mut [V] {
+        self.mark_all_dirty();
         &mut self.data
     }
+
+    /// Returns the position of the cell at (column, row) in "raw_data", if the indices
+    /// are in range.
+    #[inline]
+    pub fn raw_index(&self, col: usize, row: usize) -> Option<usize> {
+        if col < self.columns && row < self.rows {
+            Some(self.flat_index(col, row))
+        } else {
+            None
+        }
+    }
+
+    // Position of a cell in the storage. Doesn't check the indices.
+    #[inline]
+    pub(crate) fn flat_index(&self, col: usize, row: usize) -> usize {
+        (col * self.rows) + row
+    }
+
+    // Storage range of a column. Empty if the column is out of range.
+    pub(crate) fn column_range(&self, col: usize) -> core::ops::Range<usize> {
+        if col < self.columns {
+            (col * self.rows)..((col + 1) * self.rows)
+        } else {
+            0..0
+        }
+    }
+
+    /// Returns the cells of a column as a slice, from bottom to top. None if the column is
+    /// out of range.
+    pub fn column_slice(&self, col: usize) -> Option<&[V]> {
+        (col < self.columns).then(|| &self.data[self.column_range(col)])
+    }
+
+    /// Same as "column_slice", returning a mutable slice.
+    pub fn column_slice_mut(&mut self, col: usize) -> Option<&mut [V]> {
+        if col >= self.columns {
+            return None;
+        }
+        self.mark_dirty_edges(Some((col, 0, col, self.rows - 1)));
+        let range = self.column_range(col);
+        Some(&mut self.data[range])
+    }
+
+    /// Returns an iterator over the columns as slices, from left to right, each one from
+    /// bottom to top.
+    pub fn iter_column_slices(&self) -> core::slice::ChunksExact<'_, V> {
+        self.data.chunks_exact(self.rows)
+    }
+}
+
+// Wraps a value into the 0.0..size range, including negative values.
+fn wrap(value: Scalar, size: Scalar) -> Scalar {
+    value - (floor(value / size) * size)
+}
+
+impl<V> Debug for Grid<V>
+where
+    V: Debug,
+{
+    /// Prints the geometry and the number of cells, but not their contents, so large grids
+    /// stay readable. The alternate format ("{:#?}") adds the first few cells, column by
+    /// column; use "debug_cells" to print all of them.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let alternate = f.alternate();
+        let mut debug = f.debug_struct("Grid");
+        debug
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("columns", &self.columns)
+            .field("rows", &self.rows)
+            .field("pivot", &(self.offset_x, self.offset_y))
+            .field("position", &(self.x, self.y))
+            .field("cells", &self.data.len());
+        if alternate {
+            debug.field("sample", &Sample(&self.data));
+        }
+        debug.finish()
+    }
+}
+
+// First cells of a Grid, for its alternate Debug format.
+struct Sample<'a, V>(&'a [V]);
+
+impl<V> Debug for Sample<'_, V>
+where
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        const LEN: usize = 8;
+        let mut list = f.debug_list();
+        list.entries(self.0.iter().take(LEN));
+        if self.0.len() > LEN {
+            list.finish_non_exhaustive()
+        } else {
+            list.finish()
+        }
+    }
+}
+
+// Sizes are validated on creation, and the position and pivot setters reject NaN, so
+// equality is reflexive.
+impl<V> Eq for Grid<V> where V: Eq {}
+
+impl<V> Hash for Grid<V>
+where
+    V: Hash,
+{
+    /// Hashes the column and row counts, the size, pivot and position, then every cell
+    /// column by column from the bottom row up. Consistent with equality, so dirty tracking
+    /// is ignored.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.columns.hash(state);
+        self.rows.hash(state);
+        for value in [
+            self.width,
+            self.height,
+            self.offset_x,
+            self.offset_y,
+            self.x,
+            self.y,
+        ] {
+            hash_scalar(value, state);
+        }
+        self.data.hash(state);
+    }
+}
+
+impl<V> Grid<V>
+where
+    V: Hash,
+{
+    /// Hashes the column and row counts and the cells, in the same order as "Hash", but not
+    /// the physical size, pivot or position. Moving or resizing the grid keeps this hash.
+    pub fn content_hash<H: Hasher>(&self, state: &mut H) {
+        self.columns.hash(state);
+        self.rows.hash(state);
+        self.data.hash(state);
+    }
+}
+
+// Hashes the bits of a float, with -0.0 hashed as 0.0 since they compare equal.
+fn hash_scalar<H: Hasher>(value: Scalar, state: &mut H) {
+    (value + 0.0).to_bits().hash(state);
+}
+
+impl<V> TryFrom<Vec<Vec<V>>> for Grid<V> {
+    type Error = GridError;
+
+    /// Same as "from_nested", with each cell one unit wide and tall and the pivot at the
+    /// bottom-left corner.
+    fn try_from(columns: Vec<Vec<V>>) -> Result<Self, GridError> {
+        let width = columns.len() as Scalar;
+        let height = columns.first().map_or(0, Vec::len) as Scalar;
+        Self::from_nested(width, height, false, columns)
+    }
+}
+
+impl<V> Index<(usize, usize)> for Grid<V> {
+    type Output = V;
+
+    /// Returns the cell at (column, row). Panics if the indices are out of range.
+    fn index(&self, (col, row): (usize, usize)) -> &V {
+        let (columns, rows) = (self.columns, self.rows);
+        match self.get_cell_by_indices(col, row) {
+            Some(cell) => cell,
+            None => panic!(
+                err!("Cell ({}, {}) is out of range for a {}x{} grid"),
+                col, row, columns, rows
+            ),
+        }
+    }
+}
+
+impl<V> IndexMut<(usize, usize)> for Grid<V> {
+    /// Returns the cell at (column, row) mutably. Panics if the indices are out of range.
+    fn index_mut(&mut self, (col, row): (usize, usize)) -> &mut V {
+        let (columns, rows) = (self.columns, self.rows);
+        match self.get_cell_by_indices_mut(col, row) {
+            Some(cell) => cell,
+            None => panic!(
+                err!("Cell ({}, {}) is out of range for a {}x{} grid"),
+                col, row, columns, rows
+            ),
+        }
+    }
 }
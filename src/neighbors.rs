@@ -0,0 +1,209 @@
+use super::*;
+use core::iter::FusedIterator;
+
+/// Which cells count as neighbors of a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Cells sharing an edge: left, right, below and above.
+    Four,
+    /// Cells sharing an edge or a corner.
+    Eight,
+}
+
+// Neighbor offsets, row by row starting at the bottom row.
+const FOUR: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+const EIGHT: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+// Offsets of the neighbors visited from each cell by the pair methods, so every adjacent
+// pair is reached from exactly one of its two cells.
+const FORWARD: [(isize, isize); 4] = [(1, 0), (-1, 1), (0, 1), (1, 1)];
+
+impl Connectivity {
+    // Relative (column, row) offsets of the neighbors.
+    pub(super) fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Connectivity::Four => &FOUR,
+            Connectivity::Eight => &EIGHT,
+        }
+    }
+}
+
+/// Iterator that yields the (column, row) coordinates of the neighbors of a cell that are
+/// inside the grid, row by row starting at the bottom row.
+#[derive(Debug, Clone)]
+pub struct NeighborCoords {
+    pub(super) col: usize,
+    pub(super) row: usize,
+    pub(super) columns: usize,
+    pub(super) rows: usize,
+    pub(super) offsets: &'static [(isize, isize)],
+}
+
+impl Iterator for NeighborCoords {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(((dx, dy), rest)) = self.offsets.split_first() {
+            self.offsets = rest;
+            let col = self.col.checked_add_signed(*dx);
+            let row = self.row.checked_add_signed(*dy);
+            if let (Some(col), Some(row)) = (col, row) {
+                if col < self.columns && row < self.rows {
+                    return Some((col, row));
+                }
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.offsets.len()))
+    }
+}
+
+impl FusedIterator for NeighborCoords {}
+
+/// Iterator that yields ((column, row), mutable value) for the neighbors of a cell.
+#[derive(Debug)]
+pub struct IterNeighborsMut<'a, V> {
+    pub(super) coords: NeighborCoords,
+    pub(super) data: *mut V,
+    pub(super) _marker: PhantomData<&'a mut V>,
+}
+
+impl<'a, V> Iterator for IterNeighborsMut<'a, V> {
+    type Item = ((usize, usize), &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (col, row) = self.coords.next()?;
+        // SAFETY: The iterator holds the grid's exclusive borrow for 'a, the coordinates are
+        // inside the grid, and each neighbor is yielded only once.
+        let cell = unsafe { &mut *self.data.add((col * self.coords.rows) + row) };
+        Some(((col, row), cell))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.coords.size_hint()
+    }
+}
+
+impl<'a, V> FusedIterator for IterNeighborsMut<'a, V> {}
+
+impl<V> Grid<V> {
+    /// Returns an iterator with the (column, row) coordinates of the neighbors of a cell.
+    /// Cells on the edges simply have fewer neighbors. Empty if the cell is out of range.
+    pub fn neighbor_coords(
+        &self,
+        col: usize,
+        row: usize,
+        connectivity: Connectivity,
+    ) -> NeighborCoords {
+        let in_range = col < self.columns && row < self.rows;
+        NeighborCoords {
+            col,
+            row,
+            columns: self.columns,
+            rows: self.rows,
+            offsets: if in_range {
+                connectivity.offsets()
+            } else {
+                &[]
+            },
+        }
+    }
+
+    /// Returns an iterator that yields ((column, row), value) for the neighbors of a cell.
+    pub fn iter_neighbors(
+        &self,
+        col: usize,
+        row: usize,
+        connectivity: Connectivity,
+    ) -> impl Iterator<Item = ((usize, usize), &V)> + '_ {
+        self.neighbor_coords(col, row, connectivity)
+            .map(|(col, row)| ((col, row), &self.data[self.flat_index(col, row)]))
+    }
+
+    /// Returns an iterator that yields ((column, row), mutable value) for the neighbors of
+    /// a cell.
+    pub fn iter_neighbors_mut(
+        &mut self,
+        col: usize,
+        row: usize,
+        connectivity: Connectivity,
+    ) -> IterNeighborsMut<'_, V> {
+        if col < self.columns && row < self.rows {
+            self.mark_dirty_edges(Some((
+                col.saturating_sub(1),
+                row.saturating_sub(1),
+                (col + 1).min(self.columns - 1),
+                (row + 1).min(self.rows - 1),
+            )));
+        }
+        IterNeighborsMut {
+            coords: self.neighbor_coords(col, row, connectivity),
+            data: self.data.as_mut_ptr(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Calls "func" once for every unordered pair of cells overlapping a rectangle that are
+    /// either the same cell or 8-neighbors, i.e. the candidate pairs of a broadphase. Each
+    /// cell is first paired with itself, then with its neighbors right and above.
+    pub fn for_each_pair_in_rect<F>(
+        &self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+        func: F,
+    ) where
+        F: FnMut(&V, &V),
+    {
+        self.for_each_pair_in_edges(self.get_edges(left, bottom, right, top), func);
+    }
+
+    /// Same as "for_each_pair_in_rect", over the whole grid.
+    pub fn for_each_neighbor_pair<F>(&self, func: F)
+    where
+        F: FnMut(&V, &V),
+    {
+        self.for_each_pair_in_edges(Some((0, 0, self.columns - 1, self.rows - 1)), func);
+    }
+
+    fn for_each_pair_in_edges<F>(&self, edges: Option<(usize, usize, usize, usize)>, mut func: F)
+    where
+        F: FnMut(&V, &V),
+    {
+        let Some((col_left, row_bottom, col_right, row_top)) = edges else {
+            return;
+        };
+        for (col, row) in Self::coords_in_edges(edges) {
+            let cell = &self.data[self.flat_index(col, row)];
+            func(cell, cell);
+            for (dx, dy) in FORWARD {
+                let (Some(other_col), Some(other_row)) =
+                    (col.checked_add_signed(dx), row.checked_add_signed(dy))
+                else {
+                    continue;
+                };
+                if other_col < col_left
+                    || other_col > col_right
+                    || other_row < row_bottom
+                    || other_row > row_top
+                {
+                    continue;
+                }
+                func(cell, &self.data[self.flat_index(other_col, other_row)]);
+            }
+        }
+    }
+}
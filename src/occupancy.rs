@@ -0,0 +1,114 @@
+use super::*;
+use alloc::vec;
+use core::iter;
+
+/// Bitset with one bit per cell of a Grid, for quickly finding the occupied cells in a
+/// rectangle when most cells are empty. It is kept up to date with explicit calls to
+/// "set_occupied"; changes to the grid are not reflected automatically.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Occupancy {
+    // Same geometry as the source grid, used to convert rectangles to cells
+    frame: Grid<()>,
+    // Bits stored row by row, each row padded to whole words
+    words: Vec<u64>,
+    words_per_row: usize,
+}
+
+impl<V> Grid<V> {
+    /// Builds an Occupancy where a cell is occupied if "predicate" returns true for it.
+    pub fn build_occupancy<F>(&self, mut predicate: F) -> Occupancy
+    where
+        F: FnMut(&V) -> bool,
+    {
+        let words_per_row = self.columns.div_ceil(64);
+        let mut occupancy = Occupancy {
+            frame: self.map(|_| ()),
+            words: vec![0; words_per_row * self.rows],
+            words_per_row,
+        };
+        for (index, value) in self.data.iter().enumerate() {
+            if predicate(value) {
+                occupancy.set_occupied(index / self.rows, index % self.rows, true);
+            }
+        }
+        occupancy
+    }
+}
+
+impl Occupancy {
+    /// Marks a cell as occupied or empty. Does nothing if the indices are out of range.
+    pub fn set_occupied(&mut self, col: usize, row: usize, occupied: bool) {
+        if col >= self.frame.columns || row >= self.frame.rows {
+            return;
+        }
+        let word = &mut self.words[(row * self.words_per_row) + (col / 64)];
+        let bit = 1 << (col % 64);
+        if occupied {
+            *word |= bit;
+        } else {
+            *word &= !bit;
+        }
+    }
+
+    /// Returns true if the cell at the provided indices is occupied. False if it is empty or
+    /// the indices are out of range.
+    pub fn is_occupied(&self, col: usize, row: usize) -> bool {
+        if col >= self.frame.columns || row >= self.frame.rows {
+            return false;
+        }
+        let word = self.words[(row * self.words_per_row) + (col / 64)];
+        word & (1 << (col % 64)) != 0
+    }
+
+    /// Number of occupied cells.
+    pub fn count_occupied(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Returns the (column, row) coordinates of the occupied cells overlapping a rectangle,
+    /// row by row starting at the bottom row, like "iter_coords". Empty stretches of 64
+    /// cells are skipped at once.
+    pub fn iter_occupied_in_rect(
+        &self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let edges = self.frame.get_edges(left, bottom, right, top);
+        let (col_left, row_bottom, col_right, row_top) = edges.unwrap_or_default();
+        let rows = if edges.is_some() {
+            row_bottom..row_top + 1
+        } else {
+            0..0
+        };
+        rows.flat_map(move |row| {
+            (col_left / 64..=col_right / 64).flat_map(move |w| {
+                let mut word = self.words[(row * self.words_per_row) + w];
+                // Keep only the bits within the rectangle
+                if w == col_left / 64 {
+                    word &= u64::MAX << (col_left % 64);
+                }
+                if w == col_right / 64 {
+                    word &= u64::MAX >> (63 - (col_right % 64));
+                }
+                iter::from_fn(move || {
+                    if word == 0 {
+                        return None;
+                    }
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    Some(((w * 64) + bit, row))
+                })
+            })
+        })
+    }
+
+    /// A Grid without cells that has the same geometry as the source grid.
+    pub fn frame(&self) -> &Grid<()> {
+        &self.frame
+    }
+}
@@ -0,0 +1,35 @@
+// Shortcuts for the common "at most one object per cell" Grid<Option<T>>.
+use super::*;
+
+impl<T> Grid<Option<T>> {
+    /// Stores a value in the cell containing the provided coordinates and returns the value
+    /// it displaced, if any. If the coordinates are outside the grid the value is given back
+    /// as an error.
+    pub fn insert_at(&mut self, x: Scalar, y: Scalar, value: T) -> Result<Option<T>, T> {
+        match self.get_cell_mut(x, y) {
+            Some(cell) => Ok(cell.replace(value)),
+            None => Err(value),
+        }
+    }
+
+    /// Empties the cell containing the provided coordinates and returns its value, if any.
+    pub fn remove_at(&mut self, x: Scalar, y: Scalar) -> Option<T> {
+        self.get_cell_mut(x, y)?.take()
+    }
+
+    /// Returns true if the cell containing the provided coordinates holds a value. False if
+    /// it is empty or the coordinates are outside the grid.
+    pub fn is_occupied(&self, x: Scalar, y: Scalar) -> bool {
+        matches!(self.get_cell(x, y), Some(Some(_)))
+    }
+
+    /// Returns a mutable reference to the value in the cell containing the provided
+    /// coordinates, storing the result of "func" first if the cell is empty. None if the
+    /// coordinates are outside the grid.
+    pub fn get_or_insert_with_at<F>(&mut self, x: Scalar, y: Scalar, func: F) -> Option<&mut T>
+    where
+        F: FnOnce() -> T,
+    {
+        Some(self.get_cell_mut(x, y)?.get_or_insert_with(func))
+    }
+}
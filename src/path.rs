@@ -0,0 +1,150 @@
+use super::*;
+use alloc::collections::BinaryHeap;
+use core::cmp::Reverse;
+
+/// Reusable buffers for "find_path_with". Keeping one around between searches avoids
+/// allocating on every call.
+#[derive(Debug, Clone, Default)]
+pub struct PathContext {
+    // Search in which each cell was last reached. Cells from older searches are unvisited.
+    stamps: Vec<u32>,
+    generation: u32,
+    // Cost from the start, and previous cell (as a flat index), of each reached cell
+    costs: Vec<u32>,
+    parents: Vec<usize>,
+    // Cells to explore as (estimated total cost, heuristic, flat index)
+    open: BinaryHeap<Reverse<(u32, u32, usize)>>,
+}
+
+impl PathContext {
+    /// Returns an empty context. Its buffers grow on the first search.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Prepares the buffers for a search over "len" cells.
+    fn reset(&mut self, len: usize) {
+        if self.stamps.len() != len {
+            self.stamps.clear();
+            self.stamps.resize(len, 0);
+            self.costs.resize(len, 0);
+            self.parents.resize(len, 0);
+            self.generation = 0;
+        }
+        self.generation = self.generation.wrapping_add(1);
+        if self.generation == 0 {
+            self.stamps.fill(0);
+            self.generation = 1;
+        }
+        self.open.clear();
+    }
+
+    // Returns the cost to reach a cell in the current search, if it was reached.
+    fn cost(&self, index: usize) -> Option<u32> {
+        (self.stamps[index] == self.generation).then(|| self.costs[index])
+    }
+
+    fn visit(&mut self, index: usize, cost: u32, parent: usize) {
+        self.stamps[index] = self.generation;
+        self.costs[index] = cost;
+        self.parents[index] = parent;
+    }
+}
+
+impl<V> Grid<V> {
+    /// Returns the cheapest path from "start" to "goal" as a list of (column, row) pairs,
+    /// including both ends, using A*. The "cost" function returns the cost of entering a
+    /// cell, or None if it can't be entered; the start cell's cost is ignored.
+    /// With Connectivity::Eight diagonal steps cost the same as straight ones.
+    /// Returns None if the goal can't be reached or either end is out of range.
+    pub fn find_path<F>(
+        &self,
+        start: (usize, usize),
+        goal: (usize, usize),
+        connectivity: Connectivity,
+        cost: F,
+    ) -> Option<Vec<(usize, usize)>>
+    where
+        F: Fn(&V) -> Option<u32>,
+    {
+        self.find_path_with(&mut PathContext::new(), start, goal, connectivity, cost)
+    }
+
+    /// Same as "find_path", reusing the buffers in "context" instead of allocating new
+    /// ones. Only the returned path is allocated.
+    /// The search assumes entering a cell costs at least 1; with zero costs the path is still
+    /// valid but may not be the cheapest.
+    pub fn find_path_with<F>(
+        &self,
+        context: &mut PathContext,
+        start: (usize, usize),
+        goal: (usize, usize),
+        connectivity: Connectivity,
+        cost: F,
+    ) -> Option<Vec<(usize, usize)>>
+    where
+        F: Fn(&V) -> Option<u32>,
+    {
+        let start_index = self.raw_index(start.0, start.1)?;
+        let goal_index = self.raw_index(goal.0, goal.1)?;
+        cost(&self.data[goal_index])?;
+        // Manhattan distance, or Chebyshev when diagonal steps are allowed
+        let heuristic = |col: usize, row: usize| {
+            let dx = col.abs_diff(goal.0);
+            let dy = row.abs_diff(goal.1);
+            let distance = match connectivity {
+                Connectivity::Four => dx + dy,
+                Connectivity::Eight => dx.max(dy),
+            };
+            distance.min(u32::MAX as usize) as u32
+        };
+
+        context.reset(self.data.len());
+        context.visit(start_index, 0, start_index);
+        let h = heuristic(start.0, start.1);
+        context.open.push(Reverse((h, h, start_index)));
+
+        while let Some(Reverse((estimate, h, index))) = context.open.pop() {
+            let current = context.costs[index];
+            // Entries superseded by a cheaper route
+            if estimate > current.saturating_add(h) {
+                continue;
+            }
+            if index == goal_index {
+                return Some(self.trace_path(context, start_index, goal_index));
+            }
+            let (col, row) = (index / self.rows, index % self.rows);
+            for (next_col, next_row) in self.neighbor_coords(col, row, connectivity) {
+                let next = self.flat_index(next_col, next_row);
+                let Some(step) = cost(&self.data[next]) else {
+                    continue;
+                };
+                let next_cost = current.saturating_add(step);
+                if context.cost(next).is_some_and(|known| known <= next_cost) {
+                    continue;
+                }
+                context.visit(next, next_cost, index);
+                let h = heuristic(next_col, next_row);
+                context
+                    .open
+                    .push(Reverse((next_cost.saturating_add(h), h, next)));
+            }
+        }
+        None
+    }
+
+    // Follows the parents from the goal back to the start.
+    fn trace_path(&self, context: &PathContext, start: usize, goal: usize) -> Vec<(usize, usize)> {
+        let mut path = Vec::new();
+        let mut index = goal;
+        loop {
+            path.push((index / self.rows, index % self.rows));
+            if index == start {
+                break;
+            }
+            index = context.parents[index];
+        }
+        path.reverse();
+        path
+    }
+}
@@ -0,0 +1,29 @@
+use crate::Scalar;
+
+/// Named pivot positions. The pivot is the point of the Grid placed at its position, and
+/// the origin of its physical coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Pivot {
+    BottomLeft,
+    Center,
+    TopLeft,
+    TopRight,
+    BottomRight,
+    /// Normalized pivot, where (0.0, 0.0) is the bottom-left corner and (1.0, 1.0) the
+    /// top-right one.
+    Custom(Scalar, Scalar),
+}
+
+impl Pivot {
+    /// Returns the normalized (x, y) pivot.
+    pub fn normalized(self) -> (Scalar, Scalar) {
+        match self {
+            Pivot::BottomLeft => (0.0, 0.0),
+            Pivot::Center => (0.5, 0.5),
+            Pivot::TopLeft => (0.0, 1.0),
+            Pivot::TopRight => (1.0, 1.0),
+            Pivot::BottomRight => (1.0, 0.0),
+            Pivot::Custom(x, y) => (x, y),
+        }
+    }
+}
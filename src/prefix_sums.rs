@@ -0,0 +1,100 @@
+use super::*;
+use alloc::vec;
+use core::ops::{Add, Sub};
+
+/// Summed-area table of a Grid, answering region sums in constant time. It is a snapshot:
+/// changes to the grid after "build_prefix_sums" are not reflected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrefixSums<S> {
+    // Same geometry as the source grid, used to convert rectangles to cells
+    frame: Grid<()>,
+    // Sum of all cells below and to the left of each corner, column by column, with an
+    // extra row and column of zeros
+    table: Vec<S>,
+}
+
+impl<V> Grid<V> {
+    /// Builds a summed-area table from the cell values, converted to the accumulator type
+    /// "S". Using a wider accumulator, like f64 for a Grid<f32>, reduces rounding errors.
+    pub fn build_prefix_sums<S>(&self) -> PrefixSums<S>
+    where
+        V: Copy + Into<S>,
+        S: Copy + Default + Add<Output = S> + Sub<Output = S>,
+    {
+        let stride = self.rows + 1;
+        let mut table = vec![S::default(); (self.columns + 1) * stride];
+        for col in 1..=self.columns {
+            for row in 1..=self.rows {
+                let value: S = self.data[self.flat_index(col - 1, row - 1)].into();
+                let left = table[((col - 1) * stride) + row];
+                let below = table[(col * stride) + row - 1];
+                let corner = table[((col - 1) * stride) + row - 1];
+                // Subtracting last keeps unsigned accumulators from underflowing
+                table[(col * stride) + row] = (value + left + below) - corner;
+            }
+        }
+        PrefixSums {
+            frame: self.map(|_| ()),
+            table,
+        }
+    }
+}
+
+impl<S> PrefixSums<S>
+where
+    S: Copy + Default + Add<Output = S> + Sub<Output = S>,
+{
+    /// Returns the sum of the cells overlapping a rectangle, using the same rules as
+    /// "iter_cells_in_rect". Zero (the default value) if the rectangle is outside the grid.
+    pub fn sum_in_rect(&self, left: Scalar, bottom: Scalar, right: Scalar, top: Scalar) -> S {
+        self.sum_in_edges(self.frame.get_edges(left, bottom, right, top))
+    }
+
+    /// Returns the mean of the cells overlapping a rectangle, or None if the rectangle is
+    /// outside the grid.
+    pub fn mean_in_rect(
+        &self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+    ) -> Option<f64>
+    where
+        S: Into<f64>,
+    {
+        let (col_min, row_min, col_max, row_max) =
+            self.frame.get_edges(left, bottom, right, top)?;
+        let count = (col_max - col_min + 1) * (row_max - row_min + 1);
+        let sum = self.sum_in_edges(Some((col_min, row_min, col_max, row_max)));
+        Some(sum.into() / count as f64)
+    }
+
+    /// Returns the sum of the cells from (col_min, row_min) to (col_max, row_max), inclusive.
+    /// The maximum indices are clamped to the grid; zero if the range is empty or the minimum
+    /// indices are out of range.
+    pub fn sum_in_cells(
+        &self,
+        col_min: usize,
+        row_min: usize,
+        col_max: usize,
+        row_max: usize,
+    ) -> S {
+        let col_max = col_max.min(self.frame.columns - 1);
+        let row_max = row_max.min(self.frame.rows - 1);
+        if col_min > col_max || row_min > row_max {
+            return S::default();
+        }
+        self.sum_in_edges(Some((col_min, row_min, col_max, row_max)))
+    }
+
+    // Sum of the cells within the (left, bottom, right, top) indices. None sums nothing.
+    fn sum_in_edges(&self, edges: Option<(usize, usize, usize, usize)>) -> S {
+        let Some((col_min, row_min, col_max, row_max)) = edges else {
+            return S::default();
+        };
+        let stride = self.frame.rows + 1;
+        let corner = |col: usize, row: usize| self.table[(col * stride) + row];
+        let outer = corner(col_max + 1, row_max + 1) + corner(col_min, row_min);
+        outer - corner(col_min, row_max + 1) - corner(col_max + 1, row_min)
+    }
+}
@@ -0,0 +1,121 @@
+use super::*;
+
+impl<V> Grid<V> {
+    /// Builds progressively halved copies of this Grid, down to a single cell. Each cell of a
+    /// level is "reduce" applied to the 2x2 block of cells below it, as (bottom/left,
+    /// bottom/right, top/left, top/right). With an odd number of columns or rows the last
+    /// block repeats the edge cells. Levels keep the physical size, pivot and position.
+    /// The first level has half the columns and rows of this Grid; a 1x1 Grid has no levels.
+    pub fn build_pyramid<F>(&self, reduce: F) -> Vec<Grid<V>>
+    where
+        F: Fn(&V, &V, &V, &V) -> V,
+    {
+        let mut levels: Vec<Grid<V>> = Vec::new();
+        loop {
+            let source = levels.last().unwrap_or(self);
+            if source.columns == 1 && source.rows == 1 {
+                return levels;
+            }
+            let level = source.halved(&reduce);
+            levels.push(level);
+        }
+    }
+
+    // Returns the next pyramid level.
+    fn halved<F>(&self, reduce: &F) -> Grid<V>
+    where
+        F: Fn(&V, &V, &V, &V) -> V,
+    {
+        let cell = |col: usize, row: usize| &self.data[self.flat_index(col, row)];
+        let mut grid = Grid::from_fn(
+            self.width,
+            self.height,
+            self.columns.div_ceil(2),
+            self.rows.div_ceil(2),
+            self.offset_x,
+            self.offset_y,
+            |col, row| {
+                let (left, bottom) = (col * 2, row * 2);
+                let right = (left + 1).min(self.columns - 1);
+                let top = (bottom + 1).min(self.rows - 1);
+                reduce(
+                    cell(left, bottom),
+                    cell(right, bottom),
+                    cell(left, top),
+                    cell(right, top),
+                )
+            },
+        );
+        grid.x = self.x;
+        grid.y = self.y;
+        grid
+    }
+
+    /// Returns true if any cell of this Grid overlapping the rectangle matches "pred", using
+    /// the "levels" returned by "build_pyramid" to skip whole blocks. For the answer to be
+    /// exact, "pred" must match a reduced cell whenever it matches any of its four sources,
+    /// e.g. "reduce" is a logical or, or a max with a "greater than" predicate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the levels don't have the dimensions of a pyramid built from this Grid.
+    pub fn query_pyramid<P>(
+        &self,
+        levels: &[Grid<V>],
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+        pred: P,
+    ) -> bool
+    where
+        P: Fn(&V) -> bool,
+    {
+        // Levels from another Grid would be indexed out of range, or give wrong answers
+        let mut expected = (self.columns, self.rows);
+        for (n, level) in levels.iter().enumerate() {
+            expected = (expected.0.div_ceil(2), expected.1.div_ceil(2));
+            assert!(
+                (level.columns, level.rows) == expected,
+                "Grid: Error, pyramid level {} is {}x{}, expected {}x{} for a {}x{} grid",
+                n,
+                level.columns,
+                level.rows,
+                expected.0,
+                expected.1,
+                self.columns,
+                self.rows
+            );
+        }
+        let Some(edges) = self.get_edges(left, bottom, right, top) else {
+            return false;
+        };
+        // Level 0 is this Grid; the cells of level n cover 2^n x 2^n cells of it
+        let level = |n: usize| if n == 0 { self } else { &levels[n - 1] };
+        let edges_at = |n: usize| (edges.0 >> n, edges.1 >> n, edges.2 >> n, edges.3 >> n);
+        let coarsest = levels.len();
+        let mut stack = Vec::new();
+        let (col_min, row_min, col_max, row_max) = edges_at(coarsest);
+        for col in col_min..=col_max {
+            for row in row_min..=row_max {
+                stack.push((coarsest, col, row));
+            }
+        }
+        while let Some((n, col, row)) = stack.pop() {
+            let grid = level(n);
+            if !pred(&grid.data[grid.flat_index(col, row)]) {
+                continue;
+            }
+            if n == 0 {
+                return true;
+            }
+            let (col_min, row_min, col_max, row_max) = edges_at(n - 1);
+            for child_col in (col * 2).max(col_min)..=((col * 2) + 1).min(col_max) {
+                for child_row in (row * 2).max(row_min)..=((row * 2) + 1).min(row_max) {
+                    stack.push((n - 1, child_col, child_row));
+                }
+            }
+        }
+        false
+    }
+}
@@ -0,0 +1,197 @@
+use super::*;
+use scalar::sqrt;
+
+impl<V> Grid<V> {
+    /// Returns true if any cell overlapping the rectangle matches "pred". Stops at the first
+    /// match.
+    pub fn any_in_rect<P>(
+        &self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+        pred: P,
+    ) -> bool
+    where
+        P: FnMut(&V) -> bool,
+    {
+        self.iter_cells_in_rect(left, bottom, right, top).any(pred)
+    }
+
+    /// Returns true if every cell overlapping the rectangle matches "pred", including when no
+    /// cell overlaps it. Stops at the first cell that doesn't match.
+    pub fn all_in_rect<P>(
+        &self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+        pred: P,
+    ) -> bool
+    where
+        P: FnMut(&V) -> bool,
+    {
+        self.iter_cells_in_rect(left, bottom, right, top).all(pred)
+    }
+
+    /// Returns the number of cells overlapping the rectangle that match "pred".
+    pub fn count_in_rect<P>(
+        &self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+        mut pred: P,
+    ) -> usize
+    where
+        P: FnMut(&V) -> bool,
+    {
+        self.iter_cells_in_rect(left, bottom, right, top)
+            .filter(|cell| pred(cell))
+            .count()
+    }
+
+    /// Returns the first cell overlapping the rectangle that matches "pred", with its
+    /// (column, row) coordinates. Cells are checked in the same order as
+    /// "iter_cells_in_rect".
+    pub fn find_in_rect<P>(
+        &self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+        mut pred: P,
+    ) -> Option<((usize, usize), &V)>
+    where
+        P: FnMut(&V) -> bool,
+    {
+        self.iter_cells_in_rect(left, bottom, right, top)
+            .enumerate_coords()
+            .find(|(cell, _, _)| pred(cell))
+            .map(|(cell, col, row)| ((col, row), cell))
+    }
+    /// Folds every cell overlapping the rectangle into an accumulator, in the same order as
+    /// "iter_cells_in_rect". The function also receives the (column, row) coordinates of each
+    /// cell. Returns "init" if no cell overlaps the rectangle.
+    pub fn fold_in_rect<A, F>(
+        &self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+        init: A,
+        mut func: F,
+    ) -> A
+    where
+        F: FnMut(A, (usize, usize), &V) -> A,
+    {
+        self.iter_cells_in_rect(left, bottom, right, top)
+            .enumerate_coords()
+            .fold(init, |acc, (cell, col, row)| func(acc, (col, row), cell))
+    }
+
+    /// Returns the cell overlapping the rectangle with the largest key, with its (column, row)
+    /// coordinates. If several cells share the largest key, the last one is returned.
+    pub fn max_by_key_in_rect<K, F>(
+        &self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+        mut key: F,
+    ) -> Option<((usize, usize), &V)>
+    where
+        K: Ord,
+        F: FnMut(&V) -> K,
+    {
+        self.iter_cells_in_rect(left, bottom, right, top)
+            .enumerate_coords()
+            .max_by_key(|(cell, _, _)| key(cell))
+            .map(|(cell, col, row)| ((col, row), cell))
+    }
+
+    /// Returns the cell overlapping the rectangle with the smallest key, with its (column, row)
+    /// coordinates. If several cells share the smallest key, the first one is returned.
+    pub fn min_by_key_in_rect<K, F>(
+        &self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+        mut key: F,
+    ) -> Option<((usize, usize), &V)>
+    where
+        K: Ord,
+        F: FnMut(&V) -> K,
+    {
+        self.iter_cells_in_rect(left, bottom, right, top)
+            .enumerate_coords()
+            .min_by_key(|(cell, _, _)| key(cell))
+            .map(|(cell, col, row)| ((col, row), cell))
+    }
+
+    /// Returns the matching cell whose center is closest to the point, with its (column, row)
+    /// coordinates. Cells are searched in rings around the cell containing the point (or the
+    /// nearest one, if it is outside the grid), stopping once no closer cell can be found or
+    /// beyond "max_radius". Ties go to the cell in the smallest ring, then the lowest row and
+    /// column.
+    pub fn find_nearest<P>(
+        &self,
+        x: Scalar,
+        y: Scalar,
+        max_radius: Scalar,
+        mut pred: P,
+    ) -> Option<((usize, usize), &V)>
+    where
+        P: FnMut(&V) -> bool,
+    {
+        if max_radius.is_nan() || max_radius < 0.0 || x.is_nan() || y.is_nan() {
+            return None;
+        }
+        let (start_col, start_row) = self.get_cell_coords_clamped(x, y);
+        // Cell centers in ring "n" are at least (n - 0.5) cells away from the point.
+        let cell_size = self.cell_width.min(self.cell_height);
+        let ring_distance = |ring: usize| (ring as Scalar - 0.5).max(0.0) * cell_size;
+        let max_ring = floor((max_radius / cell_size) + 0.5) as usize;
+
+        let mut nearest: Option<(Scalar, (usize, usize))> = None;
+        for (col, row) in self.iter_spiral_coords(start_col, start_row, Some(max_ring)) {
+            let ring = col.abs_diff(start_col).max(row.abs_diff(start_row));
+            if let Some((distance, _)) = nearest {
+                if ring_distance(ring) > distance {
+                    break;
+                }
+            }
+            let (center_x, center_y) = self.cell_center(col, row)?;
+            let (dx, dy) = (center_x - x, center_y - y);
+            let distance = sqrt((dx * dx) + (dy * dy));
+            if distance > max_radius || nearest.is_some_and(|(best, _)| distance >= best) {
+                continue;
+            }
+            if pred(&self.data[self.flat_index(col, row)]) {
+                nearest = Some((distance, (col, row)));
+            }
+        }
+        let (_, (col, row)) = nearest?;
+        Some(((col, row), &self.data[self.flat_index(col, row)]))
+    }
+}
+
+impl<V> Grid<V>
+where
+    V: PartialEq,
+{
+    /// Returns the coordinates of the first cell equal to "value", scanning row by row
+    /// starting at the bottom row, same as "iter_all_cells".
+    pub fn position_of(&self, value: &V) -> Option<(usize, usize)> {
+        self.positions_of(value).next()
+    }
+
+    /// Returns an iterator with the coordinates of every cell equal to "value", in the same
+    /// order as "position_of".
+    pub fn positions_of<'a>(&'a self, value: &'a V) -> impl Iterator<Item = (usize, usize)> + 'a {
+        self.iter_all_with_coords()
+            .filter(move |(cell, _, _)| *cell == value)
+            .map(|(_, col, row)| (col, row))
+    }
+}
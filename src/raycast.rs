@@ -0,0 +1,86 @@
+use super::*;
+use core::iter::FusedIterator;
+use scalar::sqrt;
+
+/// A cell crossed by a ray. "t_enter" and "t_exit" are the distances along the ray at which
+/// it enters and leaves the cell.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit<'a, V> {
+    pub col: usize,
+    pub row: usize,
+    pub t_enter: Scalar,
+    pub t_exit: Scalar,
+    pub value: &'a V,
+}
+
+/// Iterator that yields a RayHit for each cell crossed by a ray, in order from its origin.
+#[derive(Debug, Clone)]
+pub struct IterRaycast<'a, V> {
+    pub(super) grid: &'a Grid<V>,
+    pub(super) coords: IterSegmentCoords,
+}
+
+impl<'a, V> Iterator for IterRaycast<'a, V> {
+    type Item = RayHit<'a, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (col, row, t_enter, t_exit) = self.coords.next_hit()?;
+        Some(RayHit {
+            col,
+            row,
+            t_enter,
+            t_exit,
+            value: &self.grid.data[self.grid.flat_index(col, row)],
+        })
+    }
+}
+
+impl<'a, V> FusedIterator for IterRaycast<'a, V> {}
+
+impl<V> Grid<V> {
+    /// Casts a ray and returns an iterator with every cell it crosses, up to "max_distance".
+    /// The direction doesn't need to be normalized. Rays starting outside the grid begin at
+    /// the point where they enter it, while distances are still measured from the origin.
+    pub fn raycast(
+        &self,
+        origin_x: Scalar,
+        origin_y: Scalar,
+        dir_x: Scalar,
+        dir_y: Scalar,
+        max_distance: Scalar,
+    ) -> IterRaycast<'_, V> {
+        let length = sqrt((dir_x * dir_x) + (dir_y * dir_y));
+        // A NaN or infinite direction stays non-finite, so the ray finds nothing
+        let (dir_x, dir_y) = if length == 0.0 {
+            (0.0, 0.0)
+        } else {
+            (dir_x / length, dir_y / length)
+        };
+        IterRaycast {
+            grid: self,
+            coords: IterSegmentCoords::new(
+                self,
+                self.local_x(origin_x),
+                self.local_y(origin_y),
+                dir_x,
+                dir_y,
+                0.0,
+                max_distance,
+                false,
+            ),
+        }
+    }
+
+    /// Same as "raycast", but takes the origin and direction as points. See
+    /// "get_cell_coords_at".
+    pub fn raycast_at(
+        &self,
+        origin: impl Into<[Scalar; 2]>,
+        dir: impl Into<[Scalar; 2]>,
+        max_distance: Scalar,
+    ) -> IterRaycast<'_, V> {
+        let [origin_x, origin_y] = origin.into();
+        let [dir_x, dir_y] = dir.into();
+        self.raycast(origin_x, origin_y, dir_x, dir_y, max_distance)
+    }
+}
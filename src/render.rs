@@ -0,0 +1,65 @@
+use super::*;
+use alloc::string::String;
+use core::fmt::{self, Debug, Formatter};
+
+// Text rendering, useful for debugging.
+impl<V> Grid<V> {
+    /// Returns a text representation of the grid with one character per cell, as returned
+    /// by "func". Rows are printed top to bottom so the output is oriented like the world
+    /// (Y up). If "ruler" is true, the last digit of each row and column index is printed
+    /// along the left and bottom edges.
+    pub fn render_ascii<F>(&self, ruler: bool, func: F) -> String
+    where
+        F: Fn(&V) -> char,
+    {
+        let mut text = String::new();
+        for row in (0..self.rows).rev() {
+            if ruler {
+                text.push(digit(row));
+                text.push(' ');
+            }
+            for cell in self.iter_row(row) {
+                text.push(func(cell));
+            }
+            text.push('\n');
+        }
+        if ruler {
+            text.push_str("  ");
+            for col in 0..self.columns {
+                text.push(digit(col));
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Returns an adapter whose Debug output lists every cell, one row per line from the
+    /// top row down, since Grid's own Debug output omits them.
+    pub fn debug_cells(&self) -> DebugCells<'_, V> {
+        DebugCells { grid: self }
+    }
+}
+
+/// Debug adapter that prints all the cells of a Grid, created with "debug_cells".
+pub struct DebugCells<'a, V> {
+    grid: &'a Grid<V>,
+}
+
+impl<V> Debug for DebugCells<'_, V>
+where
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let grid = self.grid;
+        for row in (0..grid.rows).rev() {
+            f.debug_list().entries(grid.iter_row(row)).finish()?;
+            f.write_str("\n")?;
+        }
+        Ok(())
+    }
+}
+
+// Last decimal digit of an index.
+fn digit(index: usize) -> char {
+    char::from(b'0' + (index % 10) as u8)
+}
@@ -0,0 +1,520 @@
+use super::*;
+
+// Methods that need to clone cell contents.
+impl<V> Grid<V>
+where
+    V: Clone,
+{
+    /// Returns a new Grid with the cells from (col_min, row_min) to (col_max, row_max),
+    /// inclusive. The cell size is kept, and the pivot is adjusted so that the new Grid
+    /// occupies the same physical area as the region did in this one.
+    pub fn crop(
+        &self,
+        col_min: usize,
+        row_min: usize,
+        col_max: usize,
+        row_max: usize,
+    ) -> Result<Grid<V>, GridError> {
+        self.check_region(col_min, row_min, col_max, row_max)?;
+        let mut data = Vec::with_capacity((col_max - col_min + 1) * (row_max - row_min + 1));
+        for col in col_min..=col_max {
+            data.extend_from_slice(&self.data[self.column_range(col)][row_min..=row_max]);
+        }
+        Ok(self.cropped_with(col_min, row_min, col_max, row_max, data))
+    }
+
+    /// Copies all cells from "source" into this Grid, with the source's bottom/left cell
+    /// landing at (dst_col, dst_row). Cells are copied by indices, so cell sizes don't need
+    /// to match, and anything falling outside this Grid is clipped.
+    pub fn blit(&mut self, source: &Grid<V>, dst_col: usize, dst_row: usize) {
+        let (col_max, row_max) = (source.columns - 1, source.rows - 1);
+        self.blit_unchecked(source, 0, 0, col_max, row_max, dst_col, dst_row);
+    }
+
+    /// Same as "blit", but only copies the source cells from (col_min, row_min) to
+    /// (col_max, row_max), inclusive. Fails if that region isn't inside the source.
+    #[allow(clippy::too_many_arguments)]
+    pub fn blit_region(
+        &mut self,
+        source: &Grid<V>,
+        col_min: usize,
+        row_min: usize,
+        col_max: usize,
+        row_max: usize,
+        dst_col: usize,
+        dst_row: usize,
+    ) -> Result<(), GridError> {
+        source.check_region(col_min, row_min, col_max, row_max)?;
+        self.blit_unchecked(source, col_min, row_min, col_max, row_max, dst_col, dst_row);
+        Ok(())
+    }
+
+    // Blits a source region that is already known to be valid.
+    #[allow(clippy::too_many_arguments)]
+    fn blit_unchecked(
+        &mut self,
+        source: &Grid<V>,
+        col_min: usize,
+        row_min: usize,
+        col_max: usize,
+        row_max: usize,
+        dst_col: usize,
+        dst_row: usize,
+    ) {
+        if dst_col >= self.columns || dst_row >= self.rows {
+            return;
+        }
+        let columns = (col_max - col_min + 1).min(self.columns - dst_col);
+        let rows = (row_max - row_min + 1).min(self.rows - dst_row);
+        self.mark_dirty_edges(Some((
+            dst_col,
+            dst_row,
+            dst_col + columns - 1,
+            dst_row + rows - 1,
+        )));
+        for offset in 0..columns {
+            let src = &source.data[source.column_range(col_min + offset)][row_min..row_min + rows];
+            let start = self.flat_index(dst_col + offset, dst_row);
+            self.data[start..start + rows].clone_from_slice(src);
+        }
+    }
+}
+
+impl<V> Grid<V> {
+    /// Changes the number of columns and rows while keeping the physical size, so the cell
+    /// size is recomputed. Cells that still fit keep their contents, new cells are created by
+    /// "fill" and cells that no longer fit are dropped.
+    pub fn set_dimensions<F>(
+        &mut self,
+        columns: usize,
+        rows: usize,
+        mut fill: F,
+    ) -> Result<(), GridError>
+    where
+        F: FnMut() -> V,
+    {
+        Self::validate_args(self.width, self.height, columns, rows)?;
+        let old_columns = self.columns;
+        let old_rows = self.rows;
+        let mut old = mem::take(&mut self.data).into_iter();
+        let mut data = Vec::with_capacity(columns * rows);
+        for col in 0..columns {
+            if col < old_columns {
+                data.extend(old.by_ref().take(rows.min(old_rows)));
+                // Drops the cells above the new top row
+                old.by_ref()
+                    .take(old_rows.saturating_sub(rows))
+                    .for_each(drop);
+            }
+            while data.len() < (col + 1) * rows {
+                data.push(fill());
+            }
+        }
+        self.data = data;
+        self.columns = columns;
+        self.rows = rows;
+        self.mark_resized();
+        self.recompute_cell_size();
+        Ok(())
+    }
+
+    /// Inserts a row at index "at" (equal to "rows()" appends it at the top), creating its
+    /// cells with "fill", which receives their (column, row) coordinates. Rows above it move
+    /// up by one. If "grow" is true the physical height grows by one cell, otherwise it is
+    /// kept and the cell height shrinks. Fails if "at" is larger than "rows()".
+    pub fn insert_row<F>(&mut self, at: usize, grow: bool, mut fill: F) -> Result<(), GridError>
+    where
+        F: FnMut(usize, usize) -> V,
+    {
+        if at > self.rows {
+            return Err(GridError::RowOutOfRange { row: at });
+        }
+        let rows = self.rows + 1;
+        let mut old = mem::take(&mut self.data).into_iter();
+        let mut data = Vec::with_capacity(self.columns * rows);
+        for col in 0..self.columns {
+            data.extend(old.by_ref().take(at));
+            data.push(fill(col, at));
+            data.extend(old.by_ref().take(self.rows - at));
+        }
+        self.data = data;
+        self.rows = rows;
+        self.mark_resized();
+        if grow {
+            self.set_height(self.height + self.cell_height);
+        }
+        self.recompute_cell_size();
+        Ok(())
+    }
+
+    /// Removes the row at index "at", returning its cells from left to right. Rows above it
+    /// move down by one. If "shrink" is true the physical height shrinks by one cell, otherwise
+    /// it is kept and the cell height grows. Fails if "at" is out of range or if it is the
+    /// last remaining row.
+    pub fn remove_row(&mut self, at: usize, shrink: bool) -> Result<Vec<V>, GridError> {
+        if at >= self.rows {
+            return Err(GridError::RowOutOfRange { row: at });
+        }
+        if self.rows == 1 {
+            return Err(GridError::ZeroRows);
+        }
+        let rows = self.rows - 1;
+        let mut old = mem::take(&mut self.data).into_iter();
+        let mut data = Vec::with_capacity(self.columns * rows);
+        let mut removed = Vec::with_capacity(self.columns);
+        for _ in 0..self.columns {
+            data.extend(old.by_ref().take(at));
+            removed.extend(old.next());
+            data.extend(old.by_ref().take(rows - at));
+        }
+        self.data = data;
+        self.rows = rows;
+        self.mark_resized();
+        if shrink {
+            self.set_height(self.height - self.cell_height);
+        }
+        self.recompute_cell_size();
+        Ok(removed)
+    }
+
+    /// Inserts a column at index "at" (equal to "columns()" appends it at the right), creating
+    /// its cells with "fill", which receives their (column, row) coordinates. Columns to the
+    /// right move right by one. If "grow" is true the physical width grows by one cell,
+    /// otherwise it is kept and the cell width shrinks. Fails if "at" is larger than
+    /// "columns()".
+    pub fn insert_column<F>(&mut self, at: usize, grow: bool, mut fill: F) -> Result<(), GridError>
+    where
+        F: FnMut(usize, usize) -> V,
+    {
+        if at > self.columns {
+            return Err(GridError::ColumnOutOfRange { col: at });
+        }
+        let start = at * self.rows;
+        self.data
+            .splice(start..start, (0..self.rows).map(|row| fill(at, row)));
+        self.columns += 1;
+        self.mark_resized();
+        if grow {
+            self.set_width(self.width + self.cell_width);
+        }
+        self.recompute_cell_size();
+        Ok(())
+    }
+
+    /// Removes the column at index "at", returning its cells from bottom to top. Columns to the
+    /// right move left by one. If "shrink" is true the physical width shrinks by one cell,
+    /// otherwise it is kept and the cell width grows. Fails if "at" is out of range or if it
+    /// is the last remaining column.
+    pub fn remove_column(&mut self, at: usize, shrink: bool) -> Result<Vec<V>, GridError> {
+        if at >= self.columns {
+            return Err(GridError::ColumnOutOfRange { col: at });
+        }
+        if self.columns == 1 {
+            return Err(GridError::ZeroColumns);
+        }
+        let removed = self.data.drain(self.column_range(at)).collect();
+        self.columns -= 1;
+        self.mark_resized();
+        if shrink {
+            self.set_width(self.width - self.cell_width);
+        }
+        self.recompute_cell_size();
+        Ok(removed)
+    }
+
+    /// Changes the physical size, keeping the number of cells, the normalized pivot and the
+    /// position. Fails if the new size isn't finite and larger than zero.
+    pub fn resize(&mut self, width: Scalar, height: Scalar) -> Result<(), GridError> {
+        Self::validate_args(width, height, self.columns, self.rows)?;
+        self.set_width(width);
+        self.set_height(height);
+        self.recompute_cell_size();
+        Ok(())
+    }
+
+    /// Same as "resize", but moves the Grid so that the physical point (anchor_x, anchor_y)
+    /// stays at the same normalized position inside it. Anchoring at the player keeps the
+    /// cell under them in place while the grid grows around it. Also fails if the anchor
+    /// isn't finite.
+    pub fn resize_anchored(
+        &mut self,
+        width: Scalar,
+        height: Scalar,
+        anchor_x: Scalar,
+        anchor_y: Scalar,
+    ) -> Result<(), GridError> {
+        for (field, value) in [("anchor_x", anchor_x), ("anchor_y", anchor_y)] {
+            if !value.is_finite() {
+                return Err(GridError::NonFiniteValue { field });
+            }
+        }
+        let u = self.local_x(anchor_x) / self.width;
+        let v = self.local_y(anchor_y) / self.height;
+        self.resize(width, height)?;
+        self.set_position(
+            anchor_x - (u * self.width) + self.offset_x,
+            anchor_y - (v * self.height) + self.offset_y,
+        );
+        Ok(())
+    }
+
+    // Changes the physical width, scaling the horizontal offset so the pivot stays at the
+    // same relative position.
+    fn set_width(&mut self, width: Scalar) {
+        let pivot = self.offset_x / self.width;
+        self.width = width;
+        self.offset_x = width * pivot;
+    }
+
+    // Changes the physical height, scaling the vertical offset so the pivot stays at the
+    // same relative position.
+    fn set_height(&mut self, height: Scalar) {
+        let pivot = self.offset_y / self.height;
+        self.height = height;
+        self.offset_y = height * pivot;
+    }
+
+    /// Same as "crop", but consumes the Grid instead of cloning the cells.
+    pub fn crop_into(
+        self,
+        col_min: usize,
+        row_min: usize,
+        col_max: usize,
+        row_max: usize,
+    ) -> Result<Grid<V>, GridError> {
+        self.check_region(col_min, row_min, col_max, row_max)?;
+        let rows = self.rows;
+        let mut old = self.data.into_iter().skip(col_min * rows);
+        let mut data = Vec::with_capacity((col_max - col_min + 1) * (row_max - row_min + 1));
+        for _ in col_min..=col_max {
+            let mut column = old.by_ref().take(rows);
+            data.extend(column.by_ref().skip(row_min).take(row_max - row_min + 1));
+            column.for_each(drop);
+        }
+        let grid = Grid {
+            data: Vec::new(),
+            ..self
+        };
+        Ok(grid.cropped_with(col_min, row_min, col_max, row_max, data))
+    }
+
+    // Checks that an inclusive cell region is inside the grid and not empty.
+    pub(super) fn check_region(
+        &self,
+        col_min: usize,
+        row_min: usize,
+        col_max: usize,
+        row_max: usize,
+    ) -> Result<(), GridError> {
+        if col_max >= self.columns || row_max >= self.rows {
+            return Err(GridError::IndicesOutOfRange {
+                col: col_max,
+                row: row_max,
+            });
+        }
+        if col_min > col_max {
+            return Err(GridError::ZeroColumns);
+        }
+        if row_min > row_max {
+            return Err(GridError::ZeroRows);
+        }
+        Ok(())
+    }
+
+    // Returns a Grid with the geometry of an inclusive cell region and the provided cells.
+    fn cropped_with<U>(
+        &self,
+        col_min: usize,
+        row_min: usize,
+        col_max: usize,
+        row_max: usize,
+        data: Vec<U>,
+    ) -> Grid<U> {
+        let columns = col_max - col_min + 1;
+        let rows = row_max - row_min + 1;
+        Grid {
+            width: columns as Scalar * self.cell_width,
+            height: rows as Scalar * self.cell_height,
+            cell_width: self.cell_width,
+            cell_height: self.cell_height,
+            columns,
+            rows,
+            offset_x: self.offset_x - (col_min as Scalar * self.cell_width),
+            offset_y: self.offset_y - (row_min as Scalar * self.cell_height),
+            x: self.x,
+            y: self.y,
+            data,
+            dirty: DirtyTracker::default(),
+            generation: self.generation.next(),
+        }
+    }
+
+    /// Reverses the order of the columns in place, so the left-most column becomes the
+    /// right-most one.
+    pub fn flip_horizontal(&mut self) {
+        self.mark_all_dirty();
+        let rows = self.rows;
+        let half = (self.columns / 2) * rows;
+        let (left, right) = self.data.split_at_mut(half);
+        let right_start = right.len() - half;
+        for (a, b) in left
+            .chunks_exact_mut(rows)
+            .zip(right[right_start..].chunks_exact_mut(rows).rev())
+        {
+            a.swap_with_slice(b);
+        }
+    }
+
+    /// Reverses the order of the rows in place, so the bottom row becomes the top one.
+    pub fn flip_vertical(&mut self) {
+        self.mark_all_dirty();
+        for column in self.data.chunks_exact_mut(self.rows) {
+            column.reverse();
+        }
+    }
+
+    /// Consumes the Grid and returns one where columns become rows and vice versa, so the
+    /// cell at (col, row) moves to (row, col). The physical size and pivot are swapped too.
+    pub fn transpose(self) -> Grid<V> {
+        // Columns stored one after the other are the rows of the transposed grid.
+        let (columns, rows) = (self.rows, self.columns);
+        let dirty = self.derived_dirty(columns, rows);
+        let mut columns_data: Vec<Vec<V>> =
+            (0..columns).map(|_| Vec::with_capacity(rows)).collect();
+        for (i, cell) in self.data.into_iter().enumerate() {
+            columns_data[i % columns].push(cell);
+        }
+        Grid {
+            width: self.height,
+            height: self.width,
+            cell_width: self.cell_height,
+            cell_height: self.cell_width,
+            columns,
+            rows,
+            offset_x: self.offset_y,
+            offset_y: self.offset_x,
+            x: self.x,
+            y: self.y,
+            data: columns_data.into_iter().flatten().collect(),
+            dirty,
+            generation: self.generation.next(),
+        }
+    }
+
+    /// Consumes the Grid and returns it rotated 90 degrees clockwise: the left column becomes
+    /// the top row. The physical shape is rotated around the pivot, so a centered grid stays
+    /// centered.
+    pub fn rotate_cw(self) -> Grid<V> {
+        let mut grid = self.transpose();
+        grid.flip_vertical();
+        grid.offset_y = grid.height - grid.offset_y;
+        grid
+    }
+
+    /// Consumes the Grid and returns it rotated 90 degrees counter-clockwise: the left column
+    /// becomes the bottom row. The physical shape is rotated around the pivot, so a centered
+    /// grid stays centered.
+    pub fn rotate_ccw(self) -> Grid<V> {
+        let mut grid = self.transpose();
+        grid.flip_horizontal();
+        grid.offset_x = grid.width - grid.offset_x;
+        grid
+    }
+
+    /// Moves all cells by "d_col" columns and "d_row" rows (positive values move them right
+    /// and up). Cells moved past an edge wrap around to the opposite side.
+    pub fn shift_wrap(&mut self, d_col: isize, d_row: isize) {
+        self.mark_all_dirty();
+        let col_steps = d_col.rem_euclid(self.columns as isize) as usize;
+        self.data.rotate_right(col_steps * self.rows);
+        let row_steps = d_row.rem_euclid(self.rows as isize) as usize;
+        if row_steps > 0 {
+            for column in self.data.chunks_exact_mut(self.rows) {
+                column.rotate_right(row_steps);
+            }
+        }
+    }
+
+    /// Moves all cells by "d_col" columns and "d_row" rows (positive values move them right
+    /// and up). Cells moved past an edge are dropped, and the vacated cells are created with
+    /// "fill". Shifting by the grid size or more fills every cell.
+    pub fn shift_fill<F>(&mut self, d_col: isize, d_row: isize, mut fill: F)
+    where
+        F: FnMut() -> V,
+    {
+        self.shift_wrap(d_col, d_row);
+        let rows = self.rows;
+        // Vacated columns
+        let col_steps = d_col.unsigned_abs().min(self.columns);
+        let cols = if d_col > 0 {
+            0..col_steps
+        } else {
+            self.columns - col_steps..self.columns
+        };
+        for cell in &mut self.data[cols.start * rows..cols.end * rows] {
+            *cell = fill();
+        }
+        // Vacated rows, skipping the columns already filled
+        let row_steps = d_row.unsigned_abs().min(rows);
+        let vacated = if d_row > 0 {
+            0..row_steps
+        } else {
+            rows - row_steps..rows
+        };
+        for (col, column) in self.data.chunks_exact_mut(rows).enumerate() {
+            if cols.contains(&col) {
+                continue;
+            }
+            for cell in &mut column[vacated.clone()] {
+                *cell = fill();
+            }
+        }
+    }
+
+    /// Returns a Grid with the same physical size, pivot and position but a different
+    /// number of columns and rows. Each new cell is created by "merge", which receives the
+    /// cells of this Grid overlapping it, row by row starting at the bottom row. When
+    /// upsampling that is usually the single cell containing it.
+    pub fn resample<U, F>(
+        &self,
+        columns: usize,
+        rows: usize,
+        mut merge: F,
+    ) -> Result<Grid<U>, GridError>
+    where
+        F: FnMut(&[&V]) -> U,
+    {
+        Self::validate_args(self.width, self.height, columns, rows)?;
+        let mut sources = Vec::new();
+        let mut grid = Grid::from_fn(
+            self.width,
+            self.height,
+            columns,
+            rows,
+            self.offset_x,
+            self.offset_y,
+            |col, row| {
+                sources.clear();
+                let (col_min, col_max) = overlapping(col, columns, self.columns);
+                let (row_min, row_max) = overlapping(row, rows, self.rows);
+                for source_row in row_min..=row_max {
+                    for source_col in col_min..=col_max {
+                        sources.push(&self.data[self.flat_index(source_col, source_row)]);
+                    }
+                }
+                merge(&sources)
+            },
+        );
+        grid.x = self.x;
+        grid.y = self.y;
+        Ok(grid)
+    }
+}
+
+// Returns the first and last source cells overlapping cell "index" when "source" cells are
+// split into "count" ones, along one axis.
+fn overlapping(index: usize, count: usize, source: usize) -> (usize, usize) {
+    let start = (index * source) / count;
+    let end = ((index + 1) * source).div_ceil(count);
+    (start, end - 1)
+}
@@ -0,0 +1,52 @@
+// Scalar type used for all physical values, plus the few float functions the grid needs.
+// no_std floats don't have "floor" and friends, so they come from libm unless the "std"
+// feature is enabled.
+
+/// Type used for physical coordinates and sizes. It is "f64" when the "f64" feature is
+/// enabled, and "f32" otherwise.
+#[cfg(not(feature = "f64"))]
+pub type Scalar = f32;
+
+/// Type used for physical coordinates and sizes. It is "f64" when the "f64" feature is
+/// enabled, and "f32" otherwise.
+#[cfg(feature = "f64")]
+pub type Scalar = f64;
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("gridstore needs either the \"std\" or the \"libm\" feature");
+
+#[cfg(all(not(feature = "std"), feature = "libm", not(feature = "f64")))]
+pub(crate) use libm::{ceilf as ceil, cosf as cos, floorf as floor, sinf as sin, sqrtf as sqrt};
+
+#[cfg(all(not(feature = "std"), feature = "libm", feature = "f64"))]
+pub(crate) use libm::{ceil, cos, floor, sin, sqrt};
+
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn floor(x: Scalar) -> Scalar {
+    x.floor()
+}
+
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn ceil(x: Scalar) -> Scalar {
+    x.ceil()
+}
+
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn sqrt(x: Scalar) -> Scalar {
+    x.sqrt()
+}
+
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn sin(x: Scalar) -> Scalar {
+    x.sin()
+}
+
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn cos(x: Scalar) -> Scalar {
+    x.cos()
+}
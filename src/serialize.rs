@@ -0,0 +1,82 @@
+use super::*;
+use serde::de::Error as _;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// Cells are stored row by row, starting at the bottom row, so the format doesn't depend
+// on the internal storage layout.
+impl<V> Serialize for Grid<V>
+where
+    V: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Grid", 9)?;
+        state.serialize_field("width", &self.width)?;
+        state.serialize_field("height", &self.height)?;
+        state.serialize_field("columns", &self.columns)?;
+        state.serialize_field("rows", &self.rows)?;
+        state.serialize_field("offset_x", &self.offset_x)?;
+        state.serialize_field("offset_y", &self.offset_y)?;
+        state.serialize_field("x", &self.x)?;
+        state.serialize_field("y", &self.y)?;
+        state.serialize_field("cells", &RowMajor(self))?;
+        state.end()
+    }
+}
+
+struct RowMajor<'a, V>(&'a Grid<V>);
+
+impl<V> Serialize for RowMajor<'_, V>
+where
+    V: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.0.iter_all_cells())
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Grid")]
+struct GridData<V> {
+    width: Scalar,
+    height: Scalar,
+    columns: usize,
+    rows: usize,
+    offset_x: Scalar,
+    offset_y: Scalar,
+    x: Scalar,
+    y: Scalar,
+    cells: Vec<V>,
+}
+
+impl<'de, V> Deserialize<'de> for Grid<V>
+where
+    V: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let data = GridData::<V>::deserialize(deserializer)?;
+        let mut grid = Grid::from_rows(
+            data.width,
+            data.height,
+            data.columns,
+            data.rows,
+            data.offset_x,
+            data.offset_y,
+            data.cells,
+        )
+        .map_err(D::Error::custom)?;
+        // Assigned directly so a corrupt position is an error instead of a panic
+        (grid.x, grid.y) = (data.x, data.y);
+        grid.validate().map_err(D::Error::custom)?;
+        Ok(grid)
+    }
+}
@@ -0,0 +1,380 @@
+use super::*;
+use iter_segment::clip;
+use scalar::{ceil, cos, sin, sqrt};
+
+const TAU: Scalar = core::f64::consts::TAU as Scalar;
+
+impl<V> Grid<V> {
+    /// Returns an iterator that yields (column,row) pairs for each cell that overlaps a circle.
+    pub fn iter_coords_in_circle(
+        &self,
+        center_x: Scalar,
+        center_y: Scalar,
+        radius: Scalar,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let coords = if radius >= 0.0 {
+            self.iter_coords(
+                center_x - radius,
+                center_y - radius,
+                center_x + radius,
+                center_y + radius,
+            )
+        } else {
+            Self::coords_in_edges(None)
+        };
+        // Circle center relative to the grid's bottom/left corner
+        let x = self.local_x(center_x);
+        let y = self.local_y(center_y);
+        let cell_width = self.cell_width;
+        let cell_height = self.cell_height;
+        coords.filter(move |&(col, row)| {
+            // Distance from the center to the closest point of the cell
+            let left = col as Scalar * cell_width;
+            let bottom = row as Scalar * cell_height;
+            let dx = (left - x).max(x - (left + cell_width)).max(0.0);
+            let dy = (bottom - y).max(y - (bottom + cell_height)).max(0.0);
+            (dx * dx) + (dy * dy) <= radius * radius
+        })
+    }
+
+    /// Returns an iterator with the cells overlapping a circle, row by row starting at the
+    /// bottom row.
+    pub fn iter_cells_in_circle(
+        &self,
+        center_x: Scalar,
+        center_y: Scalar,
+        radius: Scalar,
+    ) -> impl Iterator<Item = &V> + '_ {
+        self.iter_coords_in_circle(center_x, center_y, radius)
+            .map(|(col, row)| &self.data[self.flat_index(col, row)])
+    }
+
+    /// Same as "iter_coords_in_circle", but takes the center as a point. See
+    /// "get_cell_coords_at".
+    pub fn iter_coords_in_circle_at(
+        &self,
+        center: impl Into<[Scalar; 2]>,
+        radius: Scalar,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let [x, y] = center.into();
+        self.iter_coords_in_circle(x, y, radius)
+    }
+
+    /// Same as "iter_cells_in_circle", but takes the center as a point. See
+    /// "get_cell_coords_at".
+    pub fn iter_cells_in_circle_at(
+        &self,
+        center: impl Into<[Scalar; 2]>,
+        radius: Scalar,
+    ) -> impl Iterator<Item = &V> + '_ {
+        let [x, y] = center.into();
+        self.iter_cells_in_circle(x, y, radius)
+    }
+
+    /// Returns an iterator that yields (column,row) pairs for each cell that overlaps a ring,
+    /// the area between two circles with the same center. With an inner radius of 0.0 this
+    /// is the same as "iter_coords_in_circle".
+    pub fn iter_coords_in_ring(
+        &self,
+        center_x: Scalar,
+        center_y: Scalar,
+        inner_radius: Scalar,
+        outer_radius: Scalar,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let outer_radius = if outer_radius >= inner_radius {
+            outer_radius
+        } else {
+            -1.0
+        };
+        let x = self.local_x(center_x);
+        let y = self.local_y(center_y);
+        let cell_width = self.cell_width;
+        let cell_height = self.cell_height;
+        self.iter_coords_in_circle(center_x, center_y, outer_radius)
+            .filter(move |&(col, row)| {
+                // Distance from the center to the farthest point of the cell
+                let left = col as Scalar * cell_width;
+                let bottom = row as Scalar * cell_height;
+                let dx = (x - left).max((left + cell_width) - x);
+                let dy = (y - bottom).max((bottom + cell_height) - y);
+                inner_radius <= 0.0 || (dx * dx) + (dy * dy) >= inner_radius * inner_radius
+            })
+    }
+
+    /// Returns an iterator with the cells overlapping a ring, row by row starting at the
+    /// bottom row.
+    pub fn iter_cells_in_ring(
+        &self,
+        center_x: Scalar,
+        center_y: Scalar,
+        inner_radius: Scalar,
+        outer_radius: Scalar,
+    ) -> impl Iterator<Item = &V> + '_ {
+        self.iter_coords_in_ring(center_x, center_y, inner_radius, outer_radius)
+            .map(|(col, row)| &self.data[self.flat_index(col, row)])
+    }
+
+    /// Returns an iterator that yields (column,row) pairs for each cell that overlaps a
+    /// circular sector, going counter-clockwise from "angle_start" to "angle_end" (in radians,
+    /// 0.0 pointing right). The sector may wrap past a full turn, so 5.5 to 0.5 covers the
+    /// angles around 0.0. Sectors of zero width are empty, and a full turn or more is the same
+    /// as "iter_coords_in_circle".
+    pub fn iter_coords_in_sector(
+        &self,
+        center_x: Scalar,
+        center_y: Scalar,
+        radius: Scalar,
+        angle_start: Scalar,
+        angle_end: Scalar,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let delta = angle_end - angle_start;
+        let span = if delta >= TAU {
+            TAU
+        } else {
+            delta - (floor(delta / TAU) * TAU)
+        };
+        let radius = if span > 0.0 { radius } else { -1.0 };
+        // Directions of the two straight edges
+        let start = (cos(angle_start), sin(angle_start));
+        let end = (cos(angle_start + span), sin(angle_start + span));
+        let x = self.local_x(center_x);
+        let y = self.local_y(center_y);
+        let cell_width = self.cell_width;
+        let cell_height = self.cell_height;
+        let in_angle = move |dx: Scalar, dy: Scalar| {
+            let from_start = (start.0 * dy) - (start.1 * dx);
+            let to_end = (dx * end.1) - (dy * end.0);
+            if span >= TAU {
+                true
+            } else if span <= TAU / 2.0 {
+                from_start >= 0.0 && to_end >= 0.0
+            } else {
+                from_start >= 0.0 || to_end >= 0.0
+            }
+        };
+        self.iter_coords_in_circle(center_x, center_y, radius)
+            .filter(move |&(col, row)| {
+                let left = col as Scalar * cell_width;
+                let bottom = row as Scalar * cell_height;
+                // The closest point of the cell is inside the circle, so the cell overlaps
+                // the sector if it crosses one of the straight edges, or if it doesn't and is
+                // entirely within the angle.
+                let crosses = |(ex, ey): (Scalar, Scalar)| {
+                    let (dx, dy) = (ex * radius, ey * radius);
+                    clip(
+                        x - left,
+                        y - bottom,
+                        dx,
+                        dy,
+                        0.0,
+                        1.0,
+                        cell_width,
+                        cell_height,
+                    )
+                    .is_some()
+                };
+                let closest_x = x.clamp(left, left + cell_width);
+                let closest_y = y.clamp(bottom, bottom + cell_height);
+                in_angle(closest_x - x, closest_y - y) || crosses(start) || crosses(end)
+            })
+    }
+
+    /// Returns an iterator with the cells overlapping a circular sector, row by row starting
+    /// at the bottom row.
+    pub fn iter_cells_in_sector(
+        &self,
+        center_x: Scalar,
+        center_y: Scalar,
+        radius: Scalar,
+        angle_start: Scalar,
+        angle_end: Scalar,
+    ) -> impl Iterator<Item = &V> + '_ {
+        self.iter_coords_in_sector(center_x, center_y, radius, angle_start, angle_end)
+            .map(|(col, row)| &self.data[self.flat_index(col, row)])
+    }
+
+    /// Returns an iterator that yields (column,row) pairs for each cell that overlaps a
+    /// triangle, row by row starting at the bottom row. The winding order doesn't matter.
+    pub fn iter_coords_in_triangle(
+        &self,
+        p0: impl Into<[Scalar; 2]>,
+        p1: impl Into<[Scalar; 2]>,
+        p2: impl Into<[Scalar; 2]>,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        polygon_coords(
+            [p0.into(), p1.into(), p2.into()].into_iter(),
+            self.polygon_layout(),
+        )
+    }
+
+    /// Returns an iterator with the cells overlapping a triangle, row by row starting at the
+    /// bottom row.
+    pub fn iter_cells_in_triangle(
+        &self,
+        p0: impl Into<[Scalar; 2]>,
+        p1: impl Into<[Scalar; 2]>,
+        p2: impl Into<[Scalar; 2]>,
+    ) -> impl Iterator<Item = &V> + '_ {
+        // Converted here so the returned iterator doesn't capture the argument types
+        let [p0, p1, p2]: [[Scalar; 2]; 3] = [p0.into(), p1.into(), p2.into()];
+        self.iter_coords_in_triangle(p0, p1, p2)
+            .map(|(col, row)| &self.data[self.flat_index(col, row)])
+    }
+
+    /// Returns an iterator that yields (column,row) pairs for each cell that overlaps a convex
+    /// polygon, row by row starting at the bottom row. The winding order doesn't matter.
+    /// Non-convex polygons are filled between their left-most and right-most edges on each
+    /// row, so no overlapping cell is missed.
+    pub fn iter_coords_in_convex_polygon<'p, P>(
+        &self,
+        points: &'p [P],
+    ) -> impl Iterator<Item = (usize, usize)> + 'p
+    where
+        P: Into<[Scalar; 2]> + Copy,
+    {
+        polygon_coords(
+            points.iter().map(|&point| point.into()),
+            self.polygon_layout(),
+        )
+    }
+
+    /// Returns an iterator with the cells overlapping a convex polygon, row by row starting
+    /// at the bottom row.
+    pub fn iter_cells_in_convex_polygon<'a, P>(
+        &'a self,
+        points: &'a [P],
+    ) -> impl Iterator<Item = &'a V> + 'a
+    where
+        P: Into<[Scalar; 2]> + Copy,
+    {
+        self.iter_coords_in_convex_polygon(points)
+            .map(|(col, row)| &self.data[self.flat_index(col, row)])
+    }
+
+    // Geometry passed to "polygon_coords".
+    fn polygon_layout(&self) -> PolygonLayout {
+        PolygonLayout {
+            origin: (self.local_x(0.0), self.local_y(0.0)),
+            cell_size: (self.cell_width, self.cell_height),
+            size: (self.width, self.height),
+            counts: (self.columns, self.rows),
+        }
+    }
+
+    /// Allows a function to modify the contents of every cell whose center is inside a circle.
+    /// The function also receives the cell coordinates and the distance from the circle center
+    /// to the cell center, normalized so that 0.0 is the center and 1.0 the edge.
+    pub fn modify_in_circle<F>(
+        &mut self,
+        center_x: Scalar,
+        center_y: Scalar,
+        radius: Scalar,
+        mut func: F,
+    ) where
+        F: FnMut((usize, usize), Scalar, &mut V),
+    {
+        for (col, row) in self.iter_coords_in_circle(center_x, center_y, radius) {
+            let Some((x, y)) = self.cell_center(col, row) else {
+                continue;
+            };
+            let (dx, dy) = (x - center_x, y - center_y);
+            let distance_squared = (dx * dx) + (dy * dy);
+            if distance_squared > radius * radius {
+                continue;
+            }
+            let distance = if radius > 0.0 {
+                sqrt(distance_squared) / radius
+            } else {
+                0.0
+            };
+            self.mark_dirty(col, row);
+            let index = self.flat_index(col, row);
+            func((col, row), distance, &mut self.data[index]);
+        }
+    }
+}
+
+// Horizontal extent of the part of a convex polygon between two heights, from its edges
+// clipped to that band. None if the polygon doesn't reach the band.
+fn row_span<I>(points: I, bottom: Scalar, top: Scalar) -> Option<(Scalar, Scalar)>
+where
+    I: Iterator<Item = [Scalar; 2]> + Clone,
+{
+    let first = points.clone().next()?;
+    let edges = points
+        .clone()
+        .zip(points.skip(1).chain(core::iter::once(first)));
+    let mut span: Option<(Scalar, Scalar)> = None;
+    for ([x0, y0], [x1, y1]) in edges {
+        let (t0, t1) = if y0 == y1 {
+            if y0 < bottom || y0 > top {
+                continue;
+            }
+            (0.0, 1.0)
+        } else {
+            let t_bottom = (bottom - y0) / (y1 - y0);
+            let t_top = (top - y0) / (y1 - y0);
+            (t_bottom.min(t_top).max(0.0), t_bottom.max(t_top).min(1.0))
+        };
+        if t0 > t1 {
+            continue;
+        }
+        for t in [t0, t1] {
+            let x = x0 + ((x1 - x0) * t);
+            span = Some(match span {
+                Some((left, right)) => (left.min(x), right.max(x)),
+                None => (x, x),
+            });
+        }
+    }
+    span
+}
+
+// Geometry of a Grid, copied so the polygon iterators don't borrow it.
+#[derive(Clone, Copy)]
+struct PolygonLayout {
+    // Offset from physical coordinates to the grid's local space
+    origin: (Scalar, Scalar),
+    cell_size: (Scalar, Scalar),
+    size: (Scalar, Scalar),
+    counts: (usize, usize),
+}
+
+// Coordinates of the cells overlapping a polygon, row by row starting at the bottom row.
+// Cells touching the polygon count as overlapping.
+fn polygon_coords<I>(points: I, layout: PolygonLayout) -> impl Iterator<Item = (usize, usize)>
+where
+    I: Iterator<Item = [Scalar; 2]> + Clone,
+{
+    let PolygonLayout {
+        origin: (x, y),
+        cell_size: (cell_width, cell_height),
+        size: (width, height),
+        counts: (columns, rows),
+    } = layout;
+    // Points relative to the grid's bottom/left corner
+    let points = points.map(move |[px, py]| [px + x, py + y]);
+    let (low, high) = points.clone().fold(
+        (Scalar::INFINITY, Scalar::NEG_INFINITY),
+        |(low, high), [_, y]| (low.min(y), high.max(y)),
+    );
+    let rows = if low <= high && high >= 0.0 && low <= height {
+        let first = (ceil(low / cell_height) - 1.0).max(0.0) as usize;
+        let last = (floor(high / cell_height) as usize).min(rows - 1);
+        first.min(last)..last + 1
+    } else {
+        0..0
+    };
+    rows.flat_map(move |row| {
+        let bottom = row as Scalar * cell_height;
+        let cols = match row_span(points.clone(), bottom, bottom + cell_height) {
+            Some((left, right)) if right >= 0.0 && left <= width => {
+                let first = (ceil(left / cell_width) - 1.0).max(0.0) as usize;
+                let last = (floor(right / cell_width) as usize).min(columns - 1);
+                first.min(last)..last + 1
+            }
+            _ => 0..0,
+        };
+        cols.map(move |col| (col, row))
+    })
+}
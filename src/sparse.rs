@@ -0,0 +1,260 @@
+use super::*;
+use alloc::collections::BTreeMap;
+
+/// A Grid that only stores the cells that differ from the default value, for mostly empty
+/// layers. Absent cells read as a shared default value. Lookups and rect iteration behave
+/// exactly like the dense Grid with the same geometry.
+#[derive(Debug, Clone)]
+pub struct SparseGrid<V> {
+    // Same geometry as the dense Grid, used for all coordinate conversions
+    frame: Grid<()>,
+    // Stored cells, keyed by their position in the dense storage
+    cells: BTreeMap<usize, V>,
+    default: V,
+}
+
+impl<V> SparseGrid<V>
+where
+    V: Default,
+{
+    /// Returns an empty SparseGrid. If "centered" is true the pivot is at the center of the
+    /// grid, otherwise it is at the bottom-left corner.
+    pub fn new(width: Scalar, height: Scalar, columns: usize, rows: usize, centered: bool) -> Self {
+        Self::with_frame(Grid::new(width, height, columns, rows, centered))
+    }
+
+    /// Same as "new", but returns an error instead of panicking if the size is not finite
+    /// and larger than zero, or if there are no columns or rows.
+    pub fn try_new(
+        width: Scalar,
+        height: Scalar,
+        columns: usize,
+        rows: usize,
+        centered: bool,
+    ) -> Result<Self, GridError> {
+        Grid::try_new(width, height, columns, rows, centered).map(Self::with_frame)
+    }
+
+    fn with_frame(frame: Grid<()>) -> Self {
+        Self {
+            frame,
+            cells: BTreeMap::new(),
+            default: V::default(),
+        }
+    }
+
+    /// Returns an optional mutable reference to the cell containing the provided coordinates.
+    /// Absent cells are stored with the default value first, even if the caller doesn't
+    /// write to them; "compact" removes them again.
+    pub fn get_cell_mut(&mut self, x: Scalar, y: Scalar) -> Option<&mut V> {
+        let (col, row) = self.frame.get_cell_coords(x, y)?;
+        self.get_cell_by_indices_mut(col, row)
+    }
+
+    /// Same as "get_cell_mut", using the cell indices.
+    pub fn get_cell_by_indices_mut(&mut self, col: usize, row: usize) -> Option<&mut V> {
+        let index = self.frame.raw_index(col, row)?;
+        Some(self.cells.entry(index).or_default())
+    }
+}
+
+impl<V> SparseGrid<V>
+where
+    V: Default + PartialEq,
+{
+    /// Stores a value in the cell containing the provided coordinates and returns the
+    /// previous contents. If the coordinates are outside the grid the value is given back as
+    /// an error.
+    pub fn set_cell(&mut self, x: Scalar, y: Scalar, value: V) -> Result<V, V> {
+        match self.frame.get_cell_coords(x, y) {
+            Some((col, row)) => self.set_cell_by_indices(col, row, value),
+            None => Err(value),
+        }
+    }
+
+    /// Stores a value in the cell at the provided indices and returns the previous
+    /// contents. If the indices are out of range the value is given back as an error.
+    /// Storing the default value removes the cell.
+    pub fn set_cell_by_indices(&mut self, col: usize, row: usize, value: V) -> Result<V, V> {
+        match self.frame.raw_index(col, row) {
+            Some(index) if value == self.default => {
+                Ok(self.cells.remove(&index).unwrap_or_default())
+            }
+            Some(index) => Ok(self.cells.insert(index, value).unwrap_or_default()),
+            None => Err(value),
+        }
+    }
+
+    /// Allows a function to modify the same cells "Grid::modify_in_rect" would. Cells left
+    /// with the default value are not stored.
+    pub fn modify_in_rect<F>(
+        &mut self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+        mut func: F,
+    ) where
+        F: FnMut(&mut V),
+    {
+        for (col, row) in self.frame.modified_cells(left, bottom, right, top) {
+            let index = self.frame.flat_index(col, row);
+            let mut value = self.cells.remove(&index).unwrap_or_default();
+            func(&mut value);
+            if value != self.default {
+                self.cells.insert(index, value);
+            }
+        }
+    }
+
+    /// Removes the stored cells that hold the default value.
+    pub fn compact(&mut self) {
+        let default = &self.default;
+        self.cells.retain(|_, value| value != default);
+    }
+}
+
+// Compares the logical contents, so stored default values (see "get_cell_mut") don't
+// make otherwise equal grids different.
+impl<V> PartialEq for SparseGrid<V>
+where
+    V: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        fn stored<V: PartialEq>(grid: &SparseGrid<V>) -> impl Iterator<Item = (&usize, &V)> {
+            let default = &grid.default;
+            grid.cells
+                .iter()
+                .filter(move |(_, value)| *value != default)
+        }
+        self.frame == other.frame && self.default == other.default && stored(self).eq(stored(other))
+    }
+}
+
+impl<V> SparseGrid<V> {
+    /// A Grid without cells that has the same geometry, for the physical queries like
+    /// "cell_rect", "left" or "iter_coords".
+    pub fn frame(&self) -> &Grid<()> {
+        &self.frame
+    }
+
+    /// Number of columns.
+    pub fn columns(&self) -> usize {
+        self.frame.columns
+    }
+
+    /// Number of rows.
+    pub fn rows(&self) -> usize {
+        self.frame.rows
+    }
+
+    /// Number of stored cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns true if no cells are stored.
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Same as "Grid::get_cell_coords".
+    pub fn get_cell_coords(&self, x: Scalar, y: Scalar) -> Option<(usize, usize)> {
+        self.frame.get_cell_coords(x, y)
+    }
+
+    /// Returns an optional reference to the cell containing the provided coordinates, which
+    /// is the shared default value if the cell isn't stored.
+    pub fn get_cell(&self, x: Scalar, y: Scalar) -> Option<&V> {
+        let (col, row) = self.frame.get_cell_coords(x, y)?;
+        self.get_cell_by_indices(col, row)
+    }
+
+    /// Same as "get_cell", using the cell indices.
+    pub fn get_cell_by_indices(&self, col: usize, row: usize) -> Option<&V> {
+        let index = self.frame.raw_index(col, row)?;
+        Some(self.cells.get(&index).unwrap_or(&self.default))
+    }
+
+    /// Removes a stored cell and returns it. None if it wasn't stored or the indices are out
+    /// of range.
+    pub fn remove_cell_by_indices(&mut self, col: usize, row: usize) -> Option<V> {
+        let index = self.frame.raw_index(col, row)?;
+        self.cells.remove(&index)
+    }
+
+    /// Returns an iterator with the cells overlapping a rectangle, in the same order as
+    /// "Grid::iter_cells_in_rect". Absent cells yield the shared default value.
+    pub fn iter_cells_in_rect(
+        &self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+    ) -> impl Iterator<Item = &V> + '_ {
+        self.frame
+            .iter_coords(left, bottom, right, top)
+            .map(|(col, row)| self.cell(col, row))
+    }
+
+    /// Returns an iterator that yields (value, column, row) for the cells overlapping a
+    /// rectangle, in the same order as "iter_cells_in_rect".
+    pub fn iter_with_coords_in_rect(
+        &self,
+        left: Scalar,
+        bottom: Scalar,
+        right: Scalar,
+        top: Scalar,
+    ) -> impl Iterator<Item = (&V, usize, usize)> + '_ {
+        self.frame
+            .iter_coords(left, bottom, right, top)
+            .map(|(col, row)| (self.cell(col, row), col, row))
+    }
+
+    /// Returns an iterator that yields ((column, row), value) for the stored cells only,
+    /// column by column.
+    pub fn iter_stored(&self) -> impl Iterator<Item = ((usize, usize), &V)> + '_ {
+        let rows = self.frame.rows;
+        self.cells
+            .iter()
+            .map(move |(index, value)| ((index / rows, index % rows), value))
+    }
+
+    /// Returns a dense Grid with the same geometry and contents.
+    pub fn to_dense(&self) -> Grid<V>
+    where
+        V: Clone,
+    {
+        self.frame
+            .map_with_coords(|col, row, _| self.cell(col, row).clone())
+    }
+
+    // Cell at indices known to be in range.
+    fn cell(&self, col: usize, row: usize) -> &V {
+        let index = self.frame.flat_index(col, row);
+        self.cells.get(&index).unwrap_or(&self.default)
+    }
+}
+
+impl<V> Grid<V> {
+    /// Returns a SparseGrid with the same geometry and contents, storing only the cells that
+    /// differ from the default value.
+    pub fn to_sparse(&self) -> SparseGrid<V>
+    where
+        V: Default + PartialEq + Clone,
+    {
+        let default = V::default();
+        let cells = self
+            .data
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| **value != default)
+            .map(|(index, value)| (index, value.clone()))
+            .collect();
+        SparseGrid {
+            frame: self.map(|_| ()),
+            cells,
+            default,
+        }
+    }
+}
@@ -1,12 +1,18 @@
-use crate::Grid;
+use crate::{
+    Automaton, CellId, Connectivity, Cursor, EdgeMode, Grid, GridBuilder, GridError, IterGridRect,
+    NeighborView, PathContext, Pivot, Scalar, SparseGrid,
+};
 use rand::Rng;
 
 extern crate alloc;
+extern crate std;
+use alloc::format;
+use alloc::vec;
 use alloc::vec::Vec;
 
 #[test]
 fn grid_basic() {
-    let mut grid = Grid::<Vec<(f32, f32)>>::new(100.0, 100.0, 10, 10, false);
+    let mut grid = Grid::<Vec<(Scalar, Scalar)>>::new(100.0, 100.0, 10, 10, false);
     let mut rng = rand::thread_rng();
     for _n in 0..100 {
         let x = rng.gen_range(0.0..100.0);
@@ -16,7 +22,7 @@ fn grid_basic() {
         };
     }
 
-    for (i_x, col) in grid.data.iter().enumerate() {
+    for (i_x, col) in grid.data.chunks(grid.rows).enumerate() {
         for (i_y, cell) in col.iter().enumerate() {
             if cell.is_empty() {
                 continue;
@@ -32,7 +38,7 @@ fn grid_basic() {
 
 #[test]
 fn grid_negative_values() {
-    let mut grid = Grid::<Vec<(f32, f32)>>::new(100.0, 100.0, 10, 10, true);
+    let mut grid = Grid::<Vec<(Scalar, Scalar)>>::new(100.0, 100.0, 10, 10, true);
     let mut rng = rand::thread_rng();
     for _n in 0..100 {
         let x = rng.gen_range(grid.left()..grid.right());
@@ -42,7 +48,7 @@ fn grid_negative_values() {
         };
     }
 
-    for (i_x, col) in grid.data.iter().enumerate() {
+    for (i_x, col) in grid.data.chunks(grid.rows).enumerate() {
         for (i_y, cell) in col.iter().enumerate() {
             if cell.is_empty() {
                 continue;
@@ -63,8 +69,8 @@ fn iter_y_up() {
     let mut grid = Grid::<usize>::new(100.0, 100.0, 10, 10, false);
     for row in 0..10 {
         for col in 0..10 {
-            let x = col as f32 * grid.cell_width;
-            let y = row as f32 * grid.cell_height;
+            let x = col as Scalar * grid.cell_width;
+            let y = row as Scalar * grid.cell_height;
             if let Some(cell) = grid.get_cell_mut(x, y) {
                 *cell = (row * 10) + col;
             };
@@ -84,8 +90,8 @@ fn iter_y_down() {
     let mut grid = Grid::<usize>::new(100.0, 100.0, 10, 10, false);
     for row in 0..10 {
         for col in 0..10 {
-            let x = col as f32 * grid.cell_width;
-            let y = (9 - row) as f32 * grid.cell_height;
+            let x = col as Scalar * grid.cell_width;
+            let y = (9 - row) as Scalar * grid.cell_height;
             // print!("{}, {} -> ", x, y);
             if let Some(cell) = grid.get_cell_mut(x, y) {
                 *cell = (row * 10) + col;
@@ -121,3 +127,3894 @@ fn iter_coords(){
         assert!(row > 2 && row < 10);
     }
 }
+
+#[test]
+fn non_square_cell_size() {
+    let grid = Grid::<usize>::new(200.0, 100.0, 20, 5, false);
+    assert_eq!(grid.cell_width(), 10.0);
+    assert_eq!(grid.cell_height(), 20.0);
+
+    assert_eq!(grid.get_cell_coords(5.0, 5.0), Some((0, 0)));
+    assert_eq!(grid.get_cell_coords(195.0, 95.0), Some((19, 4)));
+    assert_eq!(grid.get_cell_coords(105.0, 55.0), Some((10, 2)));
+}
+
+#[test]
+fn non_square_iter_cells_in_rect() {
+    let mut grid = Grid::<(usize, usize)>::new(200.0, 100.0, 20, 5, false);
+    for col in 0..20 {
+        for row in 0..5 {
+            *grid.get_cell_by_indices_mut(col, row).unwrap() = (col, row);
+        }
+    }
+
+    // Upper half of the grid: rows 3 and 4, all columns.
+    let cells: Vec<_> = grid.iter_cells_in_rect(0.0, 60.0, 199.0, 99.0).collect();
+    assert_eq!(cells.len(), 40);
+    for (col, row) in cells {
+        assert!(*col < 20);
+        assert!(*row == 3 || *row == 4);
+    }
+}
+
+#[test]
+fn non_square_modify_in_rect() {
+    let mut grid = Grid::<usize>::new(200.0, 100.0, 20, 5, false);
+    grid.modify_in_rect(15.0, 25.0, 35.0, 85.0, |cell| *cell += 1);
+
+    assert_eq!(*grid.get_cell_by_indices(1, 1).unwrap(), 1);
+    assert_eq!(*grid.get_cell_by_indices(3, 1).unwrap(), 1);
+    assert_eq!(*grid.get_cell_by_indices(1, 4).unwrap(), 1);
+    assert_eq!(*grid.get_cell_by_indices(3, 4).unwrap(), 1);
+    assert_eq!(*grid.get_cell_by_indices(0, 0).unwrap(), 0);
+    assert_eq!(*grid.get_cell_by_indices(4, 1).unwrap(), 0);
+}
+
+#[test]
+fn non_square_edges() {
+    let grid = Grid::<usize>::new(100.0, 50.0, 10, 5, false);
+    assert_eq!(grid.left(), 0.0);
+    assert_eq!(grid.right(), 100.0);
+    assert_eq!(grid.bottom(), 0.0);
+    assert_eq!(grid.top(), 50.0);
+
+    let grid = Grid::<usize>::new(100.0, 50.0, 10, 5, true);
+    assert_eq!(grid.left(), -50.0);
+    assert_eq!(grid.right(), 50.0);
+    assert_eq!(grid.bottom(), -25.0);
+    assert_eq!(grid.top(), 25.0);
+    assert_eq!(grid.get_cell_coords(-49.0, 24.0), Some((0, 4)));
+}
+
+#[test]
+fn cell_coords_edges() {
+    let grid = Grid::<usize>::new(100.0, 50.0, 10, 5, false);
+    // Exactly on the right/top edges
+    assert_eq!(grid.get_cell_coords(100.0, 25.0), None);
+    assert_eq!(grid.get_cell_coords(50.0, 50.0), None);
+    assert_eq!(grid.get_cell_coords(100.0, 50.0), None);
+    // Just inside
+    assert_eq!(grid.get_cell_coords(99.99, 49.99), Some((9, 4)));
+    assert_eq!(grid.get_cell_coords(0.0, 0.0), Some((0, 0)));
+    // Far outside
+    assert_eq!(grid.get_cell_coords(150.0, 25.0), None);
+    assert_eq!(grid.get_cell_coords(50.0, 500.0), None);
+    assert_eq!(grid.get_cell_coords(-150.0, 25.0), None);
+    // Unclamped
+    assert_eq!(grid.get_cell_coords_unclamped(150.0, 25.0), (15, 2));
+    assert_eq!(grid.get_cell_coords_unclamped(-15.0, -5.0), (-2, -1));
+}
+
+#[test]
+fn iter_info() {
+    for centered in [false, true] {
+        let grid = Grid::<usize>::new(100.0, 50.0, 10, 5, centered);
+        let mut count = 0;
+        for info in grid.iter_info_in_rect(grid.left(), grid.bottom(), grid.right(), grid.top()) {
+            assert_eq!(info.right - info.left, grid.cell_width());
+            assert_eq!(info.top - info.bottom, grid.cell_height());
+            assert_eq!(
+                info.left,
+                grid.left() + info.col as Scalar * grid.cell_width()
+            );
+            assert_eq!(
+                info.bottom,
+                grid.bottom() + info.row as Scalar * grid.cell_height()
+            );
+            assert_eq!(
+                grid.get_cell_coords(info.center_x, info.center_y),
+                Some((info.col, info.row))
+            );
+            count += 1;
+        }
+        assert_eq!(count, 50);
+    }
+}
+
+#[test]
+fn iter_mut_in_rect() {
+    let mut grid = Grid::<usize>::new(100.0, 100.0, 10, 10, false);
+    for cell in grid.iter_cells_in_rect_mut(25.0, 35.0, 65.0, 55.0) {
+        *cell = 1;
+    }
+    for col in 0..10 {
+        for row in 0..10 {
+            let inside = (2..=6).contains(&col) && (3..=5).contains(&row);
+            let expected = if inside { 1 } else { 0 };
+            assert_eq!(*grid.get_cell_by_indices(col, row).unwrap(), expected);
+        }
+    }
+
+    for (cell, col, row) in grid
+        .iter_cells_in_rect_mut(0.0, 0.0, 100.0, 100.0)
+        .y_down()
+        .enumerate_coords()
+    {
+        *cell = (row * 10) + col;
+    }
+    for (i, cell) in grid.iter_cells_in_rect(0.0, 0.0, 100.0, 100.0).enumerate() {
+        assert_eq!(i, *cell);
+    }
+}
+
+#[test]
+fn enumerate_coords_directions() {
+    let mut grid = Grid::<(usize, usize)>::new(100.0, 50.0, 10, 5, false);
+    for col in 0..10 {
+        for row in 0..5 {
+            *grid.get_cell_by_indices_mut(col, row).unwrap() = (col, row);
+        }
+    }
+
+    let up = grid
+        .iter_cells_in_rect(15.0, 15.0, 75.0, 45.0)
+        .enumerate_coords();
+    let down = grid
+        .iter_cells_in_rect(15.0, 15.0, 75.0, 45.0)
+        .y_down()
+        .enumerate_coords();
+    let down_adapter = grid
+        .iter_cells_in_rect(15.0, 15.0, 75.0, 45.0)
+        .enumerate_coords()
+        .y_down();
+
+    for iter in [up, down, down_adapter] {
+        let mut count = 0;
+        for (value, col, row) in iter {
+            assert_eq!(grid.get_cell_by_indices(col, row), Some(value));
+            count += 1;
+        }
+        assert_eq!(count, 28);
+    }
+}
+
+#[test]
+fn iter_exact_size() {
+    let grid = Grid::<usize>::new(100.0, 100.0, 10, 10, false);
+    // 5 columns by 7 rows
+    let mut iter = grid.iter_cells_in_rect(25.0, 35.0, 65.0, 95.0);
+    assert_eq!(iter.len(), 35);
+    assert_eq!(iter.size_hint(), (35, Some(35)));
+    for n in (0..35).rev() {
+        iter.next();
+        assert_eq!(iter.len(), n);
+    }
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.len(), 0);
+
+    let mut iter = grid.iter_coords(25.0, 35.0, 65.0, 95.0).y_down();
+    assert_eq!(iter.len(), 35);
+    iter.nth(11);
+    assert_eq!(iter.len(), 23);
+    assert_eq!(iter.clone().count(), 23);
+    assert_eq!(iter.collect::<Vec<_>>().len(), 23);
+
+    let mut iter = grid
+        .iter_cells_in_rect(25.0, 35.0, 65.0, 95.0)
+        .y_down()
+        .enumerate_coords();
+    iter.next();
+    assert_eq!(iter.len(), 34);
+    assert_eq!(iter.count(), 34);
+
+    assert_eq!(grid.iter_all_cells().len(), 100);
+}
+
+#[test]
+fn iter_reversed() {
+    let mut grid = Grid::<usize>::new(100.0, 100.0, 10, 10, false);
+    for (i, cell) in grid
+        .iter_cells_in_rect_mut(0.0, 0.0, 100.0, 100.0)
+        .enumerate()
+    {
+        *cell = i;
+    }
+
+    // Regular, single row, single column and single cell rects.
+    let rects = [
+        (25.0, 35.0, 65.0, 95.0),
+        (25.0, 35.0, 65.0, 35.0),
+        (25.0, 35.0, 25.0, 95.0),
+        (25.0, 35.0, 25.0, 35.0),
+    ];
+    for (left, bottom, right, top) in rects {
+        let mut forward: Vec<_> = grid.iter_coords(left, bottom, right, top).collect();
+        forward.reverse();
+        let backward: Vec<_> = grid.iter_coords(left, bottom, right, top).rev().collect();
+        assert_eq!(forward, backward);
+
+        let mut forward: Vec<_> = grid
+            .iter_coords(left, bottom, right, top)
+            .y_down()
+            .collect();
+        forward.reverse();
+        let backward: Vec<_> = grid
+            .iter_coords(left, bottom, right, top)
+            .y_down()
+            .rev()
+            .collect();
+        assert_eq!(forward, backward);
+
+        let mut forward: Vec<_> = grid.iter_cells_in_rect(left, bottom, right, top).collect();
+        forward.reverse();
+        let backward: Vec<_> = grid
+            .iter_cells_in_rect(left, bottom, right, top)
+            .rev()
+            .collect();
+        assert_eq!(forward, backward);
+    }
+
+    // Both ends meeting in the middle
+    let mut iter = grid.iter_coords(25.0, 35.0, 65.0, 45.0);
+    let mut front = Vec::new();
+    let mut back = Vec::new();
+    for n in (0..10).rev() {
+        if n % 2 == 0 {
+            front.push(iter.next().unwrap());
+        } else {
+            back.push(iter.next_back().unwrap());
+        }
+        assert_eq!(iter.len(), n);
+    }
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+    back.reverse();
+    front.extend(back);
+    assert_eq!(
+        front,
+        grid.iter_coords(25.0, 35.0, 65.0, 45.0).collect::<Vec<_>>()
+    );
+
+    assert_eq!(
+        grid.iter_all_cells().rposition(|cell| *cell == 90),
+        Some(90)
+    );
+}
+
+#[test]
+fn iter_fused() {
+    let grid = Grid::<usize>::new(100.0, 100.0, 10, 10, false);
+    for y_down in [false, true] {
+        let mut cells = grid.iter_cells_in_rect(25.0, 35.0, 45.0, 55.0);
+        let mut coords = grid.iter_coords(25.0, 35.0, 45.0, 55.0);
+        let mut with_coords = grid
+            .iter_cells_in_rect(25.0, 35.0, 45.0, 55.0)
+            .enumerate_coords();
+        if y_down {
+            cells = cells.y_down();
+            coords = coords.y_down();
+            with_coords = with_coords.y_down();
+        }
+        assert_eq!(cells.by_ref().count(), 9);
+        assert_eq!(coords.by_ref().count(), 9);
+        assert_eq!(with_coords.by_ref().count(), 9);
+        for _ in 0..5 {
+            assert!(cells.next().is_none());
+            assert!(cells.next_back().is_none());
+            assert!(coords.next().is_none());
+            assert!(coords.next_back().is_none());
+            assert!(with_coords.next().is_none());
+        }
+    }
+}
+
+#[test]
+fn index_by_tuple() {
+    let mut grid = Grid::<usize>::new(100.0, 50.0, 10, 5, false);
+    grid[(3, 4)] = 7;
+    grid[(9, 0)] += 2;
+    assert_eq!(grid[(3, 4)], 7);
+    assert_eq!(grid[(9, 0)], 2);
+    assert_eq!(grid.get_cell(35.0, 45.0), Some(&7));
+}
+
+#[test]
+#[should_panic(expected = "Cell (3, 5) is out of range for a 10x5 grid")]
+fn index_out_of_range() {
+    let grid = Grid::<usize>::new(100.0, 50.0, 10, 5, false);
+    let _ = grid[(3, 5)];
+}
+
+#[test]
+fn set_cells() {
+    let mut grid = Grid::<usize>::new(100.0, 50.0, 10, 5, true);
+    assert_eq!(grid.set_cell(-45.0, 20.0, 3), Ok(0));
+    assert_eq!(grid.set_cell(-45.0, 20.0, 4), Ok(3));
+    assert_eq!(grid[(0, 4)], 4);
+
+    assert_eq!(grid.set_cell_by_indices(9, 0, 5), Ok(0));
+    assert_eq!(grid.set_cell_by_indices(9, 0, 6), Ok(5));
+    assert_eq!(grid[(9, 0)], 6);
+
+    // Out of bounds writes give the value back and leave the grid untouched.
+    assert_eq!(grid.set_cell(60.0, 0.0, 7), Err(7));
+    assert_eq!(grid.set_cell(0.0, -30.0, 8), Err(8));
+    assert_eq!(grid.set_cell_by_indices(10, 0, 9), Err(9));
+    assert_eq!(grid.set_cell_by_indices(0, 5, 10), Err(10));
+    assert_eq!(grid.iter_all_cells().sum::<usize>(), 10);
+}
+
+#[test]
+fn swap_cells() {
+    let mut grid = Grid::<usize>::new(100.0, 50.0, 10, 5, false);
+    grid[(2, 1)] = 1;
+    grid[(2, 3)] = 2;
+    grid[(7, 4)] = 3;
+
+    // Within a column
+    assert_eq!(grid.swap_cells(2, 1, 2, 3), Ok(()));
+    assert_eq!((grid[(2, 1)], grid[(2, 3)]), (2, 1));
+
+    // Across columns, in both directions
+    assert_eq!(grid.swap_cells(2, 1, 7, 4), Ok(()));
+    assert_eq!((grid[(2, 1)], grid[(7, 4)]), (3, 2));
+    assert_eq!(grid.swap_cells(7, 4, 2, 1), Ok(()));
+    assert_eq!((grid[(2, 1)], grid[(7, 4)]), (2, 3));
+
+    // Same cell
+    assert_eq!(grid.swap_cells(7, 4, 7, 4), Ok(()));
+    assert_eq!(grid[(7, 4)], 3);
+
+    // Out of range
+    assert_eq!(
+        grid.swap_cells(2, 1, 10, 0),
+        Err(GridError::IndicesOutOfRange { col: 10, row: 0 })
+    );
+    assert_eq!(
+        grid.swap_cells(2, 5, 0, 0),
+        Err(GridError::IndicesOutOfRange { col: 2, row: 5 })
+    );
+    assert_eq!(grid.iter_all_cells().sum::<usize>(), 6);
+}
+
+#[test]
+fn take_and_replace_cells() {
+    let mut grid = Grid::<Option<usize>>::new(100.0, 50.0, 10, 5, false);
+    assert_eq!(grid.replace_cell_by_indices(3, 2, Some(1)), Some(None));
+    assert_eq!(grid.replace_cell_by_indices(3, 2, Some(2)), Some(Some(1)));
+    assert_eq!(grid.take_cell(35.0, 25.0), Some(Some(2)));
+    assert_eq!(grid[(3, 2)], None);
+
+    grid[(9, 4)] = Some(3);
+    assert_eq!(grid.take_cell_by_indices(9, 4), Some(Some(3)));
+    assert_eq!(grid.take_cell_by_indices(9, 4), Some(None));
+
+    // Misses
+    assert_eq!(grid.take_cell(100.0, 25.0), None);
+    assert_eq!(grid.take_cell_by_indices(10, 0), None);
+    assert_eq!(grid.replace_cell_by_indices(0, 5, Some(4)), None);
+    assert!(grid.iter_all_cells().all(|cell| cell.is_none()));
+}
+
+#[test]
+fn fill_and_clear() {
+    let mut grid = Grid::<Vec<usize>>::new(100.0, 50.0, 10, 5, false);
+    grid.fill(vec![1, 2]);
+    assert_eq!(grid.iter_all_cells().len(), 50);
+    assert!(grid.iter_all_cells().all(|cell| *cell == vec![1, 2]));
+
+    grid.clear();
+    assert!(grid.iter_all_cells().all(|cell| cell.is_empty()));
+}
+
+#[test]
+fn fill_rect() {
+    let mut grid = Grid::<usize>::new(100.0, 50.0, 10, 5, true);
+    // Partially overlapping the bottom-left corner: columns 0 and 1, rows 0 and 1.
+    grid.fill_rect(-80.0, -40.0, -35.0, -10.0, 1);
+    // Partially overlapping the top-right corner: columns 8 and 9, row 4.
+    grid.fill_rect(35.0, 20.0, 80.0, 40.0, 2);
+    // Entirely outside
+    grid.fill_rect(60.0, -10.0, 80.0, 10.0, 3);
+    grid.fill_rect(-10.0, -80.0, 10.0, -30.0, 3);
+
+    for (value, col, row) in grid.iter_all_cells().enumerate_coords() {
+        let expected = if col < 2 && row < 2 {
+            1
+        } else if col > 7 && row == 4 {
+            2
+        } else {
+            0
+        };
+        assert_eq!(*value, expected);
+    }
+    assert_eq!(grid.iter_coords(60.0, -10.0, 80.0, 10.0).count(), 0);
+    assert_eq!(grid.iter_cells_in_rect(-10.0, 30.0, 10.0, 40.0).len(), 0);
+}
+
+#[test]
+fn map_grid() {
+    let mut grid = Grid::<usize>::new(100.0, 50.0, 10, 5, true);
+    for (value, col, row) in grid
+        .iter_cells_in_rect_mut(-50.0, -25.0, 50.0, 25.0)
+        .enumerate_coords()
+    {
+        *value = (row * 10) + col;
+    }
+
+    let doubled = grid.map(|value| *value as f32 * 2.0);
+    let mut visited = Vec::new();
+    let coords = grid.map_with_coords(|col, row, value| {
+        visited.push((col, row));
+        (col, row, *value)
+    });
+    let moved = grid.map(|value| *value).into_map(|value| value + 1);
+
+    for other in [doubled.width(), coords.width(), moved.width()] {
+        assert_eq!(other, grid.width());
+    }
+    assert_eq!(coords.height(), grid.height());
+    assert_eq!(coords.columns(), grid.columns());
+    assert_eq!(coords.rows(), grid.rows());
+    assert_eq!(coords.cell_width(), grid.cell_width());
+    assert_eq!(coords.cell_height(), grid.cell_height());
+    assert_eq!(coords.offset_x(), grid.offset_x());
+    assert_eq!(moved.offset_y(), grid.offset_y());
+
+    // Closure coordinates follow the iteration order: row by row, from the bottom row up.
+    let iter_order: Vec<_> = grid
+        .iter_all_cells()
+        .enumerate_coords()
+        .map(|(_, col, row)| (col, row))
+        .collect();
+    assert_eq!(visited, iter_order);
+    assert_eq!(visited[..2], [(0, 0), (1, 0)]);
+
+    for (value, col, row) in grid.iter_all_cells().enumerate_coords() {
+        assert_eq!(doubled[(col, row)], *value as f32 * 2.0);
+        assert_eq!(coords[(col, row)], (col, row, *value));
+        assert_eq!(moved[(col, row)], *value + 1);
+    }
+}
+
+#[test]
+fn zip_grids() {
+    let mut height = Grid::<f32>::new(100.0, 50.0, 10, 5, false);
+    let moisture = Grid::<f32>::new_with(200.0, 100.0, 10, 5, false, || 0.5);
+    for (value, col, row) in height
+        .iter_cells_in_rect_mut(0.0, 0.0, 100.0, 50.0)
+        .enumerate_coords()
+    {
+        *value = (col + row) as f32;
+    }
+
+    let biome = height
+        .zip_with(&moisture, |h, m| if *h > 5.0 && *m > 0.2 { 1 } else { 0 })
+        .unwrap();
+    assert_eq!(biome.width(), 100.0);
+    assert_eq!(biome[(0, 0)], 0);
+    assert_eq!(biome[(9, 4)], 1);
+
+    height.zip_modify(&moisture, |h, m| *h *= m).unwrap();
+    assert_eq!(height[(9, 4)], 6.5);
+
+    let other = Grid::<f32>::new(100.0, 50.0, 5, 10, false);
+    let expected = GridError::ShapeMismatch {
+        expected: (10, 5),
+        found: (5, 10),
+    };
+    assert_eq!(height.zip_with(&other, |a, b| a + b).err(), Some(expected));
+    assert_eq!(height.zip_modify(&other, |a, b| *a += b), Err(expected));
+}
+
+#[test]
+fn new_with_coords() {
+    let grid = Grid::new_with_coords(100.0, 50.0, 10, 5, true, |col, row| (col, row));
+    for (value, col, row) in grid.iter_all_cells().enumerate_coords() {
+        assert_eq!(*value, (col, row));
+    }
+    assert_eq!(grid.get_cell(-45.0, 20.0), Some(&(0, 4)));
+    assert_eq!(grid.get_cell(45.0, -20.0), Some(&(9, 0)));
+
+    let checkers = Grid::new_with_coords(8.0, 8.0, 8, 8, false, |col, row| (col + row) % 2 == 0);
+    assert!(checkers[(0, 0)]);
+    assert!(!checkers[(1, 0)]);
+    assert!(checkers[(7, 7)]);
+}
+
+#[test]
+fn new_cloned() {
+    let grid = Grid::new_cloned(100.0, 50.0, 10, 5, false, vec![1, 2, 3]);
+    assert_eq!(grid.iter_all_cells().len(), 50);
+    assert!(grid.iter_all_cells().all(|cell| *cell == vec![1, 2, 3]));
+    assert_eq!(grid.cell_width(), 10.0);
+}
+
+#[test]
+fn builder() {
+    let grid = GridBuilder::new()
+        .size(100.0, 50.0)
+        .cells(10, 5)
+        .pivot(0.5, 0.5)
+        .build_with(|| 1usize)
+        .unwrap();
+    assert_eq!(grid.left(), -50.0);
+    assert_eq!(grid.right(), 50.0);
+    assert_eq!(grid.bottom(), -25.0);
+    assert_eq!(grid.top(), 25.0);
+    assert_eq!(grid.get_cell_coords(-45.0, 20.0), Some((0, 4)));
+    assert_eq!(grid.iter_all_cells().sum::<usize>(), 50);
+
+    let grid = GridBuilder::new()
+        .size(100.0, 50.0)
+        .cells(10, 5)
+        .pivot(0.5, 0.5)
+        .position(200.0, -100.0)
+        .build_default::<u8>()
+        .unwrap();
+    assert_eq!((grid.x(), grid.y()), (200.0, -100.0));
+    assert_eq!((grid.left(), grid.bottom()), (150.0, -125.0));
+    assert_eq!(grid.get_cell_coords(155.0, -80.0), Some((0, 4)));
+
+    let builder = GridBuilder::new().size(100.0, 50.0).cells(10, 5);
+    assert!(builder.build_default::<u8>().is_ok());
+    assert_eq!(
+        builder.cells(0, 5).build_default::<u8>().err(),
+        Some(GridError::ZeroColumns)
+    );
+    assert_eq!(
+        builder.cells(10, 0).build_default::<u8>().err(),
+        Some(GridError::ZeroRows)
+    );
+    assert_eq!(
+        builder.size(-1.0, 50.0).build_default::<u8>().err(),
+        Some(GridError::NonPositiveSize {
+            width: -1.0,
+            height: 50.0
+        })
+    );
+    assert_eq!(
+        builder.size(Scalar::NAN, 50.0).build_default::<u8>().err(),
+        Some(GridError::NonFiniteSize)
+    );
+    assert_eq!(
+        builder
+            .position(0.0, Scalar::INFINITY)
+            .build_default::<u8>()
+            .err(),
+        Some(GridError::NonFiniteValue { field: "y" })
+    );
+    assert_eq!(
+        builder.pivot(Scalar::NAN, 0.5).build_default::<u8>().err(),
+        Some(GridError::NonFiniteValue { field: "pivot_x" })
+    );
+}
+
+#[test]
+fn try_new() {
+    assert!(Grid::<u8>::try_new(100.0, 50.0, 10, 5, true).is_ok());
+    assert_eq!(
+        Grid::<u8>::try_new(100.0, 50.0, 0, 5, true).err(),
+        Some(GridError::ZeroColumns)
+    );
+    assert_eq!(
+        Grid::<u8>::try_new(100.0, 50.0, 10, 0, true).err(),
+        Some(GridError::ZeroRows)
+    );
+    assert_eq!(
+        Grid::<u8>::try_new(0.0, 50.0, 10, 5, true).err(),
+        Some(GridError::NonPositiveSize {
+            width: 0.0,
+            height: 50.0
+        })
+    );
+    assert_eq!(
+        Grid::<u8>::try_new(100.0, Scalar::INFINITY, 10, 5, true).err(),
+        Some(GridError::NonFiniteSize)
+    );
+    assert_eq!(
+        Grid::<u8>::try_new_with(Scalar::NAN, 50.0, 10, 5, true, || 0).err(),
+        Some(GridError::NonFiniteSize)
+    );
+}
+
+#[test]
+#[should_panic(expected = "a grid needs at least one row")]
+fn new_panics_on_invalid_size() {
+    let _ = Grid::<u8>::new(100.0, 50.0, 10, 0, false);
+}
+
+#[test]
+fn try_lookups() {
+    let mut grid = Grid::<usize>::new(100.0, 50.0, 10, 5, true);
+    grid[(0, 4)] = 1;
+    assert_eq!(grid.try_get_cell(-45.0, 20.0), Ok(&1));
+    assert_eq!(
+        grid.try_get_cell(60.0, 20.0),
+        Err(GridError::PointOutOfBounds { x: 60.0, y: 20.0 })
+    );
+    assert_eq!(grid.try_get_cell_by_indices(0, 4), Ok(&1));
+    assert_eq!(
+        grid.try_get_cell_by_indices(0, 5),
+        Err(GridError::IndicesOutOfRange { col: 0, row: 5 })
+    );
+    assert_eq!(
+        grid.try_modify_in_rect(-45.0, 15.0, -41.0, 20.0, |cell| *cell += 1),
+        Ok(())
+    );
+    assert_eq!(grid[(0, 4)], 2);
+    assert_eq!(
+        grid.try_modify_in_rect(60.0, 0.0, 70.0, 10.0, |cell| *cell += 1),
+        Err(GridError::RectOutOfBounds {
+            left: 60.0,
+            bottom: 0.0,
+            right: 70.0,
+            top: 10.0
+        })
+    );
+    assert_eq!(grid.iter_all_cells().sum::<usize>(), 2);
+}
+
+#[test]
+fn grid_equality() {
+    let grid = Grid::new_with_coords(100.0, 50.0, 10, 5, false, |col, row| col * row);
+    let clone = grid.clone();
+    assert_eq!(grid, clone);
+
+    let centered = Grid::new_with_coords(100.0, 50.0, 10, 5, true, |col, row| col * row);
+    assert_ne!(grid, centered);
+    assert!(grid.same_shape(&centered));
+
+    let mut changed = grid.clone();
+    changed[(3, 3)] = 0;
+    assert_ne!(grid, changed);
+    assert!(grid.same_shape(&changed));
+
+    let resized = Grid::new_with_coords(200.0, 50.0, 10, 5, false, |col, row| col * row);
+    assert_ne!(grid, resized);
+    assert!(grid.same_shape(&resized));
+    assert!(!grid.same_shape(&Grid::<f32>::new(100.0, 50.0, 5, 10, false)));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip() {
+    for centered in [false, true] {
+        let mut grid = Grid::new_with_coords(100.0, 50.0, 4, 3, centered, |col, row| {
+            (row * 4 + col) as u32
+        });
+        grid.set_position(10.0, -20.0);
+        let json = serde_json::to_string(&grid).unwrap();
+        // Cells are listed row by row.
+        assert!(json.contains("\"cells\":[0,1,2,3,4,5,6,7,8,9,10,11]"));
+        let loaded: Grid<u32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, grid);
+    }
+
+    // The position is required
+    let bad = r#"{"width":100.0,"height":50.0,"columns":2,"rows":1,
+        "offset_x":0.0,"offset_y":0.0,"cells":[0,1]}"#;
+    assert!(serde_json::from_str::<Grid<u32>>(bad).is_err());
+
+    let bad = r#"{"width":100.0,"height":50.0,"columns":4,"rows":3,
+        "offset_x":0.0,"offset_y":0.0,"x":0.0,"y":0.0,"cells":[0,1,2]}"#;
+    assert!(serde_json::from_str::<Grid<u32>>(bad).is_err());
+    let bad = r#"{"width":100.0,"height":50.0,"columns":0,"rows":3,
+        "offset_x":0.0,"offset_y":0.0,"x":0.0,"y":0.0,"cells":[]}"#;
+    assert!(serde_json::from_str::<Grid<u32>>(bad).is_err());
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn bytes_round_trip() {
+    for centered in [false, true] {
+        let mut grid = Grid::new_with_coords(100.0, 50.0, 4, 3, centered, |col, row| {
+            [col as u16, row as u16]
+        });
+        grid.set_position(10.0, -20.0);
+        let mut bytes = Vec::new();
+        grid.to_bytes(&mut bytes);
+        assert_eq!(&bytes[..4], b"GRDS");
+        assert_eq!(Grid::<[u16; 2]>::from_bytes(&bytes), Ok(grid.clone()));
+        // Cells are little-endian, row by row
+        assert_eq!(&bytes[bytes.len() - 8..], &[2, 0, 2, 0, 3, 0, 2, 0]);
+    }
+
+    let grid = Grid::new_with(100.0, 50.0, 4, 3, false, || 7u32);
+    let mut bytes = Vec::new();
+    grid.to_bytes(&mut bytes);
+    assert_eq!(&bytes[bytes.len() - 4..], &[7, 0, 0, 0]);
+    let floats = Grid::new_with(100.0, 50.0, 2, 1, false, || 1.5f32);
+    let mut float_bytes = Vec::new();
+    floats.to_bytes(&mut float_bytes);
+    assert_eq!(&float_bytes[float_bytes.len() - 4..], &1.5f32.to_le_bytes());
+    assert_eq!(Grid::<f32>::from_bytes(&float_bytes), Ok(floats));
+
+    assert_eq!(
+        Grid::<u32>::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(GridError::TruncatedData)
+    );
+    assert_eq!(
+        Grid::<u32>::from_bytes(&bytes[..10]),
+        Err(GridError::TruncatedData)
+    );
+    assert_eq!(
+        Grid::<u16>::from_bytes(&bytes),
+        Err(GridError::InvalidHeader)
+    );
+    let mut corrupt = bytes.clone();
+    corrupt[50..58].copy_from_slice(&f64::NAN.to_le_bytes());
+    assert_eq!(
+        Grid::<u32>::from_bytes(&corrupt),
+        Err(GridError::NonFiniteValue { field: "x" })
+    );
+    let mut corrupt = bytes.clone();
+    corrupt[4] = 2;
+    assert_eq!(
+        Grid::<u32>::from_bytes(&corrupt),
+        Err(GridError::InvalidHeader)
+    );
+    bytes[0] = b'X';
+    assert_eq!(
+        Grid::<u32>::from_bytes(&bytes),
+        Err(GridError::InvalidHeader)
+    );
+}
+
+#[test]
+fn render_ascii() {
+    let mut grid = Grid::<bool>::new(30.0, 20.0, 3, 2, false);
+    grid[(0, 1)] = true;
+    grid[(2, 0)] = true;
+    let render = |cell: &bool| if *cell { '#' } else { '.' };
+    assert_eq!(grid.render_ascii(false, render), "#..\n..#\n");
+    assert_eq!(grid.render_ascii(true, render), "1 #..\n0 ..#\n  012\n");
+}
+
+#[test]
+fn iter_all_mut() {
+    let mut grid = Grid::<usize>::new(100.0, 50.0, 10, 5, false);
+    for (i, cell) in grid.iter_all_cells_mut().enumerate() {
+        *cell = i;
+    }
+    for (value, col, row) in grid.iter_all_with_coords() {
+        assert_eq!(*value, (row * 10) + col);
+        assert_eq!(grid.get_cell_by_indices(col, row), Some(value));
+    }
+
+    for (value, col, row) in grid.iter_all_with_coords_mut() {
+        *value = col * row;
+    }
+    for col in 0..10 {
+        for row in 0..5 {
+            assert_eq!(grid.get_cell_by_indices(col, row), Some(&(col * row)));
+        }
+    }
+    assert_eq!(grid.iter_all_cells_mut().len(), 50);
+}
+
+#[test]
+fn iter_rows_and_columns() {
+    let mut grid = Grid::new_with_coords(100.0, 50.0, 10, 5, false, |col, row| (col, row));
+    let row: Vec<_> = grid.iter_row(3).collect();
+    assert_eq!(row.len(), grid.columns());
+    for (col, value) in row.into_iter().enumerate() {
+        assert_eq!(*value, (col, 3));
+    }
+    let column: Vec<_> = grid.iter_column(7).collect();
+    assert_eq!(column.len(), grid.rows());
+    for (row, value) in column.into_iter().enumerate() {
+        assert_eq!(*value, (7, row));
+    }
+
+    for value in grid.iter_row_mut(0) {
+        value.1 = 100;
+    }
+    for value in grid.iter_column_mut(0) {
+        value.0 = 100;
+    }
+    assert_eq!(grid[(0, 0)], (100, 100));
+    assert_eq!(grid[(5, 0)], (5, 100));
+    assert_eq!(grid[(0, 4)], (100, 4));
+    assert_eq!(grid[(5, 4)], (5, 4));
+
+    assert_eq!(grid.iter_row(5).count(), 0);
+    assert_eq!(grid.iter_row_mut(5).count(), 0);
+    assert_eq!(grid.iter_column(10).count(), 0);
+    assert_eq!(grid.iter_column_mut(10).count(), 0);
+}
+
+#[test]
+fn into_iterator() {
+    let mut grid = Grid::new_with_coords(100.0, 50.0, 10, 5, false, |col, row| (row * 10) + col);
+    let mut count = 0;
+    for (i, cell) in (&grid).into_iter().enumerate() {
+        assert_eq!(*cell, i);
+        count += 1;
+    }
+    assert_eq!(count, 50);
+
+    for cell in &mut grid {
+        *cell *= 2;
+    }
+    let mut expected = 0;
+    for cell in &grid {
+        assert_eq!(*cell, expected);
+        expected += 2;
+    }
+
+    let iter = grid.into_iter();
+    assert_eq!(iter.len(), 50);
+    let cells: Vec<usize> = iter.collect();
+    assert_eq!(cells, (0..50).map(|i| i * 2).collect::<Vec<_>>());
+}
+
+#[test]
+fn flat_vec_round_trip() {
+    let grid = Grid::new_with_coords(100.0, 50.0, 4, 3, true, |col, row| (col, row));
+    let flat = grid.to_flat_vec();
+    // Row by row, starting at the bottom row.
+    assert_eq!(&flat[..5], &[(0, 0), (1, 0), (2, 0), (3, 0), (0, 1)]);
+    assert_eq!(flat[11], (3, 2));
+    assert_eq!(grid.clone().into_flat_vec(), flat);
+
+    let loaded = Grid::from_flat_vec(100.0, 50.0, 4, 3, true, flat.clone()).unwrap();
+    assert_eq!(loaded, grid);
+
+    assert_eq!(
+        Grid::from_flat_vec(100.0, 50.0, 3, 3, true, flat.clone()).err(),
+        Some(GridError::CellCountMismatch {
+            expected: 9,
+            found: 12
+        })
+    );
+    assert_eq!(
+        Grid::from_flat_vec(100.0, 50.0, 0, 3, true, flat).err(),
+        Some(GridError::ZeroColumns)
+    );
+}
+
+#[test]
+fn unchecked_access() {
+    let mut grid = Grid::new_with_coords(100.0, 50.0, 10, 5, false, |col, row| (col, row));
+    for col in 0..10 {
+        for row in 0..5 {
+            // SAFETY: Indices are within the grid's columns and rows.
+            unsafe {
+                assert_eq!(*grid.get_cell_by_indices_unchecked(col, row), (col, row));
+                grid.get_cell_by_indices_unchecked_mut(col, row).0 += 1;
+            }
+        }
+    }
+    assert_eq!(grid[(9, 4)], (10, 4));
+    assert_eq!(grid[(0, 0)], (1, 0));
+}
+
+#[test]
+fn disjoint_cells_mut() {
+    let mut grid = Grid::new_with_coords(100.0, 50.0, 10, 5, false, |col, row| vec![(col, row)]);
+    let (a, b) = grid.get_pair_mut((1, 2), (8, 4)).unwrap();
+    let item = a.pop().unwrap();
+    b.push(item);
+    assert!(grid[(1, 2)].is_empty());
+    assert_eq!(grid[(8, 4)], vec![(8, 4), (1, 2)]);
+
+    let [a, b, c] = grid
+        .get_disjoint_cells_mut([(0, 0), (0, 1), (9, 0)])
+        .unwrap();
+    a.clear();
+    b.clear();
+    c.clear();
+    assert!(grid[(0, 0)].is_empty() && grid[(0, 1)].is_empty() && grid[(9, 0)].is_empty());
+
+    assert!(grid.get_pair_mut((3, 3), (3, 3)).is_none());
+    assert!(grid
+        .get_disjoint_cells_mut([(0, 0), (1, 1), (0, 0)])
+        .is_none());
+    assert!(grid.get_pair_mut((3, 3), (10, 3)).is_none());
+    assert!(grid.get_pair_mut((3, 5), (1, 1)).is_none());
+}
+
+#[test]
+fn neighbors() {
+    let mut grid = Grid::new_with_coords(100.0, 50.0, 10, 5, false, |col, row| (col, row));
+    let coords = |grid: &Grid<(usize, usize)>, col, row, connectivity| {
+        let mut list: Vec<_> = grid.neighbor_coords(col, row, connectivity).collect();
+        list.sort();
+        list
+    };
+
+    // Corner
+    assert_eq!(
+        coords(&grid, 0, 0, Connectivity::Four),
+        vec![(0, 1), (1, 0)]
+    );
+    assert_eq!(
+        coords(&grid, 0, 0, Connectivity::Eight),
+        vec![(0, 1), (1, 0), (1, 1)]
+    );
+    // Edge
+    assert_eq!(
+        coords(&grid, 9, 2, Connectivity::Four),
+        vec![(8, 2), (9, 1), (9, 3)]
+    );
+    assert_eq!(
+        coords(&grid, 9, 2, Connectivity::Eight),
+        vec![(8, 1), (8, 2), (8, 3), (9, 1), (9, 3)]
+    );
+    // Interior
+    assert_eq!(
+        coords(&grid, 4, 2, Connectivity::Four),
+        vec![(3, 2), (4, 1), (4, 3), (5, 2)]
+    );
+    assert_eq!(
+        coords(&grid, 4, 2, Connectivity::Eight),
+        vec![
+            (3, 1),
+            (3, 2),
+            (3, 3),
+            (4, 1),
+            (4, 3),
+            (5, 1),
+            (5, 2),
+            (5, 3)
+        ]
+    );
+    // Out of range
+    assert_eq!(grid.neighbor_coords(10, 2, Connectivity::Eight).count(), 0);
+
+    for ((col, row), value) in grid.iter_neighbors(4, 2, Connectivity::Eight) {
+        assert_eq!(*value, (col, row));
+    }
+    for (_, value) in grid.iter_neighbors_mut(0, 4, Connectivity::Eight) {
+        *value = (100, 100);
+    }
+    assert_eq!(grid[(1, 4)], (100, 100));
+    assert_eq!(grid[(0, 3)], (100, 100));
+    assert_eq!(grid[(1, 3)], (100, 100));
+    assert_eq!(grid[(0, 4)], (0, 4));
+}
+
+#[test]
+fn circle() {
+    let grid = Grid::new_with_coords(100.0, 50.0, 10, 5, true, |col, row| (col, row));
+    // Brute force comparison against all cells in the bounding rectangle
+    let brute_force = |x: Scalar, y: Scalar, radius: Scalar| -> Vec<(usize, usize)> {
+        grid.iter_coords(x - radius, y - radius, x + radius, y + radius)
+            .filter(|&(col, row)| {
+                let left = (col as Scalar * grid.cell_width()) - grid.offset_x();
+                let bottom = (row as Scalar * grid.cell_height()) - grid.offset_y();
+                let closest_x = x.clamp(left, left + grid.cell_width());
+                let closest_y = y.clamp(bottom, bottom + grid.cell_height());
+                let (dx, dy) = (x - closest_x, y - closest_y);
+                (dx * dx) + (dy * dy) <= radius * radius
+            })
+            .collect()
+    };
+    for (x, y, radius) in [
+        (0.0, 0.0, 2.0),
+        (3.0, 3.0, 4.0),
+        (-22.0, 7.0, 12.5),
+        (25.0, -20.0, 18.0),
+        (0.0, 0.0, 200.0),
+    ] {
+        let coords: Vec<_> = grid.iter_coords_in_circle(x, y, radius).collect();
+        assert_eq!(coords, brute_force(x, y, radius));
+        let cells: Vec<_> = grid.iter_cells_in_circle(x, y, radius).copied().collect();
+        assert_eq!(cells, coords);
+    }
+    // Smaller than one cell, inside a single cell
+    let coords: Vec<_> = grid.iter_coords_in_circle(5.0, 2.0, 1.0).collect();
+    assert_eq!(coords, [(5, 2)]);
+    // Covers the whole grid
+    assert_eq!(grid.iter_coords_in_circle(0.0, 0.0, 200.0).count(), 50);
+    // A corner cell is left out when only its bounding rect overlaps
+    let coords: Vec<_> = grid.iter_coords_in_circle(-50.0, -25.0, 14.0).collect();
+    assert_eq!(coords, [(0, 0), (1, 0), (0, 1)]);
+    // Outside the grid, or negative radius
+    assert_eq!(grid.iter_coords_in_circle(100.0, 0.0, 10.0).count(), 0);
+    assert_eq!(grid.iter_coords_in_circle(0.0, 0.0, -1.0).count(), 0);
+}
+
+#[test]
+fn segment() {
+    let grid = Grid::new_with_coords(100.0, 50.0, 10, 5, false, |col, row| (col, row));
+    let coords = |x0, y0, x1, y1, supercover| -> Vec<(usize, usize)> {
+        grid.iter_coords_on_segment(x0, y0, x1, y1, supercover)
+            .collect()
+    };
+    // Horizontal, both directions
+    assert_eq!(
+        coords(5.0, 15.0, 45.0, 15.0, false),
+        [(0, 1), (1, 1), (2, 1), (3, 1), (4, 1)]
+    );
+    assert_eq!(
+        coords(45.0, 15.0, 5.0, 15.0, false),
+        [(4, 1), (3, 1), (2, 1), (1, 1), (0, 1)]
+    );
+    // Vertical
+    assert_eq!(
+        coords(25.0, 5.0, 25.0, 45.0, false),
+        [(2, 0), (2, 1), (2, 2), (2, 3), (2, 4)]
+    );
+    // Diagonal through cell corners
+    assert_eq!(
+        coords(5.0, 5.0, 35.0, 35.0, false),
+        [(0, 0), (1, 1), (2, 2), (3, 3)]
+    );
+    assert_eq!(
+        coords(5.0, 5.0, 35.0, 35.0, true),
+        [
+            (0, 0),
+            (1, 0),
+            (0, 1),
+            (1, 1),
+            (2, 1),
+            (1, 2),
+            (2, 2),
+            (3, 2),
+            (2, 3),
+            (3, 3)
+        ]
+    );
+    // Steep
+    assert_eq!(
+        coords(5.0, 5.0, 15.0, 45.0, false),
+        [(0, 0), (0, 1), (0, 2), (1, 2), (1, 3), (1, 4)]
+    );
+    // Single point
+    assert_eq!(coords(55.0, 25.0, 55.0, 25.0, false), [(5, 2)]);
+    // Clipped to the grid
+    let clipped = coords(-50.0, 15.0, 150.0, 15.0, false);
+    assert_eq!(clipped.len(), 10);
+    assert_eq!(clipped.first(), Some(&(0, 1)));
+    assert_eq!(clipped.last(), Some(&(9, 1)));
+    assert_eq!(coords(95.0, 45.0, 200.0, 100.0, false), [(9, 4)]);
+    // Entirely outside
+    assert!(coords(-50.0, -10.0, 150.0, -10.0, false).is_empty());
+    assert!(coords(110.0, 0.0, 120.0, 40.0, true).is_empty());
+    assert!(coords(100.0, 0.0, 100.0, 40.0, false).is_empty());
+
+    // Values, with the pivot applied
+    let grid = Grid::new_with_coords(100.0, 50.0, 10, 5, true, |col, row| (col, row));
+    for ((col, row), value) in grid.iter_cells_on_segment(-45.0, -20.0, 45.0, 20.0, true) {
+        assert_eq!(*value, (col, row));
+    }
+    let cells: Vec<_> = grid
+        .iter_cells_on_segment(-45.0, -20.0, 45.0, -20.0, false)
+        .map(|(_, value)| *value)
+        .collect();
+    assert_eq!(cells.len(), 10);
+    assert!(cells.iter().all(|&(_, row)| row == 0));
+
+    // Non-finite end points find nothing
+    let nan = Scalar::NAN;
+    let inf = Scalar::INFINITY;
+    assert_eq!(
+        grid.iter_coords_on_segment(nan, 0.0, 10.0, 0.0, false)
+            .count(),
+        0
+    );
+    assert_eq!(
+        grid.iter_coords_on_segment(0.0, 0.0, 10.0, nan, true)
+            .count(),
+        0
+    );
+    assert_eq!(
+        grid.iter_coords_on_segment(nan, nan, nan, nan, false)
+            .count(),
+        0
+    );
+    assert_eq!(
+        grid.iter_coords_on_segment(-inf, 0.0, 0.0, 0.0, false)
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn raycast() {
+    let grid = Grid::new_with_coords(100.0, 50.0, 10, 5, true, |col, row| (col, row));
+    let approx = |a: Scalar, b: Scalar| (a - b).abs() < 0.001;
+
+    // Starts inside, stops at the first "solid" cell
+    let hits: Vec<_> = grid.raycast(-45.0, -15.0, 1.0, 0.0, 1000.0).collect();
+    assert_eq!(hits.len(), 10);
+    assert!(approx(hits[0].t_enter, 0.0) && approx(hits[0].t_exit, 5.0));
+    assert!(approx(hits[1].t_enter, 5.0) && approx(hits[1].t_exit, 15.0));
+    assert!(approx(hits[9].t_exit, 95.0));
+    for hit in &hits {
+        assert_eq!(*hit.value, (hit.col, hit.row));
+    }
+    let wall = grid
+        .raycast(-45.0, -15.0, 2.0, 0.0, 1000.0)
+        .find(|hit| hit.value.0 == 4)
+        .unwrap();
+    assert_eq!((wall.col, wall.row), (4, 1));
+    assert!(approx(wall.t_enter, 35.0));
+
+    // Limited by the maximum distance
+    let hits: Vec<_> = grid.raycast(-45.0, -15.0, 1.0, 0.0, 12.0).collect();
+    assert_eq!(hits.len(), 2);
+    assert!(approx(hits[1].t_exit, 12.0));
+
+    // Starts outside and clips the bottom/left corner cell
+    let hits: Vec<_> = grid.raycast(-55.0, -20.0, 1.0, -1.0, 100.0).collect();
+    assert_eq!(hits.len(), 1);
+    assert_eq!((hits[0].col, hits[0].row), (0, 0));
+    assert!(approx(hits[0].t_enter, 5.0 * (2.0 as Scalar).sqrt()));
+    assert!(approx(hits[0].t_exit, 5.0 * (2.0 as Scalar).sqrt()));
+    let hits: Vec<_> = grid.raycast(-60.0, -30.0, 1.0, 1.0, 100.0).collect();
+    assert_eq!((hits[0].col, hits[0].row), (0, 0));
+    assert!(approx(hits[0].t_enter, 10.0 * (2.0 as Scalar).sqrt()));
+    assert!(approx(hits[0].t_exit, 15.0 * (2.0 as Scalar).sqrt()));
+
+    // Never touches the grid
+    assert_eq!(grid.raycast(-60.0, 0.0, -1.0, 0.0, 1000.0).count(), 0);
+    assert_eq!(grid.raycast(-60.0, 0.0, 0.0, 1.0, 1000.0).count(), 0);
+    assert_eq!(grid.raycast(-60.0, 0.0, 1.0, 0.0, 5.0).count(), 0);
+
+    // Non-finite inputs find nothing, but an unlimited distance is fine
+    let nan = Scalar::NAN;
+    assert_eq!(grid.raycast(nan, 0.0, 1.0, 0.0, 1000.0).count(), 0);
+    assert_eq!(grid.raycast(0.0, 0.0, nan, 0.0, 1000.0).count(), 0);
+    assert_eq!(
+        grid.raycast(0.0, 0.0, Scalar::INFINITY, 0.0, 1000.0)
+            .count(),
+        0
+    );
+    assert_eq!(grid.raycast(0.0, 0.0, 1.0, 0.0, nan).count(), 0);
+    assert_eq!(
+        grid.raycast(0.0, 0.0, 1.0, 0.0, Scalar::INFINITY).count(),
+        5
+    );
+}
+
+#[test]
+fn border() {
+    let grid = Grid::new_with_coords(100.0, 50.0, 10, 5, false, |col, row| (col, row));
+    let border = |left, bottom, right, top| -> Vec<(usize, usize)> {
+        let iter = grid.iter_border_coords_in_rect(left, bottom, right, top);
+        let len = iter.len();
+        let coords: Vec<_> = iter.collect();
+        assert_eq!(len, coords.len());
+        coords
+    };
+    // 1x1
+    assert_eq!(border(15.0, 15.0, 15.0, 15.0), [(1, 1)]);
+    // 1xN and Nx1
+    assert_eq!(
+        border(15.0, 5.0, 15.0, 35.0),
+        [(1, 0), (1, 1), (1, 2), (1, 3)]
+    );
+    assert_eq!(
+        border(15.0, 25.0, 45.0, 25.0),
+        [(1, 2), (2, 2), (3, 2), (4, 2)]
+    );
+    // 2x2 has no inner cells
+    assert_eq!(
+        border(5.0, 5.0, 15.0, 15.0),
+        [(0, 0), (1, 0), (0, 1), (1, 1)]
+    );
+    // NxM
+    assert_eq!(
+        border(15.0, 5.0, 45.0, 35.0),
+        [
+            (1, 0),
+            (2, 0),
+            (3, 0),
+            (4, 0),
+            (1, 1),
+            (4, 1),
+            (1, 2),
+            (4, 2),
+            (1, 3),
+            (2, 3),
+            (3, 3),
+            (4, 3)
+        ]
+    );
+    // Clamped to the grid, same result as filtering the whole rectangle
+    let expected: Vec<_> = grid
+        .iter_coords(-100.0, -100.0, 500.0, 500.0)
+        .filter(|&(col, row)| col == 0 || row == 0 || col == 9 || row == 4)
+        .collect();
+    assert_eq!(border(-100.0, -100.0, 500.0, 500.0), expected);
+    // Outside
+    assert!(border(200.0, 0.0, 300.0, 10.0).is_empty());
+
+    let cells: Vec<_> = grid
+        .iter_border_in_rect(15.0, 5.0, 25.0, 25.0)
+        .copied()
+        .collect();
+    assert_eq!(cells, [(1, 0), (2, 0), (1, 1), (2, 1), (1, 2), (2, 2)]);
+}
+
+#[test]
+fn spiral() {
+    let grid = Grid::new_with_coords(100.0, 50.0, 10, 5, false, |col, row| (col, row));
+    let distance = |(col, row): (usize, usize), (start_col, start_row): (usize, usize)| {
+        col.abs_diff(start_col).max(row.abs_diff(start_row))
+    };
+    for start in [(0, 0), (9, 4), (5, 2), (2, 3)] {
+        let coords: Vec<_> = grid.iter_spiral_coords(start.0, start.1, None).collect();
+        // Visits every cell exactly once
+        assert_eq!(coords.len(), grid.columns() * grid.rows());
+        let mut sorted = coords.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted.len(), coords.len());
+        // Never moves closer to the start
+        assert_eq!(coords[0], start);
+        for pair in coords.windows(2) {
+            assert!(distance(pair[0], start) <= distance(pair[1], start));
+        }
+    }
+    // Corner
+    let coords: Vec<_> = grid.iter_spiral_coords(0, 0, Some(1)).collect();
+    assert_eq!(coords, [(0, 0), (1, 0), (0, 1), (1, 1)]);
+    // Center
+    let coords: Vec<_> = grid.iter_spiral_coords(5, 2, Some(1)).collect();
+    assert_eq!(
+        coords,
+        [
+            (5, 2),
+            (4, 1),
+            (5, 1),
+            (6, 1),
+            (4, 2),
+            (6, 2),
+            (4, 3),
+            (5, 3),
+            (6, 3)
+        ]
+    );
+    assert_eq!(grid.iter_spiral_coords(5, 2, Some(0)).count(), 1);
+    assert_eq!(grid.iter_spiral_coords(5, 2, Some(100)).count(), 50);
+    assert_eq!(grid.iter_spiral_coords(10, 2, None).count(), 0);
+
+    // Nearest match
+    let (coords, value) = grid
+        .iter_spiral(1, 1, None)
+        .find(|(_, value)| value.0 >= 7)
+        .unwrap();
+    assert_eq!(coords, (7, 0));
+    assert_eq!(*value, (7, 0));
+}
+
+#[test]
+fn clamped_lookup() {
+    let mut grid = Grid::new_with_coords(100.0, 50.0, 10, 5, true, |col, row| (col, row));
+    // Far left, far above, max corner
+    assert_eq!(grid.get_cell_coords_clamped(-1000.0, 0.0), (0, 2));
+    assert_eq!(grid.get_cell_coords_clamped(12.0, 1000.0), (6, 4));
+    assert_eq!(grid.get_cell_coords_clamped(50.0, 25.0), (9, 4));
+    assert_eq!(grid.get_cell_coords_clamped(-50.0, -25.0), (0, 0));
+    assert_eq!(grid.get_cell_coords_clamped(1000.0, -1000.0), (9, 0));
+    // Same as the regular lookup inside the grid
+    assert_eq!(
+        Some(grid.get_cell_coords_clamped(-3.0, 7.0)),
+        grid.get_cell_coords(-3.0, 7.0)
+    );
+    assert_eq!(*grid.get_cell_clamped(-1000.0, 1000.0), (0, 4));
+    *grid.get_cell_clamped_mut(1000.0, 1000.0) = (0, 0);
+    assert_eq!(grid[(9, 4)], (0, 0));
+}
+
+#[test]
+fn wrapped_lookup() {
+    let mut grid = Grid::new_with_coords(100.0, 50.0, 10, 5, false, |col, row| (col, row));
+    assert_eq!(grid.get_cell_coords_wrapped(15.0, 25.0), (1, 2));
+    // Several sizes outside the grid in both directions
+    assert_eq!(grid.get_cell_coords_wrapped(315.0, 175.0), (1, 2));
+    assert_eq!(grid.get_cell_coords_wrapped(-285.0, -125.0), (1, 2));
+    assert_eq!(grid.get_cell_coords_wrapped(-5.0, -5.0), (9, 4));
+    assert_eq!(grid.get_cell_coords_wrapped(100.0, 50.0), (0, 0));
+    assert_eq!(*grid.get_cell_wrapped(-0.5, 50.5), (9, 0));
+    *grid.get_cell_wrapped_mut(-185.0, 0.0) = (0, 0);
+    assert_eq!(grid[(0, 0)], (0, 0));
+    assert_eq!(grid[(1, 0)], (0, 0));
+
+    // Straddling the right edge, wrapping to column 0
+    let coords: Vec<_> = grid
+        .iter_coords_in_rect_wrapped(85.0, 5.0, 115.0, 15.0)
+        .collect();
+    assert_eq!(
+        coords,
+        [
+            (8, 0),
+            (9, 0),
+            (0, 0),
+            (1, 0),
+            (8, 1),
+            (9, 1),
+            (0, 1),
+            (1, 1)
+        ]
+    );
+    // Straddling the bottom/left corner
+    let coords: Vec<_> = grid
+        .iter_coords_in_rect_wrapped(-5.0, -5.0, 5.0, 5.0)
+        .collect();
+    assert_eq!(coords, [(9, 4), (0, 4), (9, 0), (0, 0)]);
+    // Larger than the grid, each cell only once
+    let mut coords: Vec<_> = grid
+        .iter_coords_in_rect_wrapped(-500.0, -500.0, 500.0, 500.0)
+        .collect();
+    assert_eq!(coords.len(), 50);
+    coords.sort();
+    coords.dedup();
+    assert_eq!(coords.len(), 50);
+    // Same as the regular iterator inside the grid
+    assert!(grid
+        .iter_coords_in_rect_wrapped(15.0, 5.0, 45.0, 35.0)
+        .eq(grid.iter_coords(15.0, 5.0, 45.0, 35.0)));
+    assert_eq!(
+        grid.iter_coords_in_rect_wrapped(10.0, 0.0, 5.0, 0.0)
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn cell_geometry() {
+    for centered in [false, true] {
+        let grid = Grid::<u8>::new(100.0, 50.0, 10, 7, centered);
+        for (col, row) in grid.iter_coords(-500.0, -500.0, 500.0, 500.0) {
+            let (x, y) = grid.cell_center(col, row).unwrap();
+            assert_eq!(grid.get_cell_coords(x, y), Some((col, row)));
+            let (left, bottom, right, top) = grid.cell_rect(col, row).unwrap();
+            assert!(left < x && x < right && bottom < y && y < top);
+        }
+    }
+    let grid = Grid::<u8>::new(100.0, 50.0, 10, 5, true);
+    assert_eq!(grid.cell_rect(0, 0), Some((-50.0, -25.0, -40.0, -15.0)));
+    assert_eq!(grid.cell_center(9, 4), Some((45.0, 20.0)));
+    assert_eq!(grid.cell_rect(10, 0), None);
+    assert_eq!(grid.cell_center(0, 5), None);
+}
+
+#[test]
+fn point_predicates() {
+    let mut rng = rand::thread_rng();
+    for centered in [false, true] {
+        let grid = Grid::<u8>::new(100.0, 50.0, 10, 7, centered);
+        let (left, bottom, right, top) = (grid.left(), grid.bottom(), grid.right(), grid.top());
+        // Edges and random points agree with get_cell_coords
+        let mut points = vec![
+            (left, bottom),
+            (right, top),
+            (right, bottom),
+            (left, top),
+            (right.next_down(), top.next_down()),
+            (left.next_down(), bottom),
+        ];
+        for _ in 0..1000 {
+            points.push((rng.gen_range(-120.0..120.0), rng.gen_range(-70.0..70.0)));
+        }
+        for (x, y) in points {
+            assert_eq!(
+                grid.contains_point(x, y),
+                grid.get_cell_coords(x, y).is_some()
+            );
+            let (cx, cy) = grid.clamp_point(x, y);
+            assert!(grid.contains_point(cx, cy));
+            if grid.contains_point(x, y) {
+                assert_eq!((cx, cy), (x, y));
+            }
+        }
+        assert_eq!(grid.clamp_point(-1000.0, -1000.0), (left, bottom));
+        let (x, y) = grid.clamp_point(1000.0, 1000.0);
+        assert!(x < right && x > right - 0.001);
+        assert!(y < top && y > top - 0.001);
+        assert!(grid.intersects_rect(left - 10.0, bottom - 10.0, left, bottom));
+        assert!(grid.intersects_rect(-1000.0, -1000.0, 1000.0, 1000.0));
+        assert!(!grid.intersects_rect(right, bottom, right + 10.0, top));
+        assert!(!grid.intersects_rect(left - 10.0, bottom, left.next_down(), top));
+    }
+}
+
+#[test]
+fn modify_in_circle() {
+    let mut grid = Grid::new_with(100.0, 50.0, 10, 5, true, || 0.0 as Scalar);
+    // Fully inside
+    let mut count = 0;
+    grid.modify_in_circle(5.0, 0.0, 10.0, |(col, row), distance, value| {
+        assert!((0.0..=1.0).contains(&distance));
+        if (col, row) == (5, 2) {
+            assert_eq!(distance, 0.0);
+        }
+        *value = 1.0 - distance;
+        count += 1;
+    });
+    assert_eq!(count, 5);
+    assert_eq!(grid[(5, 2)], 1.0);
+    assert_eq!(grid[(4, 2)], 0.0);
+    assert_eq!(grid[(5, 3)], 0.0);
+    assert_eq!(
+        grid.iter_all_cells().filter(|value| **value > 0.0).count(),
+        1
+    );
+
+    // Clipped by the grid edges
+    let mut coords = Vec::new();
+    grid.modify_in_circle(-50.0, -25.0, 16.0, |coords_, _, value| {
+        coords.push(coords_);
+        *value = 2.0;
+    });
+    assert_eq!(coords, [(0, 0), (1, 0), (0, 1)]);
+    assert_eq!(grid[(1, 1)], 0.0);
+
+    // Smaller than a cell
+    let mut count = 0;
+    grid.modify_in_circle(45.0, 20.0, 1.0, |coords, distance, _| {
+        assert_eq!(coords, (9, 4));
+        assert_eq!(distance, 0.0);
+        count += 1;
+    });
+    grid.modify_in_circle(42.0, 18.0, 1.0, |_, _, _| count += 1);
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn set_dimensions() {
+    let new_grid = || Grid::new_with_coords(100.0, 50.0, 10, 5, true, |col, row| (col, row));
+    let fill = || (100, 100);
+    let check = |grid: &Grid<(usize, usize)>, columns: usize, rows: usize| {
+        assert_eq!((grid.columns(), grid.rows()), (columns, rows));
+        assert_eq!((grid.width(), grid.height()), (100.0, 50.0));
+        assert_eq!(grid.cell_width(), 100.0 / columns as Scalar);
+        assert_eq!(grid.cell_height(), 50.0 / rows as Scalar);
+        for (col, row) in grid.iter_coords(-100.0, -100.0, 100.0, 100.0) {
+            let expected = if col < 10 && row < 5 {
+                (col, row)
+            } else {
+                fill()
+            };
+            assert_eq!(grid[(col, row)], expected);
+        }
+    };
+    // Grow
+    let mut grid = new_grid();
+    grid.set_dimensions(12, 8, fill).unwrap();
+    check(&grid, 12, 8);
+    // Shrink
+    let mut grid = new_grid();
+    grid.set_dimensions(4, 3, fill).unwrap();
+    check(&grid, 4, 3);
+    // Mixed
+    let mut grid = new_grid();
+    grid.set_dimensions(15, 2, fill).unwrap();
+    check(&grid, 15, 2);
+    grid.set_dimensions(3, 6, fill).unwrap();
+    for row in 0..6 {
+        let expected = if row < 2 { (0, row) } else { fill() };
+        assert_eq!(grid[(0, row)], expected);
+    }
+    // Physical lookups use the new cell size
+    assert_eq!(grid.get_cell_coords(49.0, 24.0), Some((2, 5)));
+
+    assert_eq!(grid.set_dimensions(0, 3, fill), Err(GridError::ZeroColumns));
+    assert_eq!(grid.set_dimensions(3, 0, fill), Err(GridError::ZeroRows));
+    assert_eq!(grid.columns(), 3);
+}
+
+#[test]
+fn insert_remove_rows_columns() {
+    let mut grid = Grid::new_with_coords(100.0, 50.0, 10, 5, true, |col, row| (col, row));
+    let new_cell = (100, 100);
+
+    // Rows, keeping the physical size
+    grid.insert_row(2, false, |_, _| new_cell).unwrap();
+    assert_eq!((grid.rows(), grid.height()), (6, 50.0));
+    assert_eq!(grid.cell_height(), 50.0 / 6.0);
+    assert_eq!(grid[(3, 1)], (3, 1));
+    assert_eq!(grid[(3, 2)], new_cell);
+    assert_eq!(grid[(3, 3)], (3, 2));
+    assert_eq!(grid[(3, 5)], (3, 4));
+    assert_eq!(grid.get_cell(-50.0, 24.0), Some(&(0, 4)));
+    let removed = grid.remove_row(2, false).unwrap();
+    assert_eq!(removed, [new_cell; 10]);
+    assert_eq!((grid.rows(), grid.cell_height()), (5, 10.0));
+    assert_eq!(grid[(3, 2)], (3, 2));
+
+    // Columns, growing the physical size
+    grid.insert_column(10, true, |col, row| (col * 10, row))
+        .unwrap();
+    assert_eq!(
+        (grid.columns(), grid.width(), grid.cell_width()),
+        (11, 110.0, 10.0)
+    );
+    assert_eq!((grid.left(), grid.right()), (-55.0, 55.0));
+    assert_eq!(grid[(10, 3)], (100, 3));
+    assert_eq!(grid.get_cell(50.0, -25.0), Some(&(100, 0)));
+    assert_eq!(grid.get_cell(-50.0, -25.0), Some(&(0, 0)));
+    grid.insert_column(0, true, |_, _| new_cell).unwrap();
+    assert_eq!(grid[(0, 4)], new_cell);
+    assert_eq!(grid[(1, 4)], (0, 4));
+    let removed = grid.remove_column(0, true).unwrap();
+    assert_eq!(removed, [new_cell; 5]);
+    assert_eq!(
+        grid.remove_column(10, true).unwrap(),
+        [(100, 0), (100, 1), (100, 2), (100, 3), (100, 4)]
+    );
+    assert_eq!(
+        (grid.columns(), grid.width(), grid.left()),
+        (10, 100.0, -50.0)
+    );
+    assert_eq!(
+        grid,
+        Grid::new_with_coords(100.0, 50.0, 10, 5, true, |col, row| (col, row))
+    );
+
+    // Growing a row on a grid starting at the bottom/left corner
+    let mut grid = Grid::new_with_coords(100.0, 50.0, 10, 5, false, |col, row| (col, row));
+    grid.insert_row(5, true, |_, _| new_cell).unwrap();
+    assert_eq!((grid.bottom(), grid.top()), (0.0, 60.0));
+    assert_eq!(grid.get_cell(5.0, 55.0), Some(&new_cell));
+
+    // Errors
+    assert_eq!(
+        grid.insert_row(7, false, |_, _| new_cell),
+        Err(GridError::RowOutOfRange { row: 7 })
+    );
+    assert_eq!(
+        grid.remove_row(6, false),
+        Err(GridError::RowOutOfRange { row: 6 })
+    );
+    assert_eq!(
+        grid.insert_column(11, false, |_, _| new_cell),
+        Err(GridError::ColumnOutOfRange { col: 11 })
+    );
+    assert_eq!(
+        grid.remove_column(10, false),
+        Err(GridError::ColumnOutOfRange { col: 10 })
+    );
+    let mut grid = Grid::<u8>::new(10.0, 10.0, 1, 1, false);
+    assert_eq!(grid.remove_row(0, false), Err(GridError::ZeroRows));
+    assert_eq!(grid.remove_column(0, false), Err(GridError::ZeroColumns));
+}
+
+#[test]
+fn crop() {
+    let grid = Grid::new_with_coords(100.0, 50.0, 10, 5, true, |col, row| (col, row));
+    let cropped = grid.crop(2, 1, 5, 3).unwrap();
+    assert_eq!((cropped.columns(), cropped.rows()), (4, 3));
+    assert_eq!((cropped.width(), cropped.height()), (40.0, 30.0));
+    assert_eq!((cropped.cell_width(), cropped.cell_height()), (10.0, 10.0));
+    assert_eq!(cropped[(0, 0)], (2, 1));
+    assert_eq!(cropped[(3, 2)], (5, 3));
+    // Same physical location as in the parent
+    assert_eq!(
+        (
+            cropped.left(),
+            cropped.bottom(),
+            cropped.right(),
+            cropped.top()
+        ),
+        (-30.0, -15.0, 10.0, 15.0)
+    );
+    for info in grid.iter_info_in_rect(-30.0, -15.0, 9.0, 14.0) {
+        let (x, y) = (info.center_x, info.center_y);
+        assert_eq!(cropped.get_cell(x, y), grid.get_cell(x, y));
+        assert_eq!(
+            cropped.get_cell(info.left, info.bottom),
+            grid.get_cell(info.left, info.bottom)
+        );
+    }
+    assert_eq!(cropped.get_cell(-31.0, 0.0), None);
+
+    let consumed = grid.clone().crop_into(2, 1, 5, 3).unwrap();
+    assert_eq!(consumed, cropped);
+    assert_eq!(grid.clone().crop_into(0, 0, 9, 4).unwrap(), grid);
+    assert_eq!(grid.crop(9, 4, 9, 4).unwrap().raw_data(), [(9, 4)]);
+
+    assert_eq!(
+        grid.crop(0, 0, 10, 4),
+        Err(GridError::IndicesOutOfRange { col: 10, row: 4 })
+    );
+    assert_eq!(grid.crop(3, 0, 2, 4), Err(GridError::ZeroColumns));
+    assert_eq!(grid.clone().crop_into(0, 3, 2, 2), Err(GridError::ZeroRows));
+}
+
+#[test]
+fn blit() {
+    let base = Grid::new_with_coords(100.0, 50.0, 10, 5, false, |col, row| (col, row));
+    // Different cell size, copied by indices
+    let stamp = Grid::new_with_coords(3.0, 2.0, 3, 2, true, |col, row| (col + 100, row + 100));
+
+    // Fully inside
+    let mut grid = base.clone();
+    grid.blit(&stamp, 2, 1);
+    for (col, row) in grid.iter_coords(0.0, 0.0, 100.0, 50.0) {
+        let expected = if (2..5).contains(&col) && (1..3).contains(&row) {
+            (col - 2 + 100, row - 1 + 100)
+        } else {
+            (col, row)
+        };
+        assert_eq!(grid[(col, row)], expected);
+    }
+
+    // Clipped at the right/top edge
+    let mut grid = base.clone();
+    grid.blit(&stamp, 8, 4);
+    assert_eq!(grid[(8, 4)], (100, 100));
+    assert_eq!(grid[(9, 4)], (101, 100));
+    assert_eq!(grid[(8, 3)], (8, 3));
+    assert_eq!(
+        grid.iter_all_cells().filter(|cell| cell.0 >= 100).count(),
+        2
+    );
+
+    // Entirely outside
+    let mut grid = base.clone();
+    grid.blit(&stamp, 10, 0);
+    grid.blit(&stamp, 0, 5);
+    assert_eq!(grid, base);
+
+    // Source region
+    let mut grid = base.clone();
+    grid.blit_region(&stamp, 1, 1, 2, 1, 0, 0).unwrap();
+    assert_eq!(grid[(0, 0)], (101, 101));
+    assert_eq!(grid[(1, 0)], (102, 101));
+    assert_eq!(grid[(2, 0)], (2, 0));
+    assert_eq!(grid[(0, 1)], (0, 1));
+    assert_eq!(
+        grid.blit_region(&stamp, 0, 0, 3, 1, 0, 0),
+        Err(GridError::IndicesOutOfRange { col: 3, row: 1 })
+    );
+}
+
+#[test]
+fn flip_and_transpose() {
+    let original = Grid::new_with_coords(100.0, 50.0, 7, 5, true, |col, row| (col, row));
+
+    let mut grid = original.clone();
+    grid.flip_horizontal();
+    assert_eq!(grid.get_cell_by_indices(0, 2), Some(&(6, 2)));
+    assert_eq!(grid[(3, 4)], (3, 4));
+    assert_eq!(grid[(5, 1)], (1, 1));
+    grid.flip_horizontal();
+    assert_eq!(grid, original);
+
+    grid.flip_vertical();
+    assert_eq!(grid[(2, 0)], (2, 4));
+    assert_eq!(grid[(2, 2)], (2, 2));
+    assert_eq!(grid[(6, 3)], (6, 1));
+    grid.flip_vertical();
+    assert_eq!(grid, original);
+
+    let grid = Grid::new_with_coords(100.0, 50.0, 10, 5, false, |col, row| (col, row));
+    let transposed = grid.clone().transpose();
+    assert_eq!((transposed.columns(), transposed.rows()), (5, 10));
+    assert_eq!((transposed.width(), transposed.height()), (50.0, 100.0));
+    assert_eq!(
+        (transposed.cell_width(), transposed.cell_height()),
+        (10.0, 10.0)
+    );
+    assert_eq!((transposed.offset_x(), transposed.offset_y()), (0.0, 0.0));
+    for (col, row) in transposed.iter_coords(0.0, 0.0, 50.0, 100.0) {
+        assert_eq!(transposed[(col, row)], (row, col));
+    }
+    assert_eq!(transposed.transpose(), grid);
+    let transposed = Grid::<u8>::new(30.0, 20.0, 3, 4, true).transpose();
+    assert_eq!((transposed.left(), transposed.bottom()), (-10.0, -15.0));
+    assert_eq!(
+        (transposed.cell_width(), transposed.cell_height()),
+        (5.0, 10.0)
+    );
+}
+
+#[test]
+fn rotate() {
+    let original = Grid::new_with_coords(100.0, 50.0, 10, 5, true, |col, row| (col, row));
+
+    let rotated = original.clone().rotate_cw();
+    assert_eq!((rotated.columns(), rotated.rows()), (5, 10));
+    assert_eq!((rotated.width(), rotated.height()), (50.0, 100.0));
+    assert_eq!((rotated.left(), rotated.bottom()), (-25.0, -50.0));
+    // Left column is now the top row, bottom row is now the left column
+    assert_eq!(rotated[(0, 9)], (0, 0));
+    assert_eq!(rotated[(4, 9)], (0, 4));
+    assert_eq!(rotated[(0, 0)], (9, 0));
+    for (col, row) in rotated.iter_coords(-100.0, -100.0, 100.0, 100.0) {
+        assert_eq!(rotated[(col, row)], (9 - row, col));
+    }
+    // Same physical point, rotated around the pivot
+    assert_eq!(original.get_cell(-42.0, 13.0), rotated.get_cell(13.0, 42.0));
+
+    let rotated = original.clone().rotate_ccw();
+    assert_eq!((rotated.left(), rotated.bottom()), (-25.0, -50.0));
+    for (col, row) in rotated.iter_coords(-100.0, -100.0, 100.0, 100.0) {
+        assert_eq!(rotated[(col, row)], (row, 4 - col));
+    }
+    assert_eq!(
+        original.get_cell(-42.0, 13.0),
+        rotated.get_cell(-13.0, -42.0)
+    );
+
+    // Full turns, also with a bottom/left pivot
+    for centered in [true, false] {
+        let original = Grid::new_with_coords(30.0, 80.0, 3, 4, centered, |col, row| (col, row));
+        let mut grid = original.clone();
+        for _ in 0..4 {
+            grid = grid.rotate_cw();
+        }
+        assert_eq!(grid, original);
+        assert_eq!(original.clone().rotate_cw().rotate_ccw(), original);
+    }
+}
+
+#[test]
+fn shift() {
+    let original = Grid::new_with_coords(100.0, 50.0, 10, 5, false, |col, row| (col, row));
+    let empty = (100, 100);
+
+    // Wrap, both directions
+    let mut grid = original.clone();
+    grid.shift_wrap(3, 1);
+    for (col, row) in grid.iter_coords(0.0, 0.0, 100.0, 50.0) {
+        assert_eq!(grid[(col, row)], ((col + 7) % 10, (row + 4) % 5));
+    }
+    grid.shift_wrap(-3, -1);
+    assert_eq!(grid, original);
+    // Modular for large deltas
+    grid.shift_wrap(-23, 11);
+    let mut expected = original.clone();
+    expected.shift_wrap(7, 1);
+    assert_eq!(grid, expected);
+
+    // Fill, both directions
+    let mut grid = original.clone();
+    grid.shift_fill(2, -1, || empty);
+    for (col, row) in grid.iter_coords(0.0, 0.0, 100.0, 50.0) {
+        if col < 2 || row == 4 {
+            assert_eq!(grid[(col, row)], empty);
+        } else {
+            assert_eq!(grid[(col, row)], (col - 2, row + 1));
+        }
+    }
+    let mut grid = original.clone();
+    grid.shift_fill(-4, 2, || empty);
+    for (col, row) in grid.iter_coords(0.0, 0.0, 100.0, 50.0) {
+        if col >= 6 || row < 2 {
+            assert_eq!(grid[(col, row)], empty);
+        } else {
+            assert_eq!(grid[(col, row)], (col + 4, row - 2));
+        }
+    }
+    // Cells vacated in both directions are filled once
+    let mut grid = original.clone();
+    let mut fills = 0;
+    grid.shift_fill(-4, 2, || {
+        fills += 1;
+        empty
+    });
+    assert_eq!(fills, (4 * 5) + (6 * 2));
+    let mut fills = 0;
+    grid.shift_fill(12, -7, || {
+        fills += 1;
+        empty
+    });
+    assert_eq!(fills, 50);
+    let mut grid = original.clone();
+    grid.shift_fill(0, 0, || empty);
+    assert_eq!(grid, original);
+    // Larger than the grid clears everything
+    grid.shift_fill(-10, 0, || empty);
+    assert!(grid.iter_all_cells().all(|cell| *cell == empty));
+    let mut grid = original.clone();
+    grid.shift_fill(0, 8, || empty);
+    assert!(grid.iter_all_cells().all(|cell| *cell == empty));
+}
+
+#[test]
+fn position() {
+    for centered in [false, true] {
+        let mut grid = Grid::new_with_coords(100.0, 50.0, 10, 5, centered, |col, row| (col, row));
+        let (left, bottom) = (grid.left(), grid.bottom());
+        grid.set_position(1000.0, -300.0);
+        assert_eq!((grid.x(), grid.y()), (1000.0, -300.0));
+        assert_eq!(
+            (grid.left(), grid.bottom()),
+            (left + 1000.0, bottom - 300.0)
+        );
+        assert_eq!((grid.right(), grid.top()), (left + 1100.0, bottom - 250.0));
+
+        // A world point over cell (3, 2)
+        let (x, y) = (grid.left() + 35.0, grid.bottom() + 25.0);
+        assert_eq!(grid.get_cell_coords(x, y), Some((3, 2)));
+        assert_eq!(grid.get_cell(x, y), Some(&(3, 2)));
+        assert_eq!(grid.cell_center(3, 2), Some((x, y)));
+        assert!(grid.contains_point(x, y));
+        assert!(!grid.contains_point(x - 1000.0, y + 300.0));
+        assert_eq!(grid.get_cell_coords_clamped(x - 1000.0, y), (0, 2));
+        assert_eq!(grid.get_cell_coords_wrapped(x + 100.0, y), (3, 2));
+        let coords: Vec<_> = grid.iter_coords(x, y, x + 10.0, y).collect();
+        assert_eq!(coords, [(3, 2), (4, 2)]);
+        let info = grid.iter_info_in_rect(x, y, x, y).next().unwrap();
+        assert_eq!((info.center_x, info.center_y), (x, y));
+        assert_eq!(
+            grid.iter_coords_in_circle(x, y, 1.0).collect::<Vec<_>>(),
+            [(3, 2)]
+        );
+        let coords: Vec<_> = grid
+            .iter_coords_on_segment(x, y, x + 20.0, y, false)
+            .collect();
+        assert_eq!(coords, [(3, 2), (4, 2), (5, 2)]);
+        assert_eq!(
+            grid.raycast(x, y, 0.0, 1.0, 1.0).next().map(|hit| hit.col),
+            Some(3)
+        );
+
+        // Moving the grid moves the cells with it
+        grid.translate(-10.0, 10.0);
+        assert_eq!(grid.get_cell_coords(x, y), Some((4, 1)));
+        grid.translate(10.0, -10.0);
+        assert_eq!(grid.get_cell_coords(x, y), Some((3, 2)));
+
+        // Cropped grids keep the world location
+        let cropped = grid.crop(2, 1, 5, 3).unwrap();
+        assert_eq!(cropped.get_cell(x, y), Some(&(3, 2)));
+    }
+}
+
+#[test]
+#[should_panic(expected = "Position (NaN, 0) is not finite")]
+fn position_nan() {
+    let mut grid = Grid::<u8>::new(100.0, 50.0, 10, 5, false);
+    grid.set_position(Scalar::NAN, 0.0);
+}
+
+#[test]
+fn pivot() {
+    let mut grid = Grid::new_with_coords(100.0, 50.0, 10, 5, false, |col, row| (col, row));
+    assert_eq!(grid.pivot(), (0.0, 0.0));
+    assert_eq!(grid.get_cell_coords(5.0, 5.0), Some((0, 0)));
+
+    // Same point, different cell, same contents
+    grid.set_pivot_preset(Pivot::Center);
+    assert_eq!(grid.pivot(), (0.5, 0.5));
+    assert_eq!((grid.left(), grid.bottom()), (-50.0, -25.0));
+    assert_eq!(grid.get_cell_coords(5.0, 5.0), Some((5, 3)));
+    assert_eq!(grid.get_cell(5.0, 5.0), Some(&(5, 3)));
+
+    grid.set_pivot_preset(Pivot::TopRight);
+    assert_eq!(grid.pivot(), (1.0, 1.0));
+    assert_eq!(grid.get_cell_coords(5.0, 5.0), None);
+    assert_eq!(grid.get_cell_coords(-5.0, -5.0), Some((9, 4)));
+
+    grid.set_pivot_preset(Pivot::TopLeft);
+    assert_eq!(grid.get_cell_coords(5.0, -5.0), Some((0, 4)));
+    grid.set_pivot_preset(Pivot::BottomRight);
+    assert_eq!(grid.get_cell_coords(-5.0, 5.0), Some((9, 0)));
+
+    // The pivot stays at the position
+    grid.set_position(200.0, 100.0);
+    grid.set_pivot(0.25, 0.5);
+    assert_eq!(grid.pivot(), (0.25, 0.5));
+    assert_eq!((grid.left(), grid.bottom()), (175.0, 75.0));
+    assert_eq!(grid.get_cell_coords(200.0, 100.0), Some((2, 2)));
+    grid.set_pivot_preset(Pivot::Custom(0.0, 0.0));
+    assert_eq!(grid.get_cell_coords(200.0, 100.0), Some((0, 0)));
+
+    let grid = GridBuilder::new()
+        .size(100.0, 50.0)
+        .cells(10, 5)
+        .pivot_preset(Pivot::TopLeft)
+        .build_default::<u8>()
+        .unwrap();
+    assert_eq!(grid.pivot(), (0.0, 1.0));
+    assert_eq!((grid.left(), grid.top()), (0.0, 0.0));
+}
+
+#[test]
+fn resize() {
+    let mut grid = Grid::new_with_coords(100.0, 50.0, 10, 5, true, |col, row| (col, row));
+    grid.set_position(10.0, 20.0);
+    grid.resize(200.0, 60.0).unwrap();
+    assert_eq!((grid.cell_width(), grid.cell_height()), (20.0, 12.0));
+    assert_eq!(grid.pivot(), (0.5, 0.5));
+    assert_eq!((grid.x(), grid.y()), (10.0, 20.0));
+    assert_eq!((grid.left(), grid.bottom()), (-90.0, -10.0));
+
+    // Anchored at the bottom/left corner
+    let mut grid = Grid::new_with_coords(100.0, 50.0, 10, 5, true, |col, row| (col, row));
+    grid.resize_anchored(200.0, 100.0, -50.0, -25.0).unwrap();
+    assert_eq!((grid.left(), grid.bottom()), (-50.0, -25.0));
+    assert_eq!((grid.right(), grid.top()), (150.0, 75.0));
+    assert_eq!(grid.pivot(), (0.5, 0.5));
+
+    // Anchored at the center
+    let mut grid = Grid::new_with_coords(100.0, 50.0, 10, 5, false, |col, row| (col, row));
+    grid.resize_anchored(50.0, 100.0, 50.0, 25.0).unwrap();
+    assert_eq!((grid.left(), grid.bottom()), (25.0, -25.0));
+    assert_eq!(grid.get_cell_coords(50.0, 25.0), Some((5, 2)));
+    assert_eq!(grid.pivot(), (0.0, 0.0));
+
+    // A cell under the anchor stays under it
+    let mut grid = Grid::new_with_coords(100.0, 50.0, 10, 5, true, |col, row| (col, row));
+    grid.resize_anchored(300.0, 150.0, 12.0, 7.0).unwrap();
+    assert_eq!(grid.get_cell(12.0, 7.0), Some(&(6, 3)));
+
+    // Invalid sizes leave the grid untouched
+    let copy = grid.clone();
+    assert_eq!(
+        grid.resize(0.0, 10.0),
+        Err(GridError::NonPositiveSize {
+            width: 0.0,
+            height: 10.0
+        })
+    );
+    assert_eq!(
+        grid.resize_anchored(10.0, Scalar::NAN, 0.0, 0.0),
+        Err(GridError::NonFiniteSize)
+    );
+    assert_eq!(
+        grid.resize_anchored(10.0, 10.0, 0.0, Scalar::NAN),
+        Err(GridError::NonFiniteValue { field: "anchor_y" })
+    );
+    assert_eq!(grid, copy);
+}
+
+#[cfg(feature = "f64")]
+#[test]
+fn f64_precision() {
+    // Far beyond the range where f32 can tell cells apart
+    let mut grid = Grid::new_with_coords(10.0, 10.0, 10, 10, false, |col, row| (col, row));
+    grid.set_position(100_000_000.0, -100_000_000.0);
+    assert_eq!(grid.get_cell(100_000_003.5, -99_999_992.5), Some(&(3, 7)));
+    assert_eq!(grid.cell_center(3, 7), Some((100_000_003.5, -99_999_992.5)));
+}
+
+#[test]
+fn int_lookups() {
+    let mut grid = Grid::<Vec<(i32, i32)>>::new_int(10, 10, 10, 10, true, Vec::new);
+    assert_eq!(grid.left(), -50.0);
+    let frame = grid.int_frame().unwrap();
+    let mut rng = rand::thread_rng();
+    for _n in 0..100 {
+        let x = rng.gen_range(-50..50);
+        let y = rng.gen_range(-50..50);
+        if let Some(container) = grid.get_cell_int_mut(&frame, x, y) {
+            container.push((x, y));
+        };
+    }
+    for (cell, col, row) in grid.iter_all_with_coords() {
+        for &(x, y) in cell {
+            assert_eq!(((x + 50) / 10) as usize, col);
+            assert_eq!(((y + 50) / 10) as usize, row);
+            // Same result as the float lookup
+            assert_eq!(
+                grid.get_cell_coords(x as Scalar, y as Scalar),
+                Some((col, row))
+            );
+        }
+    }
+
+    // Odd sizes round the centered pivot down
+    let mut grid = Grid::new_int(7, 3, 5, 5, true, || 0u8);
+    assert_eq!((grid.left(), grid.bottom()), (-17.0, -7.0));
+    let frame = grid.int_frame().unwrap();
+    assert_eq!(grid.get_cell_coords_int(&frame, -17, -7), Some((0, 0)));
+    assert_eq!(grid.get_cell_coords_int(&frame, -18, -7), None);
+    assert_eq!(grid.get_cell_coords_int(&frame, -11, -5), Some((0, 0)));
+    assert_eq!(grid.get_cell_coords_int(&frame, -10, -4), Some((1, 1)));
+    // Exactly on the right/top edges
+    assert_eq!(grid.get_cell_coords_int(&frame, 18, 0), None);
+    assert_eq!(grid.get_cell_coords_int(&frame, 0, 8), None);
+    assert_eq!(grid.get_cell_coords_int(&frame, 17, 7), Some((4, 4)));
+
+    // A new frame follows the grid position
+    grid.set_position(100.0, -100.0);
+    assert_eq!(grid.get_cell_coords_int(&frame, -17, -7), Some((0, 0)));
+    let frame = grid.int_frame().unwrap();
+    assert_eq!(grid.get_cell_coords_int(&frame, -17, -7), None);
+    assert_eq!(grid.get_cell_coords_int(&frame, 83, -107), Some((0, 0)));
+    *grid.get_cell_int_mut(&frame, 117, -93).unwrap() = 5;
+    assert_eq!(grid.get_cell_int(&frame, 117, -93), Some(&5));
+    assert_eq!(grid[(4, 4)], 5);
+
+    assert_eq!(
+        Grid::try_new_int(0, 10, 10, 10, false, || 0u8),
+        Err(GridError::NonPositiveSize {
+            width: 0.0,
+            height: 100.0
+        })
+    );
+
+    // Frames that wouldn't match the float lookups are rejected
+    grid.set_position(100.5, 0.0);
+    assert_eq!(
+        grid.int_frame(),
+        Err(GridError::NonIntegerFrame {
+            field: "left",
+            value: 83.5
+        })
+    );
+    let grid = Grid::<u8>::new(10.0, 10.0, 4, 4, false);
+    assert_eq!(
+        grid.int_frame(),
+        Err(GridError::NonIntegerFrame {
+            field: "cell_width",
+            value: 2.5
+        })
+    );
+    // Frames from grids of another size find nothing
+    let grid = Grid::new_int(7, 3, 4, 4, false, || 0u8);
+    assert_eq!(grid.get_cell_coords_int(&frame, 83, -107), None);
+    assert_eq!(grid.iter_coords_int(&frame, 83, -107, 90, -100).count(), 0);
+}
+
+#[test]
+fn int_rects() {
+    let grid = Grid::new_int(10, 10, 10, 10, true, || 0usize);
+    let frame = grid.int_frame().unwrap();
+    // Same cells as the float version
+    let coords: Vec<_> = grid.iter_coords_int(&frame, -25, -15, 15, 65).collect();
+    let float: Vec<_> = grid.iter_coords(-25.0, -15.0, 15.0, 65.0).collect();
+    assert_eq!(coords, float);
+    assert_eq!(coords.first(), Some(&(2, 3)));
+    assert_eq!(coords.last(), Some(&(6, 9)));
+    let coords: Vec<_> = grid
+        .iter_coords_int(&frame, -25, -15, 15, 65)
+        .y_down()
+        .collect();
+    assert_eq!(coords.first(), Some(&(2, 9)));
+
+    // Touching the left edge, fully outside, and inverted
+    assert_eq!(grid.iter_coords_int(&frame, -60, -60, -50, -50).count(), 1);
+    assert_eq!(grid.iter_coords_int(&frame, -60, -60, -51, -51).count(), 0);
+    assert_eq!(grid.iter_coords_int(&frame, 50, 0, 60, 10).count(), 0);
+    assert_eq!(grid.iter_coords_int(&frame, 10, 0, 0, 10).count(), 0);
+
+    let mut grid = Grid::new_int(10, 10, 10, 10, true, || 0usize);
+    let frame = grid.int_frame().unwrap();
+    for (i, cell) in grid
+        .iter_cells_in_rect_int_mut(&frame, -50, -50, 49, 49)
+        .enumerate()
+    {
+        *cell = i;
+    }
+    for (i, cell) in grid
+        .iter_cells_in_rect_int(&frame, -50, -50, 49, 49)
+        .enumerate()
+    {
+        assert_eq!(i, *cell);
+    }
+    assert_eq!(grid.get_cell_int(&frame, -41, -31), Some(&10));
+}
+
+#[test]
+fn float_functions() {
+    // Same results with libm and with the standard library
+    use crate::scalar::{ceil, floor, sqrt};
+    assert_eq!(floor(2.5), 2.0);
+    assert_eq!(floor(-2.5), -3.0);
+    assert_eq!(floor(-0.0).to_bits(), (-0.0 as Scalar).to_bits());
+    assert_eq!(ceil(2.5), 3.0);
+    assert_eq!(ceil(-2.5), -2.0);
+    assert_eq!(ceil(-0.5).to_bits(), (-0.0 as Scalar).to_bits());
+    assert_eq!(sqrt(2.0), (2.0 as Scalar).sqrt());
+    assert!(sqrt(-1.0).is_nan());
+    assert!(floor(Scalar::NAN).is_nan());
+    assert_eq!(floor(Scalar::INFINITY), Scalar::INFINITY);
+}
+
+#[test]
+fn point_arguments() {
+    let mut grid = Grid::new_with_coords(100.0, 100.0, 10, 10, true, |col, row| (col, row));
+    let tuple: (Scalar, Scalar) = (-25.0, 35.0);
+    let array: [Scalar; 2] = [-25.0, 35.0];
+    let point = mint::Point2 { x: -25.0, y: 35.0 };
+    assert_eq!(grid.get_cell_coords_at(tuple), Some((2, 8)));
+    assert_eq!(grid.get_cell_at(array), Some(&(2, 8)));
+    assert_eq!(grid.get_cell_at(point), Some(&(2, 8)));
+    assert_eq!(grid.get_cell_at((100.0, 0.0)), None);
+    grid.get_cell_at_mut(point).unwrap().0 = 20;
+    assert_eq!(grid[(2, 8)], (20, 8));
+
+    let circle: Vec<_> = grid.iter_coords_in_circle(5.0, 5.0, 12.0).collect();
+    assert_eq!(
+        grid.iter_coords_in_circle_at((5.0, 5.0), 12.0)
+            .collect::<Vec<_>>(),
+        circle
+    );
+    let center = mint::Vector2 { x: 5.0, y: 5.0 };
+    assert_eq!(
+        grid.iter_cells_in_circle_at(center, 12.0).count(),
+        circle.len()
+    );
+
+    let segment: Vec<_> = grid
+        .iter_coords_on_segment(-45.0, -45.0, 30.0, 10.0, true)
+        .collect();
+    let start = mint::Point2 { x: -45.0, y: -45.0 };
+    assert_eq!(
+        grid.iter_coords_on_segment_at(start, [30.0, 10.0], true)
+            .collect::<Vec<_>>(),
+        segment
+    );
+    assert_eq!(
+        grid.iter_cells_on_segment_at((-45.0, -45.0), (30.0, 10.0), true)
+            .count(),
+        segment.len()
+    );
+
+    let ray: Vec<_> = grid
+        .raycast_at((-45.0, 0.0), [1.0, 0.0], 1000.0)
+        .map(|hit| (hit.col, hit.row))
+        .collect();
+    assert_eq!(ray.len(), 10);
+    assert_eq!(ray.first(), Some(&(0, 5)));
+}
+
+#[cfg(feature = "glam")]
+#[test]
+fn glam_vectors() {
+    #[cfg(feature = "f64")]
+    use glam::DVec2 as Vec2;
+    #[cfg(not(feature = "f64"))]
+    use glam::Vec2;
+
+    let mut grid = Grid::new_with_coords(100.0, 100.0, 10, 10, true, |col, row| (col, row));
+    let player = Vec2::new(-25.0, 35.0);
+    assert_eq!(grid.get_cell_at(player), Some(&(2, 8)));
+
+    let (min, max) = grid.cell_rect_vec(2, 8).unwrap();
+    assert_eq!((min, max), (Vec2::new(-30.0, 30.0), Vec2::new(-20.0, 40.0)));
+    assert_eq!(grid.cell_center_vec(2, 8), Some(Vec2::new(-25.0, 35.0)));
+    assert_eq!(grid.cell_rect_vec(10, 0), None);
+    assert_eq!(grid.cell_center_vec(0, 10), None);
+
+    // A camera view around the player
+    let half = Vec2::new(12.0, 8.0);
+    let cells: Vec<_> = grid
+        .iter_cells_in_rect_vec(player - half, player + half)
+        .copied()
+        .collect();
+    assert_eq!(
+        cells,
+        vec![
+            (1, 7),
+            (2, 7),
+            (3, 7),
+            (1, 8),
+            (2, 8),
+            (3, 8),
+            (1, 9),
+            (2, 9),
+            (3, 9)
+        ]
+    );
+    for cell in grid.iter_cells_in_rect_vec_mut(player - half, player + half) {
+        cell.0 += 100;
+    }
+    assert_eq!(
+        grid.get_cell_at(player + Vec2::new(10.0, 0.0)),
+        Some(&(103, 8))
+    );
+    assert_eq!(
+        grid.get_cell_at(player + Vec2::new(20.0, 0.0)),
+        Some(&(4, 8))
+    );
+}
+
+#[test]
+fn find_path() {
+    // Cells hold their cost, 0 is a wall
+    let passable = |cell: &u32| (*cell > 0).then_some(*cell);
+
+    // Straight corridor
+    let grid = Grid::new_with(100.0, 10.0, 10, 1, false, || 1u32);
+    let path = grid
+        .find_path((0, 0), (9, 0), Connectivity::Four, passable)
+        .unwrap();
+    assert_eq!(path, (0..10).map(|col| (col, 0)).collect::<Vec<_>>());
+    assert_eq!(
+        grid.find_path((3, 0), (3, 0), Connectivity::Four, passable),
+        Some(vec![(3, 0)])
+    );
+
+    // A wall on column 2 with a gap on the top row
+    let mut grid = Grid::new_with(50.0, 50.0, 5, 5, false, || 1u32);
+    for row in 0..4 {
+        grid[(2, row)] = 0;
+    }
+    let path = grid
+        .find_path((0, 0), (4, 0), Connectivity::Four, passable)
+        .unwrap();
+    assert_eq!(path.len(), 13);
+    assert_eq!(path.first(), Some(&(0, 0)));
+    assert_eq!(path.last(), Some(&(4, 0)));
+    assert!(path.contains(&(2, 4)));
+    for pair in path.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        assert_eq!(a.0.abs_diff(b.0) + a.1.abs_diff(b.1), 1);
+        assert_ne!(grid[b], 0);
+    }
+    // Diagonal steps cut the corners
+    let path = grid
+        .find_path((0, 0), (4, 0), Connectivity::Eight, passable)
+        .unwrap();
+    assert_eq!(path.len(), 9);
+
+    // Expensive cells are avoided when a cheaper route exists
+    let mut context = PathContext::new();
+    let mut grid = Grid::new_with(30.0, 30.0, 3, 3, false, || 1u32);
+    grid[(1, 0)] = 10;
+    let path = grid
+        .find_path_with(&mut context, (0, 0), (2, 0), Connectivity::Four, passable)
+        .unwrap();
+    assert_eq!(path, vec![(0, 0), (0, 1), (1, 1), (2, 1), (2, 0)]);
+
+    // Unreachable goals, impassable goals and out of range ends
+    grid[(1, 0)] = 0;
+    grid[(1, 1)] = 0;
+    grid[(1, 2)] = 0;
+    for _ in 0..2 {
+        // Same answer when the context is reused
+        let path = grid.find_path_with(&mut context, (0, 0), (2, 2), Connectivity::Eight, passable);
+        assert_eq!(path, None);
+    }
+    assert_eq!(
+        grid.find_path((0, 0), (1, 1), Connectivity::Four, passable),
+        None
+    );
+    assert_eq!(
+        grid.find_path((0, 0), (3, 0), Connectivity::Four, passable),
+        None
+    );
+    assert_eq!(
+        grid.find_path_with(&mut context, (0, 0), (0, 2), Connectivity::Four, passable),
+        Some(vec![(0, 0), (0, 1), (0, 2)])
+    );
+}
+
+#[test]
+fn flood_fill() {
+    // A wall on column 3 splits the grid in two regions
+    let mut grid = Grid::new_with_coords(60.0, 40.0, 6, 4, false, |col, _| col == 3);
+    grid.flood_fill(0, 0, |wall| !*wall, |cell| *cell = true);
+    for col in 0..6 {
+        for row in 0..4 {
+            assert_eq!(grid[(col, row)], col <= 3, "{col},{row}");
+        }
+    }
+
+    // Reaches the grid edges, in order of distance from the seed
+    let grid = Grid::new_with(60.0, 40.0, 6, 4, false, || 0u8);
+    let region = grid.flood_collect(2, 1, |cell| *cell == 0);
+    assert_eq!(region.len(), 24);
+    assert_eq!(region[0], (2, 1));
+    assert_eq!(&region[1..5], &[(2, 0), (1, 1), (3, 1), (2, 2)]);
+    assert_eq!(region.last(), Some(&(5, 3)));
+
+    // The seed doesn't match, or is out of range
+    let mut grid = Grid::new_with_coords(60.0, 40.0, 6, 4, false, |col, row| (col + row) % 2);
+    let copy = grid.clone();
+    grid.flood_fill(0, 0, |cell| *cell == 1, |cell| *cell = 5);
+    grid.flood_fill(6, 0, |cell| *cell == 0, |cell| *cell = 5);
+    assert_eq!(grid, copy);
+    // Diagonal cells aren't connected
+    grid.flood_fill(0, 0, |cell| *cell == 0, |cell| *cell = 5);
+    assert_eq!(grid[(0, 0)], 5);
+    assert_eq!(grid[(1, 1)], 0);
+    assert_eq!(grid.flood_collect(1, 1, |cell| *cell == 0), vec![(1, 1)]);
+}
+
+#[test]
+fn rect_queries() {
+    let mut grid = Grid::new_with(100.0, 100.0, 10, 10, true, || 0u8);
+    let (l, b, r, t) = (-25.0, -15.0, 15.0, 25.0);
+
+    // Nothing matches
+    assert!(!grid.any_in_rect(l, b, r, t, |cell| *cell > 0));
+    assert!(!grid.all_in_rect(l, b, r, t, |cell| *cell > 0));
+    assert_eq!(grid.count_in_rect(l, b, r, t, |cell| *cell > 0), 0);
+    assert_eq!(grid.find_in_rect(l, b, r, t, |cell| *cell > 0), None);
+
+    // Everything matches
+    assert!(grid.any_in_rect(l, b, r, t, |cell| *cell == 0));
+    assert!(grid.all_in_rect(l, b, r, t, |cell| *cell == 0));
+    assert_eq!(grid.count_in_rect(l, b, r, t, |cell| *cell == 0), 25);
+    assert_eq!(
+        grid.find_in_rect(l, b, r, t, |cell| *cell == 0),
+        Some(((2, 3), &0))
+    );
+
+    // Only the top/right corner of the region matches
+    grid[(6, 7)] = 1;
+    grid[(7, 7)] = 2;
+    assert!(grid.any_in_rect(l, b, r, t, |cell| *cell > 0));
+    assert!(!grid.all_in_rect(l, b, r, t, |cell| *cell > 0));
+    assert!(!grid.all_in_rect(l, b, r, t, |cell| *cell == 0));
+    assert_eq!(grid.count_in_rect(l, b, r, t, |cell| *cell > 0), 1);
+    assert_eq!(
+        grid.find_in_rect(l, b, r, t, |cell| *cell > 0),
+        Some(((6, 7), &1))
+    );
+
+    // Short-circuits on the first match
+    let mut checked = 0;
+    assert!(grid.any_in_rect(l, b, r, t, |_| {
+        checked += 1;
+        true
+    }));
+    assert_eq!(checked, 1);
+
+    // Rectangles outside the grid
+    assert!(!grid.any_in_rect(200.0, 200.0, 300.0, 300.0, |_| true));
+    assert!(grid.all_in_rect(200.0, 200.0, 300.0, 300.0, |_| false));
+    assert_eq!(grid.count_in_rect(200.0, 200.0, 300.0, 300.0, |_| true), 0);
+}
+
+#[test]
+fn position_of() {
+    let mut grid = Grid::new_with(50.0, 40.0, 5, 4, false, || '.');
+    // No matches
+    assert_eq!(grid.position_of(&'S'), None);
+    assert_eq!(grid.positions_of(&'C').count(), 0);
+
+    // A single match
+    grid[(3, 2)] = 'S';
+    assert_eq!(grid.position_of(&'S'), Some((3, 2)));
+    assert_eq!(grid.positions_of(&'S').collect::<Vec<_>>(), vec![(3, 2)]);
+
+    // Several matches, row by row from the bottom
+    grid[(4, 0)] = 'C';
+    grid[(0, 3)] = 'C';
+    grid[(1, 0)] = 'C';
+    grid[(2, 2)] = 'C';
+    assert_eq!(grid.position_of(&'C'), Some((1, 0)));
+    assert_eq!(
+        grid.positions_of(&'C').collect::<Vec<_>>(),
+        vec![(1, 0), (4, 0), (2, 2), (0, 3)]
+    );
+    assert_eq!(grid.positions_of(&'.').count(), 15);
+}
+
+#[test]
+fn fold_in_rect() {
+    let grid = Grid::new_with_coords(100.0, 100.0, 10, 10, true, |col, row| {
+        (col * 7 + row * 13) % 10
+    });
+    let (l, b, r, t) = (-25.0, -15.0, 15.0, 25.0);
+
+    let sum = grid.fold_in_rect(l, b, r, t, 0, |acc, _, cell| acc + cell);
+    let mut expected = 0;
+    for col in 0..10 {
+        for row in 0..10 {
+            let (cl, cb, cr, ct) = grid.cell_rect(col, row).unwrap();
+            if cr >= l && cl <= r && ct >= b && cb <= t {
+                expected += grid[(col, row)];
+            }
+        }
+    }
+    assert_eq!(sum, expected);
+
+    // Coordinates are passed along, in iteration order
+    let coords = grid.fold_in_rect(l, b, r, t, Vec::new(), |mut acc, coords, _| {
+        acc.push(coords);
+        acc
+    });
+    assert_eq!(coords, grid.iter_coords(l, b, r, t).collect::<Vec<_>>());
+
+    // Highest and lowest cells, clamped to the grid
+    let grid = Grid::new_with_coords(100.0, 100.0, 10, 10, true, |col, row| col * row);
+    assert_eq!(
+        grid.max_by_key_in_rect(-100.0, -100.0, 0.0, 0.0, |cell| *cell),
+        Some(((5, 5), &25))
+    );
+    assert_eq!(
+        grid.min_by_key_in_rect(-100.0, -100.0, 0.0, 0.0, |cell| *cell),
+        Some(((0, 0), &0))
+    );
+    assert_eq!(
+        grid.max_by_key_in_rect(10.0, 10.0, 500.0, 500.0, |cell| *cell),
+        Some(((9, 9), &81))
+    );
+
+    // Fully outside
+    assert_eq!(
+        grid.fold_in_rect(60.0, 60.0, 80.0, 80.0, 7, |acc, _, cell| acc + cell),
+        7
+    );
+    assert_eq!(
+        grid.max_by_key_in_rect(60.0, 60.0, 80.0, 80.0, |cell| *cell),
+        None
+    );
+    assert_eq!(
+        grid.min_by_key_in_rect(-80.0, 0.0, -60.0, 10.0, |cell| *cell),
+        None
+    );
+}
+
+#[test]
+fn prefix_sums() {
+    let mut rng = rand::thread_rng();
+    let grid = Grid::new_with(120.0, 80.0, 12, 8, true, || rng.gen_range(0..100u32));
+    let sums = grid.build_prefix_sums::<u32>();
+    let brute = |col_min: usize, row_min: usize, col_max: usize, row_max: usize| {
+        let mut sum = 0;
+        for col in col_min..=col_max {
+            for row in row_min..=row_max {
+                sum += grid[(col, row)];
+            }
+        }
+        sum
+    };
+
+    // Every index range, including single cells and the full grid
+    for col_min in 0..12 {
+        for col_max in col_min..12 {
+            for row_min in 0..8 {
+                for row_max in row_min..8 {
+                    assert_eq!(
+                        sums.sum_in_cells(col_min, row_min, col_max, row_max),
+                        brute(col_min, row_min, col_max, row_max)
+                    );
+                }
+            }
+        }
+    }
+    assert_eq!(sums.sum_in_cells(5, 5, 100, 100), brute(5, 5, 11, 7));
+    assert_eq!(sums.sum_in_cells(5, 5, 4, 7), 0);
+    assert_eq!(sums.sum_in_cells(12, 0, 12, 7), 0);
+
+    // Rectangles clamp like the rect iterators
+    assert_eq!(
+        sums.sum_in_rect(-60.0, -40.0, 60.0, 40.0),
+        brute(0, 0, 11, 7)
+    );
+    assert_eq!(
+        sums.sum_in_rect(-500.0, -500.0, 500.0, 500.0),
+        brute(0, 0, 11, 7)
+    );
+    assert_eq!(sums.sum_in_rect(-15.0, 5.0, -15.0, 5.0), grid[(4, 4)]);
+    let (l, b, r, t) = (-25.0, -15.0, 15.0, 25.0);
+    let expected: u32 = grid.iter_cells_in_rect(l, b, r, t).sum();
+    assert_eq!(sums.sum_in_rect(l, b, r, t), expected);
+    assert_eq!(sums.sum_in_rect(100.0, 0.0, 120.0, 10.0), 0);
+    assert_eq!(
+        sums.mean_in_rect(l, b, r, t),
+        Some(expected as f64 / grid.iter_cells_in_rect(l, b, r, t).count() as f64)
+    );
+    assert_eq!(sums.mean_in_rect(100.0, 0.0, 120.0, 10.0), None);
+
+    // Float cells with a wider accumulator
+    let grid = Grid::new_with_coords(100.0, 100.0, 10, 10, false, |col, row| {
+        (col as f32 * 0.1) + (row as f32 * 0.01)
+    });
+    let sums = grid.build_prefix_sums::<f64>();
+    let expected: f64 = grid.iter_all_cells().map(|cell| *cell as f64).sum();
+    assert!((sums.sum_in_cells(0, 0, 9, 9) - expected).abs() < 1e-9);
+    // Cells 0..=1 in both directions
+    assert!((sums.mean_in_rect(0.0, 0.0, 15.0, 15.0).unwrap() - 0.055).abs() < 1e-6);
+}
+
+#[test]
+fn gradient() {
+    // Rises 2.0 per cell to the right and 3.0 per cell upwards, with 10x5 cells
+    let ramp = Grid::new_with_coords(100.0, 50.0, 10, 10, true, |col, row| {
+        (col as Scalar * 2.0) + (row as Scalar * 3.0)
+    });
+    for (col, row) in ramp.iter_coords(-50.0, -25.0, 50.0, 25.0) {
+        let (x, y) = ramp.cell_center(col, row).unwrap();
+        let (dx, dy) = ramp.gradient_at(x, y, |height| *height).unwrap();
+        assert!((dx - 0.2).abs() < 1e-5, "{col},{row}: {dx}");
+        assert!((dy - 0.6).abs() < 1e-5, "{col},{row}: {dy}");
+        let slope = ramp.slope_at(x, y, |height| *height).unwrap();
+        assert!((slope - (0.2 as Scalar).hypot(0.6)).abs() < 1e-5);
+    }
+    assert_eq!(ramp.gradient_at(50.0, 0.0, |height| *height), None);
+
+    // Flat, including a single row
+    let flat = Grid::new_with(100.0, 10.0, 10, 1, false, || 4u8);
+    for col in 0..10 {
+        let (x, y) = flat.cell_center(col, 0).unwrap();
+        assert_eq!(flat.gradient_at(x, y, |h| *h as Scalar), Some((0.0, 0.0)));
+        assert_eq!(flat.slope_at(x, y, |h| *h as Scalar), Some(0.0));
+    }
+}
+
+#[test]
+fn resample() {
+    let mut grid = Grid::new_with_coords(80.0, 40.0, 8, 4, true, |col, row| col + (row * 10));
+    grid.set_position(5.0, 5.0);
+
+    // 2:1 downsample keeping the largest value
+    let coarse = grid
+        .resample(4, 2, |cells| **cells.iter().max().unwrap())
+        .unwrap();
+    assert_eq!((coarse.columns(), coarse.rows()), (4, 2));
+    assert_eq!((coarse.width(), coarse.height()), (80.0, 40.0));
+    assert_eq!(
+        (coarse.left(), coarse.bottom()),
+        (grid.left(), grid.bottom())
+    );
+    assert_eq!(coarse[(0, 0)], 11);
+    assert_eq!(coarse[(3, 0)], 17);
+    assert_eq!(coarse[(1, 1)], 33);
+    assert_eq!(coarse[(3, 1)], 37);
+    assert_eq!(coarse.get_cell(-20.0, 10.0), Some(&31));
+
+    // 10 -> 3 columns: each new cell gets every source cell it overlaps
+    let grid = Grid::new_with_coords(100.0, 10.0, 10, 1, false, |col, _| col);
+    let merged = grid
+        .resample(3, 1, |cells| {
+            cells.iter().map(|cell| **cell).collect::<Vec<_>>()
+        })
+        .unwrap();
+    assert_eq!(merged[(0, 0)], vec![0, 1, 2, 3]);
+    assert_eq!(merged[(1, 0)], vec![3, 4, 5, 6]);
+    assert_eq!(merged[(2, 0)], vec![6, 7, 8, 9]);
+
+    // Upsampling passes the single containing cell
+    let fine = grid
+        .resample(20, 2, |cells| (cells.len(), *cells[0]))
+        .unwrap();
+    assert_eq!(fine[(0, 0)], (1, 0));
+    assert_eq!(fine[(7, 1)], (1, 3));
+    assert_eq!(fine[(19, 0)], (1, 9));
+
+    assert_eq!(
+        grid.resample(0, 1, |_| 0).unwrap_err(),
+        GridError::ZeroColumns
+    );
+}
+
+#[test]
+fn pyramid() {
+    let mut rng = rand::thread_rng();
+    let grid = Grid::new_with(110.0, 70.0, 11, 7, true, || rng.gen_range(0..1000u32));
+    let max = |a: &u32, b: &u32, c: &u32, d: &u32| *a.max(b).max(c).max(d);
+    let levels = grid.build_pyramid(max);
+    let sizes: Vec<_> = levels.iter().map(|l| (l.columns(), l.rows())).collect();
+    assert_eq!(sizes, vec![(6, 4), (3, 2), (2, 1), (1, 1)]);
+
+    // Each cell is the reduction of the block below it, clamped on odd edges
+    let mut below = &grid;
+    for level in &levels {
+        for (cell, col, row) in level.iter_all_with_coords() {
+            let right = (col * 2 + 1).min(below.columns() - 1);
+            let top = (row * 2 + 1).min(below.rows() - 1);
+            let expected = max(
+                &below[(col * 2, row * 2)],
+                &below[(right, row * 2)],
+                &below[(col * 2, top)],
+                &below[(right, top)],
+            );
+            assert_eq!(*cell, expected);
+        }
+        assert_eq!((level.left(), level.top()), (grid.left(), grid.top()));
+        below = level;
+    }
+    assert_eq!(levels[3][(0, 0)], *grid.iter_all_cells().max().unwrap());
+
+    // Hierarchical queries match brute force
+    for _ in 0..200 {
+        let x = rng.gen_range(-70.0..70.0);
+        let y = rng.gen_range(-45.0..45.0);
+        let w = rng.gen_range(0.0..60.0);
+        let h = rng.gen_range(0.0..40.0);
+        let threshold = rng.gen_range(500..1000);
+        let expected = grid.any_in_rect(x, y, x + w, y + h, |cell| *cell > threshold);
+        let found = grid.query_pyramid(&levels, x, y, x + w, y + h, |cell| *cell > threshold);
+        assert_eq!(found, expected);
+    }
+    assert!(!grid.query_pyramid(&levels, 100.0, 100.0, 200.0, 200.0, |_| true));
+    assert!(grid.query_pyramid(&levels[..2], -55.0, -35.0, 55.0, 35.0, |_| true));
+
+    let single = Grid::new_with(10.0, 10.0, 1, 1, false, || 3u32);
+    let levels = single.build_pyramid(max);
+    assert!(levels.is_empty());
+    assert!(single.query_pyramid(&levels, 0.0, 0.0, 5.0, 5.0, |cell| *cell == 3));
+}
+
+#[test]
+#[should_panic(expected = "Grid: Error, pyramid level 0 is 4x2, expected 6x4 for a 11x7 grid")]
+fn pyramid_mismatched_levels() {
+    let grid = Grid::new_with(110.0, 70.0, 11, 7, true, || 0u32);
+    let other = Grid::new_with(70.0, 30.0, 7, 3, true, || 1000u32);
+    let max = |a: &u32, b: &u32, c: &u32, d: &u32| *a.max(b).max(c).max(d);
+    let levels = other.build_pyramid(max);
+    grid.query_pyramid(&levels, -55.0, -35.0, 55.0, 35.0, |_| true);
+}
+
+#[test]
+fn apply_kernel() {
+    // 1 2 3
+    // 4 5 6
+    // 7 8 9  <- bottom row
+    let values = [[7.0, 8.0, 9.0], [4.0, 5.0, 6.0], [1.0, 2.0, 3.0]];
+    let grid = Grid::new_with_coords(30.0, 30.0, 3, 3, false, |col, row| values[row][col]);
+    let blur = |_: &f32, window: &[((isize, isize), &f32)]| {
+        window.iter().map(|(_, value)| **value).sum::<f32>() / window.len() as f32
+    };
+
+    let skip = grid.apply_kernel(1, EdgeMode::Skip, blur);
+    assert_eq!(skip[(1, 1)], 5.0);
+    assert_eq!(skip[(0, 0)], (7.0 + 8.0 + 4.0 + 5.0) / 4.0);
+    assert_eq!(skip[(1, 0)], (7.0 + 8.0 + 9.0 + 4.0 + 5.0 + 6.0) / 6.0);
+    assert_eq!(skip[(2, 2)], (2.0 + 3.0 + 5.0 + 6.0) / 4.0);
+
+    let clamp = grid.apply_kernel(1, EdgeMode::Clamp, blur);
+    assert_eq!(clamp[(1, 1)], 5.0);
+    // 7 7 8 / 7 7 8 / 4 4 5
+    assert_eq!(
+        clamp[(0, 0)],
+        (7.0 * 4.0 + 8.0 * 2.0 + 4.0 * 2.0 + 5.0) / 9.0
+    );
+
+    // Every window holds the whole grid
+    let wrap = grid.apply_kernel(1, EdgeMode::Wrap, blur);
+    assert!(wrap.iter_all_cells().all(|cell| *cell == 5.0));
+
+    // Offsets are relative to the center, row by row from the bottom
+    let window = grid.apply_kernel(1, EdgeMode::Skip, |center, window| {
+        let offsets: Vec<_> = window.iter().map(|(offset, _)| *offset).collect();
+        let own = window
+            .iter()
+            .find(|(offset, _)| *offset == (0, 0))
+            .unwrap()
+            .1;
+        assert_eq!(own, center);
+        offsets
+    });
+    assert_eq!(window[(0, 0)], vec![(0, 0), (1, 0), (0, 1), (1, 1)]);
+    assert_eq!(window[(1, 1)].len(), 9);
+    assert_eq!(window[(1, 1)][0], (-1, -1));
+
+    // A zero radius only sees the cell itself
+    let same = grid.apply_kernel(0, EdgeMode::Clamp, |center, window| {
+        assert_eq!(window.len(), 1);
+        *center
+    });
+    assert_eq!(same, grid);
+}
+
+#[test]
+fn automaton_step() {
+    let life = |alive: &bool, view: NeighborView<'_, bool>| {
+        let count = view.count(|cell| *cell);
+        count == 3 || (*alive && count == 2)
+    };
+    let alive = |grid: &Grid<bool>| {
+        let mut cells: Vec<_> = grid.positions_of(&true).collect();
+        cells.sort();
+        cells
+    };
+
+    // Blinker, horizontal then vertical then horizontal again
+    let mut grid = Grid::new_with_coords(50.0, 50.0, 5, 5, false, |col, row| {
+        row == 2 && (1..=3).contains(&col)
+    });
+    let horizontal = alive(&grid);
+    let mut scratch = Vec::new();
+    grid.step_with(&mut scratch, life);
+    assert_eq!(alive(&grid), vec![(2, 1), (2, 2), (2, 3)]);
+    let capacity = scratch.capacity();
+    grid.step_with(&mut scratch, life);
+    assert_eq!(alive(&grid), horizontal);
+    assert_eq!(scratch.capacity(), capacity);
+
+    // Touching every edge: cells outside the grid count as dead
+    let grid = Grid::new_with_coords(30.0, 30.0, 3, 3, false, |col, row| col == 1 && row <= 2);
+    let mut automaton = grid.into_automaton();
+    automaton.step(life);
+    assert_eq!(alive(automaton.grid()), vec![(0, 1), (1, 1), (2, 1)]);
+    // The buffer is kept between steps
+    let buffer = automaton.grid().iter_all_cells().next().unwrap() as *const bool;
+    automaton.step(life);
+    automaton.step(life);
+    let cells = automaton.grid().iter_all_cells().next().unwrap() as *const bool;
+    assert_eq!(cells, buffer);
+    automaton.grid_mut().fill(false);
+    assert!(automaton.into_grid().iter_all_cells().all(|cell| !cell));
+
+    // Neighbor views
+    let grid = Grid::new_with_coords(30.0, 30.0, 3, 3, false, |col, row| (col, row));
+    let mut copy = Automaton::new(grid.clone());
+    copy.step(|cell, view| {
+        assert_eq!((view.col(), view.row()), *cell);
+        assert_eq!(view.get(0, 0), Some(cell));
+        let expected = if *cell == (1, 1) {
+            8
+        } else if cell.0 == 1 || cell.1 == 1 {
+            5
+        } else {
+            3
+        };
+        assert_eq!(view.iter().count(), expected);
+        view.get(-1, 1).copied().unwrap_or((9, 9))
+    });
+    let copy = copy.into_grid();
+    assert_eq!(copy[(1, 1)], (0, 2));
+    assert_eq!(copy[(0, 0)], (9, 9));
+    assert_eq!(copy[(2, 1)], (1, 2));
+}
+
+#[test]
+fn sparse_grid() {
+    let mut rng = rand::thread_rng();
+    let mut dense = Grid::new_with(120.0, 80.0, 12, 8, true, || {
+        rng.gen_bool(0.1).then(|| rng.gen_range(1..10u8))
+    });
+    let mut sparse = dense.to_sparse();
+    assert_eq!(
+        sparse.len(),
+        dense.iter_all_cells().filter(|c| c.is_some()).count()
+    );
+    assert_eq!(sparse.to_dense(), dense);
+
+    // Lookups and rect queries over present and absent cells
+    for _ in 0..200 {
+        let x = rng.gen_range(-70.0..70.0);
+        let y = rng.gen_range(-50.0..50.0);
+        assert_eq!(sparse.get_cell(x, y), dense.get_cell(x, y));
+        assert_eq!(sparse.get_cell_coords(x, y), dense.get_cell_coords(x, y));
+        let (r, t) = (x + rng.gen_range(0.0..50.0), y + rng.gen_range(0.0..30.0));
+        assert!(sparse
+            .iter_cells_in_rect(x, y, r, t)
+            .eq(dense.iter_cells_in_rect(x, y, r, t)));
+        assert!(sparse
+            .iter_with_coords_in_rect(x, y, r, t)
+            .eq(dense.iter_cells_in_rect(x, y, r, t).enumerate_coords()));
+
+        let value = rng.gen_bool(0.5).then_some(7);
+        sparse.modify_in_rect(x, y, r, t, |cell| *cell = value);
+        dense.modify_in_rect(x, y, r, t, |cell| *cell = value);
+        assert_eq!(sparse.to_dense(), dense);
+    }
+    // Cells cleared back to the default aren't stored
+    assert_eq!(
+        sparse.len(),
+        dense.iter_all_cells().filter(|c| c.is_some()).count()
+    );
+
+    // Setting, mutating and removing
+    let mut sparse = SparseGrid::<Option<u8>>::new(100.0, 100.0, 10, 10, false);
+    assert!(sparse.is_empty());
+    assert_eq!(sparse.get_cell(5.0, 5.0), Some(&None));
+    assert_eq!(sparse.get_cell(105.0, 5.0), None);
+    assert_eq!(sparse.set_cell(15.0, 25.0, Some(3)), Ok(None));
+    assert_eq!(sparse.set_cell(15.0, 25.0, Some(4)), Ok(Some(3)));
+    assert_eq!(sparse.set_cell(-1.0, 25.0, Some(4)), Err(Some(4)));
+    assert_eq!(sparse.get_cell_by_indices(1, 2), Some(&Some(4)));
+    assert!(sparse.get_cell_mut(55.0, 55.0).unwrap().is_none());
+    assert_eq!(sparse.len(), 2);
+    // Stored defaults don't affect equality
+    let mut logical = SparseGrid::<Option<u8>>::new(100.0, 100.0, 10, 10, false);
+    logical.set_cell(15.0, 25.0, Some(4)).unwrap();
+    assert_eq!(sparse, logical);
+    sparse.compact();
+    assert_eq!(sparse.len(), 1);
+    assert_eq!(
+        sparse.iter_stored().collect::<Vec<_>>(),
+        vec![((1, 2), &Some(4))]
+    );
+    assert_eq!(sparse.remove_cell_by_indices(1, 2), Some(Some(4)));
+    assert!(sparse.is_empty());
+    // Setting the default value removes the cell
+    sparse.set_cell(15.0, 25.0, Some(5)).unwrap();
+    assert_eq!(sparse.set_cell(15.0, 25.0, None), Ok(Some(5)));
+    assert_eq!(sparse.set_cell(35.0, 25.0, None), Ok(None));
+    assert!(sparse.is_empty());
+    assert_ne!(sparse, logical);
+    assert_eq!(
+        sparse.frame().cell_rect(1, 2),
+        Some((10.0, 20.0, 20.0, 30.0))
+    );
+    assert!(SparseGrid::<u8>::try_new(0.0, 10.0, 1, 1, false).is_err());
+}
+
+#[test]
+fn dirty_tracking() {
+    let mut grid = Grid::new_cloned(100.0, 100.0, 10, 10, false, 0u8);
+    let clean = grid.clone();
+    // Nothing is recorded until tracking is enabled
+    grid.set_cell(15.0, 15.0, 1).unwrap();
+    assert!(!grid.is_dirty_tracking_enabled());
+    assert_eq!(grid.take_dirty_rect(), None);
+
+    grid.enable_dirty_tracking();
+    grid.set_cell(15.0, 25.0, 1).unwrap();
+    *grid.get_cell_mut(45.0, 5.0).unwrap() = 2;
+    assert_eq!(grid.take_dirty_rect(), Some((1, 0, 4, 2)));
+    assert_eq!(grid.take_dirty_rect(), None);
+
+    grid[(7, 7)] = 3;
+    grid.fill_rect(20.0, 50.0, 39.0, 59.0, 4);
+    assert_eq!(grid.take_dirty_rect(), Some((2, 5, 7, 7)));
+
+    for cell in grid.iter_cells_in_rect_mut(60.0, 60.0, 75.0, 85.0) {
+        *cell = 5;
+    }
+    assert_eq!(
+        grid.take_dirty().collect::<Vec<_>>(),
+        vec![(6, 6), (7, 6), (6, 7), (7, 7), (6, 8), (7, 8)]
+    );
+
+    grid.modify_in_rect(0.0, 0.0, 5.0, 5.0, |cell| *cell += 1);
+    assert_eq!(grid.take_dirty_rect(), Some((0, 0, 0, 0)));
+
+    // Changing the dimensions marks every cell
+    grid.insert_row(0, true, |_, _| 0).unwrap();
+    assert_eq!(grid.take_dirty_rect(), Some((0, 0, 9, 10)));
+
+    // Tracking state doesn't affect equality
+    let mut tracked = clean.clone();
+    tracked.enable_dirty_tracking();
+    tracked.set_cell(15.0, 15.0, 0).unwrap();
+    assert_eq!(tracked, clean);
+    tracked.disable_dirty_tracking();
+    tracked.set_cell(15.0, 15.0, 0).unwrap();
+    assert_eq!(tracked.take_dirty_rect(), None);
+}
+
+#[test]
+fn diff() {
+    let a = Grid::new_with_coords(40.0, 30.0, 4, 3, false, |col, row| col * 10 + row);
+    let mut b = a.clone();
+    assert_eq!(a.diff(&b).unwrap().count(), 0);
+    assert_eq!(a.diff_count(&b), Ok(0));
+
+    b.set_cell_by_indices(2, 1, 99).unwrap();
+    assert_eq!(
+        a.diff(&b).unwrap().collect::<Vec<_>>(),
+        vec![(2, 1, &21, &99)]
+    );
+    b.set_cell_by_indices(0, 2, 98).unwrap();
+    assert_eq!(a.diff_count(&b), Ok(2));
+
+    // Patching with the changes makes the grids equal again
+    let changes: Vec<_> = a
+        .diff(&b)
+        .unwrap()
+        .map(|(col, row, _, new)| (col, row, *new))
+        .collect();
+    assert_eq!(changes, vec![(0, 2, 98), (2, 1, 99)]);
+    let mut patched = a.clone();
+    patched.apply_diff(changes).unwrap();
+    assert_eq!(patched, b);
+
+    assert_eq!(
+        patched.apply_diff([(4, 0, 1)]),
+        Err(GridError::IndicesOutOfRange { col: 4, row: 0 })
+    );
+    let other = Grid::new_cloned(40.0, 30.0, 3, 3, false, 0);
+    assert!(a.diff(&other).is_err());
+    assert_eq!(
+        a.diff_count(&other),
+        Err(GridError::ShapeMismatch {
+            expected: (4, 3),
+            found: (3, 3)
+        })
+    );
+}
+
+#[test]
+fn cell_refs() {
+    let mut grid = Grid::new_with_coords(100.0, 50.0, 10, 5, true, |col, row| col * 10 + row);
+    grid.set_position(5.0, 5.0);
+    let refs: Vec<_> = grid.iter_refs_in_rect(-20.0, -10.0, 9.0, 4.0).collect();
+    assert_eq!(refs.len(), 8);
+    for cell in &refs {
+        let (col, row) = cell.coords();
+        assert_eq!(*cell.value(), col * 10 + row);
+        let (left, bottom, right, top) = cell.rect();
+        assert_eq!(right - left, grid.cell_width());
+        assert_eq!(top - bottom, grid.cell_height());
+        assert_eq!(Some(cell.rect()), grid.cell_rect(col, row));
+        assert_eq!(Some(cell.center()), grid.cell_center(col, row));
+    }
+    // Pivot at the center, moved by (5, 5)
+    assert_eq!(refs[0].coords(), (2, 1));
+    assert_eq!(refs[0].rect(), (-25.0, -10.0, -15.0, 0.0));
+    assert_eq!(
+        grid.iter_refs_in_rect(-20.0, -10.0, 9.0, 4.0)
+            .y_down()
+            .map(|cell| cell.coords())
+            .next(),
+        Some((2, 2))
+    );
+
+    for mut cell in grid.iter_refs_in_rect_mut(-20.0, -10.0, 9.0, 4.0) {
+        let (col, _) = cell.coords();
+        *cell.value_mut() += 100 * col;
+    }
+    assert_eq!(grid.get_cell_by_indices(4, 2), Some(&442));
+    assert_eq!(grid.get_cell_by_indices(6, 2), Some(&62));
+}
+
+#[test]
+fn cursor() {
+    let mut grid = Grid::new_with_coords(40.0, 30.0, 4, 3, false, |col, row| col * 10 + row);
+    assert!(grid.cursor(4, 0).is_none());
+
+    // Walk around the border, counter-clockwise from the bottom-left corner
+    let mut cursor = grid.cursor(0, 0).unwrap();
+    assert!(!cursor.left());
+    assert!(!cursor.down());
+    let mut visited = vec![*cursor.value()];
+    for step in [Cursor::right, Cursor::up, Cursor::left, Cursor::down] {
+        while step(&mut cursor) {
+            visited.push(*cursor.value());
+        }
+    }
+    assert_eq!(visited, [0, 10, 20, 30, 31, 32, 22, 12, 2, 1, 0]);
+    assert_eq!(cursor.coords(), (0, 0));
+
+    let mut cursor = grid.cursor_mut(3, 2).unwrap();
+    assert!(!cursor.up());
+    assert!(!cursor.right());
+    assert_eq!(cursor.coords(), (3, 2));
+    while cursor.down() {
+        *cursor.value_mut() += 100;
+    }
+    assert_eq!(*cursor.value(), 130);
+    assert!(grid.iter_column(3).eq(&[130, 131, 32]));
+}
+
+#[test]
+fn option_cells() {
+    let mut grid = Grid::<Option<char>>::new(100.0, 100.0, 10, 10, true);
+    assert!(!grid.is_occupied(5.0, 5.0));
+    assert_eq!(grid.insert_at(5.0, 5.0, 'a'), Ok(None));
+    assert!(grid.is_occupied(5.0, 5.0));
+    assert_eq!(grid.insert_at(5.0, 5.0, 'b'), Ok(Some('a')));
+    assert_eq!(grid.get_cell(5.0, 5.0), Some(&Some('b')));
+
+    // Occupied cells keep their value, vacant ones are filled
+    assert_eq!(grid.get_or_insert_with_at(5.0, 5.0, || 'c'), Some(&mut 'b'));
+    assert_eq!(
+        grid.get_or_insert_with_at(-5.0, 5.0, || 'c'),
+        Some(&mut 'c')
+    );
+    assert_eq!(grid.remove_at(-5.0, 5.0), Some('c'));
+    assert_eq!(grid.remove_at(-5.0, 5.0), None);
+    assert!(!grid.is_occupied(-5.0, 5.0));
+
+    // Out of range
+    assert_eq!(grid.insert_at(50.0, 5.0, 'd'), Err('d'));
+    assert_eq!(grid.remove_at(50.0, 5.0), None);
+    assert!(!grid.is_occupied(-60.0, 5.0));
+    assert_eq!(grid.get_or_insert_with_at(5.0, 70.0, || 'e'), None);
+}
+
+#[test]
+fn occupancy() {
+    let mut rng = rand::thread_rng();
+    let mut grid = Grid::<Vec<u32>>::new(256.0, 256.0, 256, 256, false);
+    for id in 0..20 {
+        let (col, row) = (rng.gen_range(0..256), rng.gen_range(0..256));
+        grid.get_cell_by_indices_mut(col, row).unwrap().push(id);
+    }
+    let mut occupancy = grid.build_occupancy(|ids| !ids.is_empty());
+    assert_eq!(
+        occupancy.count_occupied(),
+        grid.iter_all_cells().filter(|ids| !ids.is_empty()).count()
+    );
+
+    // Same cells, in the same order, as a full scan
+    for _ in 0..100 {
+        let (x, y) = (rng.gen_range(-20.0..256.0), rng.gen_range(-20.0..256.0));
+        let (r, t) = (x + rng.gen_range(0.0..150.0), y + rng.gen_range(0.0..150.0));
+        let scan: Vec<_> = grid
+            .iter_cells_in_rect(x, y, r, t)
+            .enumerate_coords()
+            .filter(|(ids, _, _)| !ids.is_empty())
+            .map(|(_, col, row)| (col, row))
+            .collect();
+        assert!(occupancy.iter_occupied_in_rect(x, y, r, t).eq(scan));
+    }
+
+    // Explicit updates
+    occupancy.set_occupied(63, 10, true);
+    occupancy.set_occupied(64, 10, true);
+    occupancy.set_occupied(300, 10, true);
+    assert!(occupancy.is_occupied(64, 10));
+    assert!(!occupancy.is_occupied(300, 10));
+    assert!(occupancy
+        .iter_occupied_in_rect(63.0, 10.0, 64.5, 10.5)
+        .eq([(63, 10), (64, 10)]));
+    occupancy.set_occupied(63, 10, false);
+    assert!(occupancy
+        .iter_occupied_in_rect(63.0, 10.0, 64.5, 10.5)
+        .eq([(64, 10)]));
+    assert_eq!(
+        occupancy
+            .iter_occupied_in_rect(300.0, 0.0, 310.0, 10.0)
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn neighbor_pairs() {
+    let grid = Grid::new_with_coords(50.0, 40.0, 5, 4, false, |col, row| (col, row));
+    let mut pairs = Vec::new();
+    grid.for_each_neighbor_pair(|a, b| pairs.push((*a, *b)));
+
+    // Every cell with itself, and every adjacent pair, exactly once
+    let mut expected = Vec::new();
+    for a in grid.iter_all_cells() {
+        for b in grid.iter_all_cells() {
+            if a <= b && a.0.abs_diff(b.0) <= 1 && a.1.abs_diff(b.1) <= 1 {
+                expected.push((*a, *b));
+            }
+        }
+    }
+    let mut sorted: Vec<_> = pairs
+        .iter()
+        .map(|&(a, b)| if a <= b { (a, b) } else { (b, a) })
+        .collect();
+    sorted.sort();
+    expected.sort();
+    assert_eq!(pairs.len(), 20 + 4 * 4 + 5 * 3 + 2 * 4 * 3);
+    assert_eq!(sorted, expected);
+
+    // Restricted to a 2x2 block: each cell with itself, plus the 6 pairs between them
+    let mut count = 0;
+    grid.for_each_pair_in_rect(10.0, 10.0, 25.0, 25.0, |a, b| {
+        for (col, row) in [a, b] {
+            assert!((1..=2).contains(col) && (1..=2).contains(row));
+        }
+        count += 1;
+    });
+    assert_eq!(count, 10);
+    grid.for_each_pair_in_rect(60.0, 0.0, 70.0, 10.0, |_, _| panic!());
+}
+
+#[test]
+fn hashing() {
+    use core::hash::{Hash, Hasher};
+    use std::collections::hash_map::DefaultHasher;
+
+    fn full_hash(grid: &Grid<u8>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        grid.hash(&mut hasher);
+        hasher.finish()
+    }
+    fn content_hash(grid: &Grid<u8>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        grid.content_hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let a = Grid::new_with_coords(40.0, 30.0, 4, 3, true, |col, row| (col * 3 + row) as u8);
+    let mut b = a.clone();
+    b.enable_dirty_tracking();
+    assert_eq!(full_hash(&a), full_hash(&b));
+    assert_eq!(content_hash(&a), content_hash(&b));
+
+    b.set_cell_by_indices(1, 1, 99).unwrap();
+    assert_ne!(full_hash(&a), full_hash(&b));
+    assert_ne!(content_hash(&a), content_hash(&b));
+
+    // Moving changes the full hash only
+    let mut c = a.clone();
+    c.set_position(10.0, 0.0);
+    assert_ne!(full_hash(&a), full_hash(&c));
+    assert_eq!(content_hash(&a), content_hash(&c));
+
+    // Negative zero compares equal, so it hashes equally
+    c.set_position(-0.0, 0.0);
+    assert_eq!(a, c);
+    assert_eq!(full_hash(&a), full_hash(&c));
+}
+
+#[test]
+fn debug_output() {
+    let grid = Grid::new_with_coords(2.0, 3.0, 2, 3, false, |col, row| col * 10 + row);
+    assert_eq!(
+        format!("{:?}", grid),
+        "Grid { width: 2.0, height: 3.0, columns: 2, rows: 3, pivot: (0.0, 0.0), \
+         position: (0.0, 0.0), cells: 6 }"
+    );
+    assert!(format!("{:#?}", grid).contains("sample: [\n        0,\n        1,"));
+    assert_eq!(
+        format!("{:?}", grid.debug_cells()),
+        "[2, 12]\n[1, 11]\n[0, 10]\n"
+    );
+
+    // Bounded regardless of the number of cells
+    let large = Grid::<u64>::new(512.0, 512.0, 512, 512, true);
+    assert!(format!("{:?}", large).len() < 200);
+    assert!(format!("{:#?}", large).len() < 500);
+}
+
+#[test]
+fn column_slices() {
+    let mut grid = Grid::new_with_coords(30.0, 20.0, 3, 2, false, |col, row| col * 10 + row);
+    assert_eq!(grid.column_slice(1), Some(&[10, 11][..]));
+    assert_eq!(grid.column_slice(3), None);
+    assert!(grid
+        .iter_column_slices()
+        .eq([&[0, 1][..], &[10, 11], &[20, 21]]));
+
+    grid.enable_dirty_tracking();
+    grid.column_slice_mut(2).unwrap().reverse();
+    assert_eq!(grid.take_dirty_rect(), Some((2, 0, 2, 1)));
+    assert_eq!(grid.get_cell_by_indices(2, 0), Some(&21));
+    assert!(grid.column_slice_mut(3).is_none());
+    assert_eq!(grid.raw_data(), [0, 1, 10, 11, 21, 20]);
+}
+
+#[test]
+fn from_nested() {
+    let columns = vec![vec![0, 1, 2], vec![10, 11, 12]];
+    let grid = Grid::from_nested(20.0, 30.0, true, columns.clone()).unwrap();
+    assert_eq!((grid.columns(), grid.rows()), (2, 3));
+    assert_eq!(grid.get_cell(5.0, 14.0), Some(&12));
+    assert!(grid
+        .iter_column_slices()
+        .eq(columns.iter().map(Vec::as_slice)));
+
+    let grid = Grid::try_from(columns).unwrap();
+    assert_eq!((grid.width(), grid.height()), (2.0, 3.0));
+    assert_eq!(grid.get_cell(1.5, 0.5), Some(&10));
+
+    // Ragged and empty input
+    let ragged = vec![vec![0, 1], vec![2, 3], vec![4]];
+    assert_eq!(
+        Grid::from_nested(30.0, 20.0, false, ragged),
+        Err(GridError::RaggedColumn {
+            col: 2,
+            expected: 2,
+            found: 1
+        })
+    );
+    assert_eq!(
+        Grid::<u8>::try_from(Vec::new()),
+        Err(GridError::ZeroColumns)
+    );
+    assert_eq!(
+        Grid::<u8>::try_from(vec![Vec::new()]),
+        Err(GridError::ZeroRows)
+    );
+}
+
+#[test]
+fn validate_invariants() {
+    let grid = Grid::new_cloned(100.0, 50.0, 10, 5, true, 0u8);
+    assert_eq!(grid.validate(), Ok(()));
+    let mut pivot_outside = grid.clone();
+    pivot_outside.set_pivot(2.0, -1.0);
+    assert_eq!(pivot_outside.validate(), Ok(()));
+
+    let mut corrupt = grid.clone();
+    corrupt.data.pop();
+    assert_eq!(
+        corrupt.validate(),
+        Err(GridError::CellCountMismatch {
+            expected: 50,
+            found: 49
+        })
+    );
+
+    let mut corrupt = grid.clone();
+    corrupt.y = Scalar::NAN;
+    assert_eq!(
+        corrupt.validate(),
+        Err(GridError::NonFiniteValue { field: "y" })
+    );
+
+    let mut corrupt = grid.clone();
+    corrupt.cell_height = 11.0;
+    assert_eq!(
+        corrupt.validate(),
+        Err(GridError::CellSizeMismatch {
+            field: "cell_height",
+            expected: 10.0,
+            found: 11.0
+        })
+    );
+
+    let mut corrupt = grid.clone();
+    corrupt.width = Scalar::INFINITY;
+    assert_eq!(corrupt.validate(), Err(GridError::NonFiniteSize));
+}
+
+#[test]
+fn iter_skipping() {
+    let grid = Grid::new_with_coords(50.0, 40.0, 5, 4, false, |col, row| (col, row));
+    // Columns 1 to 3, rows 1 to 3
+    let rect = (15.0, 15.0, 35.0, 35.0);
+
+    let mut iter = grid.iter_cells_in_rect(rect.0, rect.1, rect.2, rect.3);
+    let mut visited = vec![*iter.next().unwrap()];
+    iter.skip_row();
+    visited.extend(iter.by_ref().take(2).copied());
+    iter.skip_row();
+    visited.extend(iter.by_ref().copied());
+    assert_eq!(visited, [(1, 1), (1, 2), (2, 2), (1, 3), (2, 3), (3, 3)]);
+
+    // Downwards, skipping the rest of the top row, then jumping ahead
+    let mut iter = grid
+        .iter_cells_in_rect(rect.0, rect.1, rect.2, rect.3)
+        .y_down();
+    assert_eq!(iter.next(), Some(&(1, 3)));
+    iter.skip_row();
+    assert_eq!(iter.next(), Some(&(1, 2)));
+    assert_eq!(iter.len(), 5);
+    iter.skip_to(0, 1);
+    assert_eq!(iter.len(), 3);
+    // Behind the front does nothing
+    iter.skip_to(3, 3);
+    assert_eq!(iter.next(), Some(&(1, 1)));
+    // Clamped to the rect
+    iter.skip_to(3, 0);
+    assert_eq!(iter.next(), Some(&(3, 1)));
+    assert_eq!(iter.next(), None);
+
+    // Past the back cursor finishes
+    let mut iter = grid.iter_cells_in_rect(rect.0, rect.1, rect.2, rect.3);
+    assert_eq!(iter.next_back(), Some(&(3, 3)));
+    iter.skip_to(3, 3);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn iter_clone_and_reset() {
+    let grid = Grid::new_with_coords(30.0, 30.0, 3, 3, false, |col, row| (col, row));
+    let all: Vec<_> = grid.iter_all_cells().copied().collect();
+
+    // A clone continues from the same cell, independently
+    let mut iter = grid.iter_all_cells();
+    iter.nth(3);
+    let rest = iter.clone();
+    assert!(iter.copied().eq(all[4..].iter().copied()));
+    assert!(rest.copied().eq(all[4..].iter().copied()));
+
+    // Two passes over the same region
+    let mut iter = grid
+        .iter_cells_in_rect(0.0, 10.0, 15.0, 25.0)
+        .enumerate_coords();
+    let first = iter.clone().count();
+    assert_eq!(iter.by_ref().count(), first);
+    iter.reset();
+    assert_eq!(iter.next(), Some((&(0, 1), 0, 1)));
+
+    // y_down after partial consumption starts over from the top
+    let mut iter = grid.iter_all_cells();
+    iter.next_back();
+    iter.next();
+    let down: Vec<_> = iter.y_down().copied().collect();
+    assert_eq!(down.len(), 9);
+    assert_eq!(down[0], (0, 2));
+    let mut iter = grid.iter_all_cells().y_down();
+    iter.next();
+    iter.reset();
+    assert_eq!(iter.next(), Some(&(0, 2)));
+
+    // Empty rects stay empty
+    let mut iter = grid.iter_cells_in_rect(100.0, 100.0, 110.0, 110.0);
+    iter.reset();
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+#[should_panic]
+fn iter_mut_y_down_after_next() {
+    let mut grid = Grid::new_cloned(30.0, 30.0, 3, 3, false, 0);
+    let mut iter = grid.iter_all_cells_mut();
+    iter.next();
+    let _ = iter.y_down();
+}
+
+#[test]
+fn iter_order() {
+    let grid = Grid::new_with_coords(50.0, 40.0, 5, 4, false, |col, row| (col, row));
+    // Columns 1 to 3, rows 1 to 2
+    let region = || grid.iter_cells_in_rect(10.0, 10.0, 35.0, 25.0);
+    let collect = |iter: IterGridRect<'_, (usize, usize)>| iter.copied().collect::<Vec<_>>();
+
+    assert_eq!(
+        collect(region()),
+        [(1, 1), (2, 1), (3, 1), (1, 2), (2, 2), (3, 2)]
+    );
+    assert_eq!(
+        collect(region().y_down()),
+        [(1, 2), (2, 2), (3, 2), (1, 1), (2, 1), (3, 1)]
+    );
+    assert_eq!(
+        collect(region().x_right_to_left()),
+        [(3, 1), (2, 1), (1, 1), (3, 2), (2, 2), (1, 2)]
+    );
+    assert_eq!(
+        collect(region().x_right_to_left().y_down()),
+        [(3, 2), (2, 2), (1, 2), (3, 1), (2, 1), (1, 1)]
+    );
+    assert_eq!(
+        collect(region().column_major()),
+        [(1, 1), (1, 2), (2, 1), (2, 2), (3, 1), (3, 2)]
+    );
+    assert_eq!(
+        collect(region().column_major().y_down().x_right_to_left()),
+        [(3, 2), (3, 1), (2, 2), (2, 1), (1, 2), (1, 1)]
+    );
+
+    // Reversing, skipping and the other rect iterators follow the same order
+    assert_eq!(region().column_major().rev().copied().next(), Some((3, 2)));
+    let mut iter = region().column_major();
+    iter.next();
+    iter.skip_row();
+    assert_eq!(iter.next(), Some(&(2, 1)));
+    assert!(grid
+        .iter_coords(10.0, 10.0, 35.0, 25.0)
+        .x_right_to_left()
+        .eq(collect(region().x_right_to_left())));
+    let mut copy = grid.clone();
+    assert!(copy
+        .iter_cells_in_rect_mut(10.0, 10.0, 35.0, 25.0)
+        .column_major()
+        .map(|cell| *cell)
+        .eq(collect(region().column_major())));
+}
+
+#[test]
+fn cell_ids() {
+    let mut grid = Grid::new_with_coords(40.0, 30.0, 4, 3, false, |col, row| (col, row));
+    for (col, row) in grid.iter_coords(0.0, 0.0, 40.0, 30.0) {
+        let id = grid.cell_id(col, row).unwrap();
+        assert_eq!(grid.coords_of(id), Some((col, row)));
+        assert_eq!(grid.get_by_id(id), Some(&(col, row)));
+    }
+    assert!(grid.cell_id(4, 0).is_none());
+    assert!(grid.cell_id(0, 3).is_none());
+
+    let ids: Vec<CellId> = grid.iter_ids_in_rect(10.0, 10.0, 25.0, 25.0).collect();
+    assert_eq!(ids.len(), 4);
+    assert_eq!(grid.coords_of(ids[0]), Some((1, 1)));
+    assert_eq!(grid.coords_of(ids[3]), Some((2, 2)));
+
+    let id = grid.cell_id(2, 1).unwrap();
+    *grid.get_by_id_mut(id).unwrap() = (9, 9);
+    assert_eq!(grid.get_cell_by_indices(2, 1), Some(&(9, 9)));
+
+    // Ids are invalidated by dimension changes, even if the cell still exists
+    grid.set_dimensions(5, 3, || (0, 0)).unwrap();
+    assert!(grid.get_by_id(id).is_none());
+    assert!(grid.get_by_id_mut(id).is_none());
+    assert!(grid.coords_of(id).is_none());
+    let fresh = grid.cell_id(2, 1).unwrap();
+    assert_ne!(fresh, id);
+    assert_eq!(grid.get_by_id(fresh), Some(&(9, 9)));
+
+    // But not by changes that keep them
+    let mapped = grid.map(|cell| cell.0);
+    assert_eq!(mapped.get_by_id(fresh), Some(&9));
+}
+
+#[test]
+fn swept_rect() {
+    // 10 x 10 cells of 10 units, from (0, 0) to (100, 100)
+    let grid = Grid::new_with_coords(100.0, 100.0, 10, 10, false, |col, row| (col, row));
+    let swept = |from: (Scalar, Scalar), to: (Scalar, Scalar)| -> Vec<(usize, usize)> {
+        grid.iter_coords_swept_rect((4.0, 4.0), from, to).collect()
+    };
+
+    // Without motion it is a plain rect query
+    assert_eq!(
+        swept((15.0, 15.0), (15.0, 15.0)),
+        grid.iter_coords(11.0, 11.0, 19.0, 19.0).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        swept((10.0, 15.0), (10.0, 15.0)),
+        grid.iter_coords(6.0, 11.0, 14.0, 19.0).collect::<Vec<_>>()
+    );
+
+    // Horizontal, moving left: cells in order of entry
+    assert_eq!(
+        swept((45.0, 55.0), (15.0, 55.0)),
+        [(4, 5), (3, 5), (2, 5), (1, 5)]
+    );
+    // Straddling two rows, both cells of a column are entered at the same time
+    assert_eq!(
+        swept((5.0, 50.0), (25.0, 50.0)),
+        [(0, 4), (0, 5), (1, 4), (1, 5), (2, 4), (2, 5)]
+    );
+
+    // Diagonal across several rows: includes the cells clipped by the corners of the rect
+    let coords = swept((15.0, 15.0), (45.0, 35.0));
+    assert_eq!(coords.first(), Some(&(1, 1)));
+    assert_eq!(coords.last(), Some(&(4, 3)));
+    assert!(coords.contains(&(2, 1)) && coords.contains(&(3, 3)));
+    assert!(!coords.contains(&(1, 3)) && !coords.contains(&(4, 1)));
+    let mut unique = coords.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(unique.len(), coords.len());
+    // Every cell of the start and end rects is swept, and no cell outside their bounds
+    for (col, row) in grid
+        .iter_coords(11.0, 11.0, 19.0, 19.0)
+        .chain(grid.iter_coords(41.0, 31.0, 49.0, 39.0))
+    {
+        assert!(coords.contains(&(col, row)));
+    }
+    assert!(coords.iter().all(|&(col, row)| col <= 4 && row <= 3));
+
+    // Starting outside the grid, the first cells are the ones on its edge
+    assert_eq!(swept((-30.0, 25.0), (15.0, 25.0)), [(0, 2), (1, 2)]);
+    assert_eq!(swept((-30.0, 25.0), (-10.0, 25.0)), []);
+    assert_eq!(swept((125.0, -30.0), (125.0, 30.0)), []);
+    let cells: Vec<_> = grid
+        .iter_cells_swept_rect((4.0, 4.0), (55.0, 130.0), (55.0, 85.0))
+        .collect();
+    assert_eq!(cells, [((5, 9), &(5, 9)), ((5, 8), &(5, 8))]);
+}
+
+#[test]
+fn polygon_coverage() {
+    // Centered, 10 x 8 cells of 10 units
+    let grid = Grid::<u8>::new(100.0, 80.0, 10, 8, true);
+    // Every cell whose rect touches the convex polygon, by the separating axis test
+    let brute_force = |points: &[(Scalar, Scalar)]| -> Vec<(usize, usize)> {
+        let mut axes = vec![(1.0, 0.0), (0.0, 1.0)];
+        for (i, &(x0, y0)) in points.iter().enumerate() {
+            let (x1, y1) = points[(i + 1) % points.len()];
+            axes.push((y0 - y1, x1 - x0));
+        }
+        let project = |(ax, ay): (Scalar, Scalar), corners: &[(Scalar, Scalar)]| {
+            corners
+                .iter()
+                .fold((Scalar::MAX, Scalar::MIN), |(min, max), &(x, y)| {
+                    let d = (x * ax) + (y * ay);
+                    (min.min(d), max.max(d))
+                })
+        };
+        grid.iter_coords(-50.0, -40.0, 50.0, 40.0)
+            .filter(|&(col, row)| {
+                let (l, b, r, t) = grid.cell_rect(col, row).unwrap();
+                let corners = [(l, b), (r, b), (r, t), (l, t)];
+                axes.iter().all(|&axis| {
+                    let (cell_min, cell_max) = project(axis, &corners);
+                    let (poly_min, poly_max) = project(axis, points);
+                    cell_max >= poly_min && poly_max >= cell_min
+                })
+            })
+            .collect()
+    };
+
+    let triangles = [
+        [(-43.0, -37.0), (36.5, -12.0), (-8.0, 31.0)],
+        // Clockwise
+        [(-8.0, 31.0), (36.5, -12.0), (-43.0, -37.0)],
+        // Partially outside the grid
+        [(-75.0, 12.5), (21.0, 55.0), (3.0, -6.5)],
+        // Sliver thinner than a cell
+        [(-46.5, -33.0), (44.0, 27.5), (-45.0, -31.5)],
+        // Smaller than a cell
+        [(1.5, 2.5), (3.5, 2.5), (2.5, 4.5)],
+    ];
+    for [p0, p1, p2] in triangles {
+        let expected = brute_force(&[p0, p1, p2]);
+        assert!(!expected.is_empty());
+        let coords: Vec<_> = grid.iter_coords_in_triangle(p0, p1, p2).collect();
+        assert_eq!(coords, expected);
+    }
+    assert_eq!(
+        grid.iter_coords_in_triangle((1.5, 2.5), (3.5, 2.5), (2.5, 4.5))
+            .collect::<Vec<_>>(),
+        [(5, 4)]
+    );
+    // The sliver covers far fewer cells than its bounding rect
+    let [p0, p1, p2] = triangles[3];
+    let sliver = grid.iter_coords_in_triangle(p0, p1, p2).count();
+    assert!(sliver * 3 < grid.iter_coords(-46.5, -33.0, 44.0, 27.5).count());
+
+    let polygons: [&[(Scalar, Scalar)]; 3] = [
+        &[
+            (-20.5, -15.0),
+            (12.0, -31.5),
+            (38.5, 4.0),
+            (18.0, 33.5),
+            (-27.0, 22.0),
+        ],
+        &[
+            (-27.0, 22.0),
+            (18.0, 33.5),
+            (38.5, 4.0),
+            (12.0, -31.5),
+            (-20.5, -15.0),
+        ],
+        &[(-60.0, -60.0), (60.0, -60.0), (60.0, 60.0), (-60.0, 60.0)],
+    ];
+    for points in polygons {
+        let coords: Vec<_> = grid.iter_coords_in_convex_polygon(points).collect();
+        assert_eq!(coords, brute_force(points));
+    }
+    assert_eq!(grid.iter_coords_in_convex_polygon(polygons[2]).count(), 80);
+    assert_eq!(
+        grid.iter_coords_in_convex_polygon::<(Scalar, Scalar)>(&[])
+            .count(),
+        0
+    );
+    assert_eq!(
+        grid.iter_coords_in_triangle((60.0, 0.0), (70.0, 5.0), (65.0, 20.0))
+            .count(),
+        0
+    );
+    let cells = grid.iter_cells_in_convex_polygon(&[[-5.0, -5.0], [5.0, -5.0], [0.0, 5.0]]);
+    assert_eq!(cells.count(), 4);
+}
+
+#[test]
+fn ring_and_sector() {
+    use core::f64::consts::TAU;
+    let tau = TAU as Scalar;
+    // 12 x 10 cells of 10 units, from (-60, -50) to (60, 50)
+    let grid = Grid::<u8>::new(120.0, 100.0, 12, 10, true);
+    let in_ring = |inner: Scalar, outer: Scalar| {
+        move |x: Scalar, y: Scalar| {
+            let d = (((x - 3.0) * (x - 3.0)) + ((y + 2.0) * (y + 2.0))).sqrt();
+            d >= inner && d <= outer
+        }
+    };
+    let in_sector = |radius: Scalar, start: Scalar, end: Scalar| {
+        move |x: Scalar, y: Scalar| {
+            let (dx, dy) = (x - 3.0, y + 2.0);
+            let angle = dy.atan2(dx);
+            let span = (end - start).rem_euclid(tau);
+            (dx * dx) + (dy * dy) <= radius * radius && (angle - start).rem_euclid(tau) <= span
+        }
+    };
+    // Cells where any of a dense set of sample points is inside the shape
+    let sampled = |inside: &dyn Fn(Scalar, Scalar) -> bool| -> Vec<(usize, usize)> {
+        grid.iter_coords(-60.0, -50.0, 60.0, 50.0)
+            .filter(|&(col, row)| {
+                let (left, bottom, ..) = grid.cell_rect(col, row).unwrap();
+                (0..=40).any(|i| {
+                    (0..=40)
+                        .any(|j| inside(left + (i as Scalar / 4.0), bottom + (j as Scalar / 4.0)))
+                })
+            })
+            .collect()
+    };
+    // Every sampled cell is yielded, and every yielded cell is close to the shape
+    let check = |coords: Vec<(usize, usize)>,
+                 inside: &dyn Fn(Scalar, Scalar) -> bool,
+                 grown: &dyn Fn(Scalar, Scalar) -> bool| {
+        assert!(sampled(inside).iter().all(|cell| coords.contains(cell)));
+        let near = sampled(grown);
+        assert!(coords.iter().all(|cell| near.contains(cell)));
+    };
+    let ring: Vec<_> = grid.iter_coords_in_ring(3.0, -2.0, 17.0, 32.0).collect();
+    check(ring.clone(), &in_ring(17.0, 32.0), &in_ring(16.0, 33.0));
+    // The cells around the center are left out
+    assert!(!ring.contains(&grid.get_cell_coords(3.0, -2.0).unwrap()));
+    assert!(grid
+        .iter_coords_in_ring(3.0, -2.0, 0.0, 32.0)
+        .eq(grid.iter_coords_in_circle(3.0, -2.0, 32.0)));
+    assert_eq!(grid.iter_coords_in_ring(3.0, -2.0, 32.0, 17.0).count(), 0);
+
+    for (start, end) in [(0.3, 1.4), (2.0, 6.0), (-1.0, 1.0), (5.5, 0.5)] {
+        let sector: Vec<_> = grid
+            .iter_coords_in_sector(3.0, -2.0, 37.5, start, end)
+            .collect();
+        check(
+            sector,
+            &in_sector(37.5, start, end),
+            &in_sector(38.5, start - 0.05, end + 0.05),
+        );
+    }
+    // Straddling 0 / 2π: both sides of the positive x axis, nothing on the negative one
+    let wrapped: Vec<_> = grid
+        .iter_coords_in_sector(3.0, -2.0, 37.5, 5.5, 0.5)
+        .collect();
+    assert!(wrapped.contains(&grid.get_cell_coords(35.0, 8.0).unwrap()));
+    assert!(wrapped.contains(&grid.get_cell_coords(35.0, -12.0).unwrap()));
+    assert!(!wrapped.contains(&grid.get_cell_coords(-25.0, -2.0).unwrap()));
+    assert!(grid
+        .iter_coords_in_sector(3.0, -2.0, 37.5, 5.5, 0.5 + tau)
+        .eq(wrapped.iter().copied()));
+
+    assert_eq!(
+        grid.iter_coords_in_sector(3.0, -2.0, 37.5, 1.0, 1.0)
+            .count(),
+        0
+    );
+    assert!(grid
+        .iter_coords_in_sector(3.0, -2.0, 37.5, 1.0, 1.0 + tau)
+        .eq(grid.iter_coords_in_circle(3.0, -2.0, 37.5)));
+    assert_eq!(
+        grid.iter_cells_in_sector(3.0, -2.0, 9.0, 0.0, 0.1).count(),
+        2
+    );
+}
+
+#[test]
+fn cells_touching_point() {
+    // 4 x 4 cells of 10 units, from (-20, -20) to (20, 20)
+    let grid = Grid::<u8>::new(40.0, 40.0, 4, 4, true);
+    let touching = |x: Scalar, y: Scalar, epsilon: Scalar| {
+        grid.cells_touching_point(x, y, epsilon).collect::<Vec<_>>()
+    };
+
+    // Middle of a cell
+    assert_eq!(touching(5.0, 5.0, 0.0), [(2, 2)]);
+    assert_eq!(touching(5.0, 5.0, 1.0), [(2, 2)]);
+    // Interior vertical edge: both cells, while "get_cell_coords" picks the right one
+    assert_eq!(touching(0.0, 5.0, 0.0), [(1, 2), (2, 2)]);
+    assert_eq!(grid.get_cell_coords(0.0, 5.0), Some((2, 2)));
+    // Near the edge, within epsilon
+    assert_eq!(touching(0.4, 5.0, 0.5), [(1, 2), (2, 2)]);
+    assert_eq!(touching(0.6, 5.0, 0.5), [(2, 2)]);
+    // Corner shared by four cells
+    assert_eq!(touching(10.0, -10.0, 0.0), [(2, 0), (3, 0), (2, 1), (3, 1)]);
+    assert_eq!(grid.get_cell_coords(10.0, -10.0), Some((3, 1)));
+    assert_eq!(touching(9.8, -9.9, 0.25), [(2, 0), (3, 0), (2, 1), (3, 1)]);
+
+    // The grid's outer edges belong to the cells along them
+    assert_eq!(touching(20.0, 20.0, 0.0), [(3, 3)]);
+    assert_eq!(grid.get_cell_coords(20.0, 20.0), None);
+    assert_eq!(touching(-20.0, 0.0, 0.0), [(0, 1), (0, 2)]);
+    assert_eq!(touching(20.5, 0.0, 0.25), []);
+    assert_eq!(touching(-20.5, 0.0, 1.0), [(0, 1), (0, 2)]);
+}
+
+#[test]
+fn find_nearest() {
+    // 10 x 10 cells of 10 units, from (-50, -50) to (50, 50)
+    let mut grid = Grid::<u8>::new(100.0, 100.0, 10, 10, true);
+    *grid.get_cell_mut(3.0, 3.0).unwrap() = 1;
+    *grid.get_cell_mut(-27.0, 6.0).unwrap() = 2;
+    *grid.get_cell_mut(33.0, -35.0).unwrap() = 3;
+
+    // Match in the start cell
+    assert_eq!(
+        grid.find_nearest(1.0, 9.0, 100.0, |cell| *cell != 0),
+        Some(((5, 5), &1))
+    );
+    // Three rings out, closer than a cell in a nearer ring that isn't matched
+    assert_eq!(
+        grid.find_nearest(4.0, -25.0, 100.0, |cell| *cell > 1),
+        Some(((8, 1), &3))
+    );
+    assert_eq!(
+        grid.find_nearest(4.0, -25.0, 100.0, |cell| *cell == 2),
+        Some(((2, 5), &2))
+    );
+    // Nothing within the radius
+    assert_eq!(grid.find_nearest(4.0, -25.0, 20.0, |cell| *cell > 1), None);
+    assert_eq!(grid.find_nearest(4.0, -25.0, 100.0, |cell| *cell > 3), None);
+    assert_eq!(grid.find_nearest(4.0, -25.0, -1.0, |_| true), None);
+
+    // Equal distances go to the smallest ring, then the lowest row and column
+    let ties = Grid::new_with_coords(40.0, 40.0, 4, 4, false, |col, row| (col, row));
+    assert_eq!(
+        ties.find_nearest(20.0, 20.0, 10.0, |_| true),
+        Some(((2, 2), &(2, 2)))
+    );
+    assert_eq!(
+        ties.find_nearest(20.0, 20.0, 10.0, |&(col, row)| col + row == 3),
+        Some(((2, 1), &(2, 1)))
+    );
+
+    // Points outside the grid start from the nearest cell, but distances are from the point
+    assert_eq!(
+        grid.find_nearest(90.0, -35.0, 100.0, |cell| *cell != 0),
+        Some(((8, 1), &3))
+    );
+    assert_eq!(
+        grid.find_nearest(90.0, -35.0, 50.0, |cell| *cell != 0),
+        None
+    );
+    assert_eq!(grid.find_nearest(Scalar::NAN, 0.0, 100.0, |_| true), None);
+
+    // Edges at 0.0 don't slow down the search for a starting cell
+    grid.set_pivot_preset(Pivot::TopRight);
+    assert_eq!(
+        grid.find_nearest(-45.0, -45.0, 1000.0, |cell| *cell == 1),
+        Some(((5, 5), &1))
+    );
+}
+
+#[test]
+fn grid_view() {
+    let grid = Grid::new_with_coords(80.0, 60.0, 8, 6, false, |col, row| (col, row));
+    let view = grid.view(2, 1, 5, 3).unwrap();
+    assert_eq!((view.columns(), view.rows()), (4, 3));
+    assert_eq!(view.get(0, 0), Some(&(2, 1)));
+    assert_eq!(view.get(3, 2), Some(&(5, 3)));
+    assert_eq!(view.get(4, 0), None);
+    assert_eq!(view.get(0, 3), None);
+
+    // Local <-> parent coordinates round-trip
+    for col in 0..view.columns() {
+        for row in 0..view.rows() {
+            let parent = view.to_parent_coords(col, row).unwrap();
+            assert_eq!(
+                view.get(col, row),
+                grid.get_cell_by_indices(parent.0, parent.1)
+            );
+            assert_eq!(view.to_local_coords(parent.0, parent.1), Some((col, row)));
+        }
+    }
+    assert_eq!(view.to_parent_coords(4, 0), None);
+    assert_eq!(view.to_local_coords(1, 1), None);
+    assert_eq!(view.to_local_coords(6, 1), None);
+
+    assert_eq!(view.iter().count(), 12);
+    assert!(view.iter().eq(grid
+        .iter_coords(20.0, 10.0, 59.0, 39.0)
+        .map(|(col, row)| { grid.get_cell_by_indices(col, row).unwrap() })));
+    for (cell, col, row) in view.iter_with_coords() {
+        assert_eq!(view.to_parent_coords(col, row), Some(*cell));
+    }
+
+    // Views only borrow the grid immutably, so several can coexist with other reads
+    let whole = grid.view(0, 0, 7, 5).unwrap();
+    let copy = view;
+    assert_eq!(whole.get(2, 1), copy.get(0, 0));
+    assert_eq!(grid.get_cell_by_indices(2, 1), view.get(0, 0));
+
+    // Invalid regions
+    assert!(matches!(
+        grid.view(2, 1, 8, 3),
+        Err(GridError::IndicesOutOfRange { .. })
+    ));
+    assert!(matches!(
+        grid.view(2, 1, 5, 6),
+        Err(GridError::IndicesOutOfRange { .. })
+    ));
+    assert!(matches!(grid.view(5, 1, 2, 3), Err(GridError::ZeroColumns)));
+    assert!(matches!(grid.view(2, 3, 5, 1), Err(GridError::ZeroRows)));
+}
+
+#[test]
+fn grid_view_mut() {
+    let mut grid = Grid::<u8>::new(80.0, 60.0, 8, 6, false);
+    grid.enable_dirty_tracking();
+    let mut view = grid.view_mut(1, 1, 6, 4).unwrap();
+    assert_eq!((view.columns(), view.rows()), (6, 4));
+    *view.get_mut(0, 0).unwrap() = 9;
+    assert_eq!(view.get(0, 0), Some(&9));
+    assert!(view.get_mut(6, 0).is_none());
+    view.modify(|cell| *cell += 1);
+
+    // Quadrants from nested splits, each filled with its own value
+    let (left, right) = view.split_columns_at(2);
+    let (mut bottom_left, mut top_left) = left.split_rows_at(1);
+    let (mut bottom_right, mut top_right) = right.split_rows_at(1);
+    assert_eq!((top_left.columns(), top_left.rows()), (2, 3));
+    assert_eq!((bottom_right.columns(), bottom_right.rows()), (4, 1));
+    // The quadrants are disjoint and cover the whole view
+    let mut covered = Vec::new();
+    for quadrant in [&bottom_left, &top_left, &bottom_right, &top_right] {
+        for col in 0..quadrant.columns() {
+            for row in 0..quadrant.rows() {
+                let parent = quadrant.to_parent_coords(col, row).unwrap();
+                assert_eq!(
+                    quadrant.to_local_coords(parent.0, parent.1),
+                    Some((col, row))
+                );
+                covered.push(parent);
+            }
+        }
+    }
+    covered.sort();
+    covered.dedup();
+    assert_eq!(covered.len(), 24);
+    assert_eq!(bottom_left.get(0, 0), Some(&10));
+    bottom_left.fill(2);
+    top_left.fill(3);
+    // Views can be edited from other threads
+    std::thread::scope(|scope| {
+        scope.spawn(|| bottom_right.fill(4));
+        scope.spawn(|| top_right.fill(5));
+    });
+    assert_eq!(top_right.get(3, 2), Some(&5));
+
+    // No bleed across the quadrant boundaries, or outside the view
+    for (col, row) in grid.iter_coords(0.0, 0.0, 80.0, 60.0) {
+        let expected = match (col, row) {
+            (1..=2, 1) => 2,
+            (1..=2, 2..=4) => 3,
+            (3..=6, 1) => 4,
+            (3..=6, 2..=4) => 5,
+            _ => 0,
+        };
+        assert_eq!(grid.get_cell_by_indices(col, row), Some(&expected));
+    }
+    assert_eq!(grid.take_dirty_rect(), Some((1, 1, 6, 4)));
+
+    // Empty halves are allowed
+    let view = grid.view_mut(0, 0, 7, 5).unwrap();
+    let (empty, whole) = view.split_rows_at(0);
+    assert_eq!((empty.rows(), whole.rows()), (0, 6));
+    assert_eq!(empty.get(0, 0), None);
+    assert!(matches!(
+        grid.view_mut(0, 0, 8, 5),
+        Err(GridError::IndicesOutOfRange { .. })
+    ));
+}
+
+#[test]
+fn grid_view_mut_disjoint() {
+    // Every split, at any position, hands out each cell to exactly one view
+    let mut grid = Grid::<u8>::new(80.0, 60.0, 8, 6, false);
+    for split_col in 0..=6 {
+        for split_row in 0..=4 {
+            let view = grid.view_mut(1, 1, 6, 4).unwrap();
+            let (left, right) = view.split_columns_at(split_col);
+            let (bottom_left, top_left) = left.split_rows_at(split_row);
+            let (bottom_right, top_right) = right.split_rows_at(split_row);
+            let views = [&bottom_left, &top_left, &bottom_right, &top_right];
+            for (col, row) in (0..8).flat_map(|col| (0..6).map(move |row| (col, row))) {
+                let owners = views
+                    .iter()
+                    .filter(|view| view.to_local_coords(col, row).is_some())
+                    .count();
+                let inside = (1..=6).contains(&col) && (1..=4).contains(&row);
+                assert_eq!(owners, usize::from(inside));
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn grid_view_mut_split_out_of_range() {
+    let mut grid = Grid::<u8>::new(80.0, 60.0, 8, 6, false);
+    let view = grid.view_mut(1, 1, 6, 4).unwrap();
+    let _ = view.split_columns_at(7);
+}
+
+#[test]
+fn iter_nan_edges() {
+    let mut grid = Grid::new_with_coords(100.0, 100.0, 10, 10, false, |col, row| (col, row));
+    let nan = Scalar::NAN;
+    for (left, bottom, right, top) in [
+        (50.0, 0.0, nan, 10.0),
+        (nan, 0.0, 50.0, 10.0),
+        (0.0, 50.0, 10.0, nan),
+        (0.0, nan, 10.0, 50.0),
+        (nan, nan, nan, nan),
+    ] {
+        let iter = grid.iter_cells_in_rect(left, bottom, right, top);
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.count(), 0);
+        let iter = grid.iter_cells_in_rect_mut(left, bottom, right, top);
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.count(), 0);
+        assert_eq!(grid.iter_coords(left, bottom, right, top).count(), 0);
+    }
+    assert!(grid
+        .iter_all_cells()
+        .zip(grid.iter_coords(0.0, 0.0, 100.0, 100.0))
+        .all(|(cell, coords)| *cell == coords));
+}
+
+#[test]
+fn clamp_point_top_right_pivot() {
+    // The right and top edges are at 0.0, so the clamped values are tiny negative numbers
+    let mut grid = Grid::<u8>::new(100.0, 100.0, 10, 10, false);
+    grid.set_pivot_preset(Pivot::TopRight);
+    assert_eq!((grid.right(), grid.top()), (0.0, 0.0));
+    let (x, y) = grid.clamp_point(500.0, 500.0);
+    assert!(x < 0.0 && x > -0.001);
+    assert!(y < 0.0 && y > -0.001);
+    assert!(grid.contains_point(x, y));
+    assert_eq!(grid.get_cell_coords(x, y), Some((9, 9)));
+    assert_eq!(grid.clamp_point(-500.0, -500.0), (-100.0, -100.0));
+    assert_eq!(grid.clamp_point(-50.0, 500.0), (-50.0, y));
+
+    // Far from the origin as well
+    grid.set_position(1.0e6, -1.0e6);
+    let (x, y) = grid.clamp_point(2.0e6, 0.0);
+    assert!(grid.contains_point(x, y));
+    assert_eq!(grid.get_cell_coords(x, y), Some((9, 9)));
+}
@@ -0,0 +1,35 @@
+// glam versions of the rect and cell geometry methods. Point lookups already accept glam
+// vectors through the "_at" methods, since they convert into [x, y].
+use super::*;
+
+#[cfg(not(feature = "f64"))]
+use glam::Vec2;
+
+#[cfg(feature = "f64")]
+use glam::DVec2 as Vec2;
+
+impl<V> Grid<V> {
+    /// Same as "iter_cells_in_rect", with the rectangle given by its min (bottom/left) and
+    /// max (top/right) corners. Uses DVec2 with the "f64" feature.
+    pub fn iter_cells_in_rect_vec(&self, min: Vec2, max: Vec2) -> IterGridRect<'_, V> {
+        self.iter_cells_in_rect(min.x, min.y, max.x, max.y)
+    }
+
+    /// Same as "iter_cells_in_rect_mut", with the rectangle given by its min and max
+    /// corners.
+    pub fn iter_cells_in_rect_vec_mut(&mut self, min: Vec2, max: Vec2) -> IterGridRectMut<'_, V> {
+        self.iter_cells_in_rect_mut(min.x, min.y, max.x, max.y)
+    }
+
+    /// Same as "cell_center", returning a vector.
+    pub fn cell_center_vec(&self, col: usize, row: usize) -> Option<Vec2> {
+        let (x, y) = self.cell_center(col, row)?;
+        Some(Vec2::new(x, y))
+    }
+
+    /// Same as "cell_rect", returning the (min, max) corners of the cell.
+    pub fn cell_rect_vec(&self, col: usize, row: usize) -> Option<(Vec2, Vec2)> {
+        let (left, bottom, right, top) = self.cell_rect(col, row)?;
+        Some((Vec2::new(left, bottom), Vec2::new(right, top)))
+    }
+}
@@ -0,0 +1,277 @@
+use super::*;
+
+/// Read-only window over a rectangular region of a Grid, created with "Grid::view". Cells
+/// are addressed relative to the view, (0, 0) being its bottom/left cell.
+/// The view only hands out shared references, so cells can't be modified through it:
+/// ```compile_fail
+/// use gridstore::Grid;
+///
+/// let grid = Grid::<u8>::new(100.0, 100.0, 10, 10, false);
+/// let view = grid.view(2, 2, 5, 5).unwrap();
+/// *view.get(0, 0).unwrap() = 1;
+/// ```
+#[derive(Debug)]
+pub struct GridView<'a, V> {
+    grid: &'a Grid<V>,
+    col_min: usize,
+    row_min: usize,
+    columns: usize,
+    rows: usize,
+}
+
+// Derived Clone and Copy would require V: Clone
+impl<'a, V> Clone for GridView<'a, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, V> Copy for GridView<'a, V> {}
+
+impl<'a, V> GridView<'a, V> {
+    /// Number of columns in the view.
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Number of rows in the view.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns an optional reference to the cell at the view's (col, row) coordinates. None
+    /// if they are outside the view.
+    pub fn get(&self, col: usize, row: usize) -> Option<&'a V> {
+        let (col, row) = self.to_parent_coords(col, row)?;
+        Some(&self.grid.data[self.grid.flat_index(col, row)])
+    }
+
+    /// Returns an iterator with the cells of the view, row by row starting at the bottom row.
+    pub fn iter(&self) -> IterGridRect<'a, V> {
+        self.grid.iter_in_edges(Some(self.edges()))
+    }
+
+    /// Returns an iterator that yields (value, column, row) for the cells of the view, with
+    /// coordinates relative to the view, in the same order as "iter".
+    pub fn iter_with_coords(&self) -> impl Iterator<Item = (&'a V, usize, usize)> + 'a {
+        let (col_min, row_min) = (self.col_min, self.row_min);
+        self.iter()
+            .enumerate_coords()
+            .map(move |(cell, col, row)| (cell, col - col_min, row - row_min))
+    }
+
+    /// Converts coordinates relative to the view into the parent grid's coordinates. None if
+    /// they are outside the view.
+    pub fn to_parent_coords(&self, col: usize, row: usize) -> Option<(usize, usize)> {
+        (col < self.columns && row < self.rows).then(|| (self.col_min + col, self.row_min + row))
+    }
+
+    /// Converts the parent grid's coordinates into coordinates relative to the view. None if
+    /// the cell is outside the view.
+    pub fn to_local_coords(&self, col: usize, row: usize) -> Option<(usize, usize)> {
+        let col = col.checked_sub(self.col_min)?;
+        let row = row.checked_sub(self.row_min)?;
+        (col < self.columns && row < self.rows).then_some((col, row))
+    }
+
+    // The (left, bottom, right, top) cell indices of the view in the parent grid.
+    fn edges(&self) -> (usize, usize, usize, usize) {
+        (
+            self.col_min,
+            self.row_min,
+            self.col_min + self.columns - 1,
+            self.row_min + self.rows - 1,
+        )
+    }
+}
+
+/// Mutable window over a rectangular region of a Grid, created with "Grid::view_mut". Cells
+/// are addressed relative to the view, (0, 0) being its bottom/left cell. It can be split
+/// into disjoint views with "split_columns_at" and "split_rows_at", which can be edited
+/// independently (even from different threads). Views can't overlap:
+/// ```compile_fail,E0499
+/// use gridstore::Grid;
+///
+/// let mut grid = Grid::<u8>::new(100.0, 100.0, 10, 10, false);
+/// let mut a = grid.view_mut(0, 0, 5, 5).unwrap();
+/// let b = grid.view_mut(5, 5, 9, 9).unwrap();
+/// a.fill(1);
+/// ```
+#[derive(Debug)]
+pub struct GridViewMut<'a, V> {
+    // Start of the parent grid's storage
+    data: *mut V,
+    // Rows in the parent grid, i.e. the length of a stored column
+    stride: usize,
+    col_min: usize,
+    row_min: usize,
+    columns: usize,
+    rows: usize,
+    _marker: PhantomData<&'a mut V>,
+}
+
+// SAFETY: The view behaves like a "&mut" to its cells, and no other view can reach them.
+unsafe impl<'a, V: Send> Send for GridViewMut<'a, V> {}
+unsafe impl<'a, V: Sync> Sync for GridViewMut<'a, V> {}
+
+impl<'a, V> GridViewMut<'a, V> {
+    /// Number of columns in the view.
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Number of rows in the view.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns an optional reference to the cell at the view's (col, row) coordinates. None
+    /// if they are outside the view.
+    pub fn get(&self, col: usize, row: usize) -> Option<&V> {
+        let index = self.index(col, row)?;
+        // SAFETY: The index is inside the view, which has exclusive access to its cells.
+        Some(unsafe { &*self.data.add(index) })
+    }
+
+    /// Returns an optional mutable reference to the cell at the view's (col, row)
+    /// coordinates. None if they are outside the view.
+    pub fn get_mut(&mut self, col: usize, row: usize) -> Option<&mut V> {
+        let index = self.index(col, row)?;
+        // SAFETY: The index is inside the view, which has exclusive access to its cells.
+        Some(unsafe { &mut *self.data.add(index) })
+    }
+
+    /// Overwrites every cell of the view with a clone of "value".
+    pub fn fill(&mut self, value: V)
+    where
+        V: Clone,
+    {
+        self.modify(|cell| *cell = value.clone());
+    }
+
+    /// Allows a function to modify the contents of every cell of the view, column by
+    /// column.
+    pub fn modify<F>(&mut self, mut func: F)
+    where
+        F: FnMut(&mut V),
+    {
+        for col in 0..self.columns {
+            for row in 0..self.rows {
+                let index = ((self.col_min + col) * self.stride) + self.row_min + row;
+                // SAFETY: The index is inside the view, which has exclusive access to its
+                // cells.
+                func(unsafe { &mut *self.data.add(index) });
+            }
+        }
+    }
+
+    /// Splits the view into the columns left of "col" and the rest. Either view may be
+    /// empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if "col" is greater than the number of columns.
+    pub fn split_columns_at(self, col: usize) -> (GridViewMut<'a, V>, GridViewMut<'a, V>) {
+        assert!(
+            col <= self.columns,
+            "GridViewMut: Error, can't split {} columns at {}",
+            self.columns,
+            col
+        );
+        let left = GridViewMut {
+            columns: col,
+            ..self
+        };
+        let right = GridViewMut {
+            col_min: self.col_min + col,
+            columns: self.columns - col,
+            ..self
+        };
+        (left, right)
+    }
+
+    /// Splits the view into the rows below "row" and the rest. Either view may be empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if "row" is greater than the number of rows.
+    pub fn split_rows_at(self, row: usize) -> (GridViewMut<'a, V>, GridViewMut<'a, V>) {
+        assert!(
+            row <= self.rows,
+            "GridViewMut: Error, can't split {} rows at {}",
+            self.rows,
+            row
+        );
+        let bottom = GridViewMut { rows: row, ..self };
+        let top = GridViewMut {
+            row_min: self.row_min + row,
+            rows: self.rows - row,
+            ..self
+        };
+        (bottom, top)
+    }
+
+    /// Converts coordinates relative to the view into the parent grid's coordinates. None if
+    /// they are outside the view.
+    pub fn to_parent_coords(&self, col: usize, row: usize) -> Option<(usize, usize)> {
+        (col < self.columns && row < self.rows).then(|| (self.col_min + col, self.row_min + row))
+    }
+
+    /// Converts the parent grid's coordinates into coordinates relative to the view. None if
+    /// the cell is outside the view.
+    pub fn to_local_coords(&self, col: usize, row: usize) -> Option<(usize, usize)> {
+        let col = col.checked_sub(self.col_min)?;
+        let row = row.checked_sub(self.row_min)?;
+        (col < self.columns && row < self.rows).then_some((col, row))
+    }
+
+    // Offset of a cell from the start of the parent grid's storage.
+    fn index(&self, col: usize, row: usize) -> Option<usize> {
+        let (col, row) = self.to_parent_coords(col, row)?;
+        Some((col * self.stride) + row)
+    }
+}
+
+impl<V> Grid<V> {
+    /// Returns a read-only view of the cells between (col_min, row_min) and (col_max,
+    /// row_max), inclusive. Fails if the region is empty or not fully inside the grid.
+    pub fn view(
+        &self,
+        col_min: usize,
+        row_min: usize,
+        col_max: usize,
+        row_max: usize,
+    ) -> Result<GridView<'_, V>, GridError> {
+        self.check_region(col_min, row_min, col_max, row_max)?;
+        Ok(GridView {
+            grid: self,
+            col_min,
+            row_min,
+            columns: col_max - col_min + 1,
+            rows: row_max - row_min + 1,
+        })
+    }
+
+    /// Returns a mutable view of the cells between (col_min, row_min) and (col_max,
+    /// row_max), inclusive, which can be split into disjoint views. Fails if the region is
+    /// empty or not fully inside the grid. Marks the region as dirty.
+    pub fn view_mut(
+        &mut self,
+        col_min: usize,
+        row_min: usize,
+        col_max: usize,
+        row_max: usize,
+    ) -> Result<GridViewMut<'_, V>, GridError> {
+        self.check_region(col_min, row_min, col_max, row_max)?;
+        self.mark_dirty_edges(Some((col_min, row_min, col_max, row_max)));
+        Ok(GridViewMut {
+            data: self.data.as_mut_ptr(),
+            stride: self.rows,
+            col_min,
+            row_min,
+            columns: col_max - col_min + 1,
+            rows: row_max - row_min + 1,
+            _marker: PhantomData,
+        })
+    }
+}